@@ -0,0 +1,22 @@
+use ndarray::{Array1, Array2};
+
+// (rows, features) combinations shared by the benchmark groups
+pub const SIZES: &[(usize, usize)] = &[
+    (1_000, 8),
+    (1_000, 64),
+    (10_000, 8),
+    (10_000, 64),
+    (100_000, 8),
+    (100_000, 64),
+    (1_000_000, 8),
+];
+
+// Deterministic synthetic regression data, no RNG needed
+pub fn synthetic_data(n_samples: usize, n_features: usize) -> (Array2<f64>, Array1<f64>) {
+    let x = Array2::from_shape_fn((n_samples, n_features), |(i, j)| {
+        ((i * (j + 3) + j) as f64 * 0.37).sin()
+    });
+    let true_weights = Array1::from_shape_fn(n_features, |j| (j + 1) as f64 * 0.5);
+    let y = x.dot(&true_weights) + 2.0;
+    (x, y)
+}
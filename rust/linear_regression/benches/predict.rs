@@ -0,0 +1,50 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use linear_regression::LinearRegression;
+use std::hint::black_box;
+
+mod common;
+use common::{synthetic_data, SIZES};
+
+fn bench_predict(c: &mut Criterion) {
+    let mut group = c.benchmark_group("predict");
+
+    for &(n_samples, n_features) in SIZES {
+        let (x, y) = synthetic_data(n_samples, n_features);
+        let mut model = LinearRegression::new(n_features, 0.1);
+        model.train(&x, &y, 10).unwrap();
+
+        group.throughput(Throughput::Elements(n_samples as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", n_samples, n_features)),
+            &x,
+            |b, x| b.iter(|| black_box(model.predict(x).unwrap())),
+        );
+    }
+
+    group.finish();
+}
+
+// Latency of scoring one small batch: the vectorized predict path against
+// ndarray's generic dot on the same standard-layout input
+fn bench_single_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("predict_single_batch");
+
+    for &(n_samples, n_features) in &[(1, 16), (32, 16), (32, 128), (256, 128)] {
+        let (x, y) = synthetic_data(n_samples, n_features);
+        let mut model = LinearRegression::new(n_features, 0.1);
+        model.train(&x, &y, 10).unwrap();
+        let id = format!("{}x{}", n_samples, n_features);
+
+        group.bench_with_input(BenchmarkId::new("predict", &id), &x, |b, x| {
+            b.iter(|| black_box(model.predict(x).unwrap()))
+        });
+        group.bench_with_input(BenchmarkId::new("ndarray_dot", &id), &x, |b, x| {
+            b.iter(|| black_box(x.dot(&model.weights) + model.bias))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_predict, bench_single_batch);
+criterion_main!(benches);
@@ -0,0 +1,94 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use linear_regression::LinearRegression;
+use ndarray::{Array1, Array2};
+use std::hint::black_box;
+
+mod common;
+use common::{synthetic_data, SIZES};
+
+// The pre-buffer-reuse training loop, kept here as a baseline
+fn train_allocating(model: &mut LinearRegression, x: &Array2<f64>, y: &Array1<f64>, lr: f64, epochs: usize) -> Vec<f64> {
+    let n_samples = x.nrows() as f64;
+    let mut history = Vec::with_capacity(epochs);
+    for _ in 0..epochs {
+        let predictions = x.dot(&model.weights) + model.bias;
+        let errors = &predictions - y;
+        assert!(errors.iter().all(|e| e.is_finite()));
+        let weight_gradients = x.t().dot(&errors) * (1.0 / n_samples);
+        let bias_gradient = errors.sum() * (1.0 / n_samples);
+        model.weights = &model.weights - &(weight_gradients * lr);
+        model.bias -= bias_gradient * lr;
+        history.push(errors.mapv(|e| e * e).mean().unwrap());
+    }
+    history
+}
+
+// Many epochs over a small batch, where per-epoch allocation dominates
+fn bench_long_run(c: &mut Criterion) {
+    let (x, y) = synthetic_data(32, 4);
+    let epochs = 20_000;
+    let mut group = c.benchmark_group("train_long_run");
+    group.sample_size(20);
+
+    group.bench_function("buffered", |b| {
+        b.iter(|| {
+            let mut model = LinearRegression::new(4, 0.01);
+            black_box(model.train(&x, &y, epochs).unwrap())
+        })
+    });
+    group.bench_function("allocating_baseline", |b| {
+        b.iter(|| {
+            let mut model = LinearRegression::new(4, 0.01);
+            black_box(train_allocating(&mut model, &x, &y, 0.01, epochs))
+        })
+    });
+
+    group.finish();
+}
+
+// A single epoch, i.e. one full-batch gradient step
+fn bench_epoch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("train_epoch");
+    group.sample_size(10);
+
+    for &(n_samples, n_features) in SIZES {
+        let (x, y) = synthetic_data(n_samples, n_features);
+        group.throughput(Throughput::Elements(n_samples as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", n_samples, n_features)),
+            &(x, y),
+            |b, (x, y)| {
+                let mut model = LinearRegression::new(n_features, 0.01);
+                b.iter(|| black_box(model.train(x, y, 1).unwrap()))
+            },
+        );
+    }
+
+    group.finish();
+}
+
+// Training from scratch for enough epochs to converge on standardized data
+fn bench_convergence(c: &mut Criterion) {
+    let mut group = c.benchmark_group("train_convergence");
+    group.sample_size(10);
+
+    for &(n_samples, n_features) in SIZES.iter().filter(|&&(_, n_features)| n_features <= 8) {
+        let (x, y) = synthetic_data(n_samples, n_features);
+        group.throughput(Throughput::Elements(n_samples as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", n_samples, n_features)),
+            &(x, y),
+            |b, (x, y)| {
+                b.iter(|| {
+                    let mut model = LinearRegression::new(n_features, 0.1);
+                    black_box(model.train(x, y, 200).unwrap())
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_long_run, bench_epoch, bench_convergence);
+criterion_main!(benches);
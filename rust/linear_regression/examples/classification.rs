@@ -0,0 +1,39 @@
+// Two-class classification on the bundled exam results, whose target holds
+// the text labels "pass" and "fail". Gradient boosting on log loss is
+// compared with a linear SVM whose margins are mapped to probabilities by a
+// logistic curve (Platt scaling).
+//
+//     cargo run --example classification
+
+use linear_regression::calibration::{CalibratedClassifier, Calibration};
+use linear_regression::loader::DataLoader;
+use linear_regression::prelude::*;
+use ndarray::arr2;
+
+fn main() -> Result<(), MlError> {
+    let loaded = DataLoader::new(concat!(env!("CARGO_MANIFEST_DIR"), "/data/exams.csv"))
+        .with_target("result")
+        .load()?;
+    let labels = loaded.labels.expect("the result column holds text labels");
+    let (x, y) = (&loaded.dataset.x, &loaded.dataset.y);
+    println!("{} students, classes: {}", y.len(), labels.classes().join(", "));
+
+    let boosting = GradientBoosting::new(Loss::Logistic, 50);
+    let logistic_svm = CalibratedClassifier::new(LinearSvc::new(), Calibration::Sigmoid);
+    let mean = |scores: Vec<f64>| scores.iter().sum::<f64>() / scores.len() as f64;
+    println!("\n5-fold cross-validated accuracy:");
+    println!("gradient boosting  {:.3}", mean(cross_val_score(&boosting, x, y, 5, 0, Some(Metric::Accuracy))?));
+    println!("calibrated SVM     {:.3}", mean(cross_val_score(&logistic_svm, x, y, 5, 0, Some(Metric::Accuracy))?));
+
+    // Probabilities and class names for new students
+    let mut model = logistic_svm;
+    model.fit(x, y)?;
+    let students = arr2(&[[2.0, 6.0], [6.0, 7.5], [9.0, 8.0]]);
+    let proba = model.predict_proba(&students)?;
+    let predicted = labels.decode(&model.predict(&students)?)?;
+    println!("\nStudied  Slept  P({})  Prediction", labels.classes()[1]);
+    for ((student, p), label) in students.rows().into_iter().zip(proba.rows()).zip(predicted) {
+        println!("{:>7.1} {:>6.1} {:>8.2}  {}", student[0], student[1], p[1], label);
+    }
+    Ok(())
+}
@@ -0,0 +1,38 @@
+// Tunes the learning rate and epoch count of gradient descent linear
+// regression on the bundled housing data with a cross-validated grid search.
+//
+//     cargo run --example grid_search
+
+use linear_regression::prelude::*;
+use serde_json::Value;
+
+fn main() -> Result<(), MlError> {
+    let dataset = Dataset::from_csv(concat!(env!("CARGO_MANIFEST_DIR"), "/data/housing.csv"), "price")?;
+    // Gradient descent needs features on a common scale
+    let (_, x) = StandardScaler::fit_transform(&dataset.x)?;
+
+    let grid = ParamGrid::from([
+        ("learning_rate".to_string(), vec![Value::from(0.001), Value::from(0.01), Value::from(0.1)]),
+        ("epochs".to_string(), vec![Value::from(100), Value::from(1000)]),
+    ]);
+    let mut search = GridSearchCV::new(LinearRegression::new(x.ncols(), 0.01), grid)
+        .with_n_folds(5)
+        .with_metric(Metric::Rmse);
+    search.fit(&x, &dataset.y)?;
+
+    println!("{:<14} {:>7} {:>10} {:>8}", "learning_rate", "epochs", "RMSE", "± std");
+    for result in search.results() {
+        println!(
+            "{:<14} {:>7} {:>10.2} {:>8.2}",
+            result.params["learning_rate"].to_string(),
+            result.params["epochs"].to_string(),
+            result.mean_score,
+            result.std_score
+        );
+    }
+    let best = serde_json::to_string(search.best_params().unwrap())?;
+    println!("\nBest: {} (RMSE {:.2})", best, search.best_score().unwrap());
+    // The search refits the best settings on all rows and predicts with them
+    println!("R² of the refitted model on the training data: {:.3}", search.score(&x, &dataset.y)?);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+// Fits the bundled housing data two ways: the gradient descent pipeline
+// (standardize, then linear regression) and closed-form ridge regression,
+// and compares them by cross-validated RMSE.
+//
+//     cargo run --example pipeline_csv
+
+use linear_regression::automl::{AutoModel, Candidate};
+use linear_regression::prelude::*;
+
+fn main() -> Result<(), MlError> {
+    let dataset = Dataset::from_csv(concat!(env!("CARGO_MANIFEST_DIR"), "/data/housing.csv"), "price")?;
+    println!("{} houses, features: {}", dataset.n_samples(), dataset.feature_names.join(", "));
+
+    let (pipeline, history) = Pipeline::fit(&dataset, 0.05, 2000)?;
+    println!("Fitted: {:.3}", pipeline);
+    println!("Final training loss: {:.3}", history.train_loss[history.len() - 1]);
+    println!("1800 sqft, 3 bedrooms: ${:.1}k", pipeline.predict_one(&[1800.0, 3.0])?);
+
+    // The same linear model next to ridge with two penalties, each scored on
+    // held-out folds
+    println!("\n5-fold cross-validated RMSE (thousands of dollars):");
+    for candidate in [Candidate::Linear, Candidate::Ridge { alpha: 1.0 }, Candidate::Ridge { alpha: 10.0 }] {
+        let scores = cross_val_score(&AutoModel::new(candidate), &dataset.x, &dataset.y, 5, 0, Some(Metric::Rmse))?;
+        println!("{:<18} {:.2}", candidate.name(), scores.iter().sum::<f64>() / scores.len() as f64);
+    }
+    Ok(())
+}
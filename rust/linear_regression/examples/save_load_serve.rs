@@ -0,0 +1,28 @@
+// Trains a pipeline on the bundled housing data, saves it as JSON, loads it
+// back and checks the copy predicts the same, then shows how to serve it
+// over HTTP with the `serve` binary.
+//
+//     cargo run --example save_load_serve
+
+use linear_regression::prelude::*;
+
+fn main() -> Result<(), MlError> {
+    let dataset = Dataset::from_csv(concat!(env!("CARGO_MANIFEST_DIR"), "/data/housing.csv"), "price")?;
+    let (pipeline, _) = Pipeline::fit(&dataset, 0.05, 2000)?;
+
+    let path = std::env::temp_dir().join("housing_model.json");
+    pipeline.save(&path)?;
+    println!("Saved {:.3} to {}", pipeline, path.display());
+
+    let loaded = Pipeline::load(&path)?;
+    let (original, reloaded) = (pipeline.predict(&dataset.x)?, loaded.predict(&dataset.x)?);
+    assert_eq!(original, reloaded);
+    println!("The loaded copy gives the same {} predictions", reloaded.len());
+
+    println!("\nServe it with:");
+    println!("    cargo run --features serve --bin serve -- --model {}", path.display());
+    println!("and ask for predictions, by position or by feature name:");
+    println!("    curl -X POST http://127.0.0.1:8080/predict -H 'Content-Type: application/json' \\");
+    println!("        -d '{{\"rows\": [[1800, 3], {{\"sqft\": 2400, \"bedrooms\": 4}}]}}'");
+    Ok(())
+}
@@ -0,0 +1,33 @@
+// cargo +nightly fuzz run csv
+//
+// Arbitrary bytes as CSV, through both the plain numeric reader and the
+// schema-inferring loader under every NaN policy. Either may reject the
+// input, but neither may panic or hand back a dataset whose parts disagree.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use linear_regression::data::Dataset;
+use linear_regression::loader::{DataLoader, NanPolicy};
+
+fn check(dataset: &Dataset) {
+    assert_eq!(dataset.x.nrows(), dataset.y.len());
+    assert_eq!(dataset.x.ncols(), dataset.feature_names.len());
+    assert!(dataset.y.iter().all(|v| !v.is_nan()));
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(dataset) = Dataset::from_csv_reader(data, "y") {
+        check(&dataset);
+        assert_eq!(dataset.target_name, "y");
+    }
+    for policy in [NanPolicy::Error, NanPolicy::DropRows, NanPolicy::FillMean] {
+        if let Ok(loaded) = DataLoader::new("input.csv").with_nan_policy(policy).load_reader(data) {
+            check(&loaded.dataset);
+            assert_eq!(loaded.schema.columns.len(), loaded.dataset.n_features() + 1);
+            // Without missing values allowed, none may slip through
+            if policy != NanPolicy::FillMean {
+                assert!(loaded.dataset.x.iter().all(|v| !v.is_nan()));
+            }
+        }
+    }
+});
@@ -0,0 +1,24 @@
+// cargo +nightly fuzz run json_lines
+//
+// Arbitrary bytes as JSON Lines through the schema-inferring loader. It may
+// reject the input, but must not panic or return a dataset whose parts
+// disagree.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use linear_regression::loader::{DataLoader, NanPolicy};
+
+fuzz_target!(|data: &[u8]| {
+    for policy in [NanPolicy::Error, NanPolicy::DropRows, NanPolicy::FillMean] {
+        if let Ok(loaded) = DataLoader::new("input.jsonl").with_nan_policy(policy).load_reader(data) {
+            let dataset = &loaded.dataset;
+            assert_eq!(dataset.x.nrows(), dataset.y.len());
+            assert_eq!(dataset.x.ncols(), dataset.feature_names.len());
+            assert_eq!(loaded.schema.columns.len(), dataset.n_features() + 1);
+            assert!(dataset.y.iter().all(|v| !v.is_nan()));
+            if policy != NanPolicy::FillMean {
+                assert!(dataset.x.iter().all(|v| !v.is_nan()));
+            }
+        }
+    }
+});
@@ -0,0 +1,19 @@
+// cargo +nightly fuzz run pipeline
+//
+// Arbitrary bytes as a saved pipeline. Loading may fail, but a pipeline that
+// loads must predict, or refuse to, without panicking.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use linear_regression::pipeline::Pipeline;
+use ndarray::Array2;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(pipeline) = Pipeline::from_reader(data) {
+        let x = Array2::from_elem((2, pipeline.feature_names.len()), 1.5);
+        if let Ok(predictions) = pipeline.predict(&x) {
+            assert_eq!(predictions.len(), 2);
+        }
+        let _ = pipeline.to_string();
+    }
+});
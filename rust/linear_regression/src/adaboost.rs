@@ -0,0 +1,426 @@
+use crate::traits::{Fit, Predictor, Score};
+use crate::random::{RandomState, Seeded};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, Axis};
+use rand::distr::weighted::WeightedIndex;
+use rand::distr::Distribution;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// The `Fit` trait has no sample weights, so each round trains on a resample
+// drawn with probabilities proportional to the current weights
+fn weighted_resample(
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+    weights: &Array1<f64>,
+    rng: &mut StdRng,
+) -> Result<(Array2<f64>, Array1<f64>), LinearRegressionError> {
+    let dist = WeightedIndex::new(weights.iter())
+        .map_err(|_| LinearRegressionError::NumericalError("sample weights are degenerate"))?;
+    let indices: Vec<usize> = (0..x.nrows()).map(|_| dist.sample(rng)).collect();
+    Ok((x.select(Axis(0), &indices), y.select(Axis(0), &indices)))
+}
+
+fn check_data(x: &Array2<f64>, y: &Array1<f64>, n_estimators: usize, learning_rate: f64) -> Result<(), LinearRegressionError> {
+    if x.nrows() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.nrows(),
+            found: y.len(),
+            context: "number of samples in X and y",
+        });
+    }
+    if x.nrows() == 0 {
+        return Err(LinearRegressionError::EmptyData);
+    }
+    if n_estimators == 0 {
+        return Err(LinearRegressionError::InvalidParameter("n_estimators must be at least 1"));
+    }
+    if learning_rate.is_nan() || learning_rate <= 0.0 {
+        return Err(LinearRegressionError::InvalidParameter("learning_rate must be positive"));
+    }
+    Ok(())
+}
+
+// SAMME (multi-class AdaBoost, Zhu et al. 2009) over copies of `base`
+#[derive(Debug, Clone)]
+pub struct AdaBoostClassifier<E> {
+    base: E,
+    n_estimators: usize,
+    learning_rate: f64,
+    seed: u64,
+    classes: Vec<f64>,
+    estimators: Vec<E>,
+    estimator_weights: Vec<f64>,
+    estimator_errors: Vec<f64>,
+}
+
+impl<E: Fit + Predictor + Clone> AdaBoostClassifier<E> {
+    pub fn new(base: E, n_estimators: usize) -> Self {
+        Self {
+            base,
+            n_estimators,
+            learning_rate: 1.0,
+            seed: 0,
+            classes: Vec::new(),
+            estimators: Vec::new(),
+            estimator_weights: Vec::new(),
+            estimator_errors: Vec::new(),
+        }
+    }
+
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    pub fn estimators(&self) -> &[E] {
+        &self.estimators
+    }
+
+    // Vote weight of each round's estimator
+    pub fn estimator_weights(&self) -> &[f64] {
+        &self.estimator_weights
+    }
+
+    // Weighted training error of each round's estimator
+    pub fn estimator_errors(&self) -> &[f64] {
+        &self.estimator_errors
+    }
+
+    // Boosting stops early when an estimator is perfect on the training data
+    // or no better than chance
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        check_data(x, y, self.n_estimators, self.learning_rate)?;
+        let mut classes = y.to_vec();
+        classes.sort_by(f64::total_cmp);
+        classes.dedup();
+        if classes.len() < 2 {
+            return Err(LinearRegressionError::InvalidParameter("need at least two classes"));
+        }
+        let k = classes.len() as f64;
+
+        self.estimators.clear();
+        self.estimator_weights.clear();
+        self.estimator_errors.clear();
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut weights = Array1::from_elem(x.nrows(), 1.0 / x.nrows() as f64);
+        for _ in 0..self.n_estimators {
+            let (x_sample, y_sample) = weighted_resample(x, y, &weights, &mut rng)?;
+            let mut estimator = self.base.clone();
+            estimator.fit(&x_sample, &y_sample)?;
+
+            let missed: Array1<f64> = (&estimator.predict(x)? - y).mapv(|d| if d != 0.0 { 1.0 } else { 0.0 });
+            let error = weights.dot(&missed) / weights.sum();
+
+            if error <= 0.0 {
+                self.estimators.push(estimator);
+                self.estimator_weights.push(1.0);
+                self.estimator_errors.push(0.0);
+                break;
+            }
+            if error >= 1.0 - 1.0 / k {
+                if self.estimators.is_empty() {
+                    return Err(LinearRegressionError::NumericalError(
+                        "base estimator is no better than chance"
+                    ));
+                }
+                break;
+            }
+
+            let alpha = self.learning_rate * (((1.0 - error) / error).ln() + (k - 1.0).ln());
+            weights.zip_mut_with(&missed, |w, &m| *w *= (alpha * m).exp());
+            weights /= weights.sum();
+
+            self.estimators.push(estimator);
+            self.estimator_weights.push(alpha);
+            self.estimator_errors.push(error);
+        }
+
+        self.classes = classes;
+        Ok(())
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        self.staged_predict(x)?.pop().ok_or(LinearRegressionError::EmptyData)
+    }
+
+    // Ensemble prediction after each boosting round
+    pub fn staged_predict(&self, x: &Array2<f64>) -> Result<Vec<Array1<f64>>, LinearRegressionError> {
+        let mut votes = Array2::<f64>::zeros((x.nrows(), self.classes.len()));
+        let mut stages = Vec::with_capacity(self.estimators.len());
+        for (estimator, &alpha) in self.estimators.iter().zip(&self.estimator_weights) {
+            for (i, label) in estimator.predict(x)?.iter().enumerate() {
+                if let Ok(class) = self.classes.binary_search_by(|c| c.total_cmp(label)) {
+                    votes[[i, class]] += alpha;
+                }
+            }
+            stages.push(
+                votes
+                    .rows()
+                    .into_iter()
+                    .map(|row| {
+                        let best = (1..row.len()).fold(0, |best, c| if row[c] > row[best] { c } else { best });
+                        self.classes[best]
+                    })
+                    .collect(),
+            );
+        }
+        Ok(stages)
+    }
+}
+
+impl<E> Seeded for AdaBoostClassifier<E> {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegressionLoss {
+    Linear,
+    Square,
+    Exponential,
+}
+
+// AdaBoost.R2 (Drucker, 1997) over copies of `base`; the ensemble predicts
+// the weighted median of its members
+#[derive(Debug, Clone)]
+pub struct AdaBoostRegressor<E> {
+    base: E,
+    n_estimators: usize,
+    learning_rate: f64,
+    loss: RegressionLoss,
+    seed: u64,
+    estimators: Vec<E>,
+    estimator_weights: Vec<f64>,
+    estimator_errors: Vec<f64>,
+}
+
+impl<E: Fit + Predictor + Clone> AdaBoostRegressor<E> {
+    pub fn new(base: E, n_estimators: usize) -> Self {
+        Self {
+            base,
+            n_estimators,
+            learning_rate: 1.0,
+            loss: RegressionLoss::Linear,
+            seed: 0,
+            estimators: Vec::new(),
+            estimator_weights: Vec::new(),
+            estimator_errors: Vec::new(),
+        }
+    }
+
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    pub fn with_loss(mut self, loss: RegressionLoss) -> Self {
+        self.loss = loss;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn estimators(&self) -> &[E] {
+        &self.estimators
+    }
+
+    pub fn estimator_weights(&self) -> &[f64] {
+        &self.estimator_weights
+    }
+
+    // Weighted average loss of each round's estimator, in [0, 1]
+    pub fn estimator_errors(&self) -> &[f64] {
+        &self.estimator_errors
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        check_data(x, y, self.n_estimators, self.learning_rate)?;
+        self.estimators.clear();
+        self.estimator_weights.clear();
+        self.estimator_errors.clear();
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut weights = Array1::from_elem(x.nrows(), 1.0 / x.nrows() as f64);
+        for _ in 0..self.n_estimators {
+            let (x_sample, y_sample) = weighted_resample(x, y, &weights, &mut rng)?;
+            let mut estimator = self.base.clone();
+            estimator.fit(&x_sample, &y_sample)?;
+
+            let abs_errors = (&estimator.predict(x)? - y).mapv(f64::abs);
+            let max_error = abs_errors.fold(0.0f64, |m, &e| m.max(e));
+            if max_error <= 0.0 {
+                self.estimators.push(estimator);
+                self.estimator_weights.push(1.0);
+                self.estimator_errors.push(0.0);
+                break;
+            }
+
+            let losses = abs_errors.mapv(|e| {
+                let l = e / max_error;
+                match self.loss {
+                    RegressionLoss::Linear => l,
+                    RegressionLoss::Square => l * l,
+                    RegressionLoss::Exponential => 1.0 - (-l).exp(),
+                }
+            });
+            let average = weights.dot(&losses) / weights.sum();
+            if average >= 0.5 {
+                if self.estimators.is_empty() {
+                    return Err(LinearRegressionError::NumericalError(
+                        "base estimator's average loss is at least 0.5"
+                    ));
+                }
+                break;
+            }
+
+            let beta = average / (1.0 - average);
+            let alpha = self.learning_rate * (1.0 / beta).ln();
+            weights.zip_mut_with(&losses, |w, &l| *w *= beta.powf((1.0 - l) * self.learning_rate));
+            weights /= weights.sum();
+
+            self.estimators.push(estimator);
+            self.estimator_weights.push(alpha);
+            self.estimator_errors.push(average);
+        }
+        Ok(())
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        self.staged_predict(x)?.pop().ok_or(LinearRegressionError::EmptyData)
+    }
+
+    // Ensemble prediction after each boosting round
+    pub fn staged_predict(&self, x: &Array2<f64>) -> Result<Vec<Array1<f64>>, LinearRegressionError> {
+        let mut members: Vec<Array1<f64>> = Vec::with_capacity(self.estimators.len());
+        let mut stages = Vec::with_capacity(self.estimators.len());
+        for estimator in &self.estimators {
+            members.push(estimator.predict(x)?);
+            let weights = &self.estimator_weights[..members.len()];
+            stages.push(
+                (0..x.nrows())
+                    .map(|i| {
+                        let values: Vec<f64> = members.iter().map(|m| m[i]).collect();
+                        weighted_median(&values, weights)
+                    })
+                    .collect(),
+            );
+        }
+        Ok(stages)
+    }
+}
+
+impl<E> Seeded for AdaBoostRegressor<E> {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+// Smallest value whose cumulative weight reaches half the total
+fn weighted_median(values: &[f64], weights: &[f64]) -> f64 {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let half = weights.iter().sum::<f64>() / 2.0;
+    let mut cumulative = 0.0;
+    for &i in &order {
+        cumulative += weights[i];
+        if cumulative >= half {
+            return values[i];
+        }
+    }
+    values[order[order.len() - 1]]
+}
+
+impl<E: Fit + Predictor + Clone> Fit for AdaBoostClassifier<E> {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        AdaBoostClassifier::fit(self, x, y)
+    }
+}
+
+impl<E: Fit + Predictor + Clone> Predictor for AdaBoostClassifier<E> {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        AdaBoostClassifier::predict(self, x)
+    }
+}
+
+impl<E: Fit + Predictor + Clone> Score for AdaBoostClassifier<E> {
+    fn is_classifier(&self) -> bool {
+        true
+    }
+}
+
+impl<E: Fit + Predictor + Clone> Fit for AdaBoostRegressor<E> {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        AdaBoostRegressor::fit(self, x, y)
+    }
+}
+
+impl<E: Fit + Predictor + Clone> Predictor for AdaBoostRegressor<E> {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        AdaBoostRegressor::predict(self, x)
+    }
+}
+
+impl<E: Fit + Predictor + Clone> Score for AdaBoostRegressor<E> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::mean_squared_error;
+    use crate::tree::{Criterion, DecisionTree};
+
+    #[test]
+    fn test_adaboost_classifier() -> Result<(), LinearRegressionError> {
+        // A diagonal boundary that no single axis-aligned stump can follow
+        let x = Array2::from_shape_fn((80, 2), |(i, j)| if j == 0 { (i % 9) as f64 } else { (i / 9) as f64 });
+        let y = Array1::from_shape_fn(80, |i| if x[[i, 0]] + x[[i, 1]] > 8.0 { 1.0 } else { 0.0 });
+
+        let stump = DecisionTree::new(Criterion::Gini).with_max_depth(1);
+        let mut single = stump.clone();
+        single.fit(&x, &y)?;
+        let single_errors = (&single.predict(&x)? - &y).iter().filter(|d| **d != 0.0).count();
+
+        let mut model = AdaBoostClassifier::new(stump, 50).with_seed(4);
+        model.fit(&x, &y)?;
+        let errors = (&model.predict(&x)? - &y).iter().filter(|d| **d != 0.0).count();
+        assert!(errors * 3 < single_errors);
+
+        let stages = model.staged_predict(&x)?;
+        assert_eq!(stages.len(), model.estimators().len());
+        assert_eq!(stages[stages.len() - 1], model.predict(&x)?);
+        assert!(model.estimator_weights().iter().all(|&w| w > 0.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_adaboost_regressor() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((60, 1), |(i, _)| i as f64 / 10.0);
+        let y = x.column(0).mapv(|v| v.sin());
+
+        let base = DecisionTree::new(Criterion::Mse).with_max_depth(2);
+        let mut single = base.clone();
+        single.fit(&x, &y)?;
+        let single_mse = mean_squared_error(&single.predict(&x)?, &y);
+
+        let mut model = AdaBoostRegressor::new(base, 30).with_loss(RegressionLoss::Square).with_seed(1);
+        model.fit(&x, &y)?;
+        assert!(mean_squared_error(&model.predict(&x)?, &y) < single_mse / 2.0);
+        assert!(model.estimator_errors().iter().all(|&e| e < 0.5));
+        assert_eq!(weighted_median(&[3.0, 1.0, 2.0], &[1.0, 1.0, 5.0]), 2.0);
+        Ok(())
+    }
+}
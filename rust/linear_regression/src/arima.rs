@@ -0,0 +1,393 @@
+use crate::linalg::inverse;
+use crate::optim::nelder_mead;
+use crate::stats::normal_ppf;
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2};
+
+// How the ARMA coefficients of the differenced series are estimated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArimaMethod {
+    // Conditional sum of squares: minimize the squared one-step errors,
+    // treating the first p observations and all pre-sample errors as fixed
+    Css,
+    // Exact Gaussian likelihood from a Kalman filter on the state-space form,
+    // started from the CSS estimates
+    Mle,
+}
+
+// Point forecasts with (1 - alpha) intervals, one entry per step ahead
+#[derive(Debug, Clone)]
+pub struct Forecast {
+    pub mean: Array1<f64>,
+    pub lower: Array1<f64>,
+    pub upper: Array1<f64>,
+}
+
+// ARIMA(p, d, q): after differencing d times, the series w follows
+// φ(B)(w_t - μ) = θ(B)ε_t with φ(B) = 1 - φ₁B - … - φ_pB^p and
+// θ(B) = 1 + θ₁B + … + θ_qB^q. The mean μ is only estimated when d = 0.
+// Estimates are restricted to stationary AR and invertible MA polynomials.
+#[derive(Debug, Clone)]
+pub struct Arima {
+    p: usize,
+    d: usize,
+    q: usize,
+    method: ArimaMethod,
+    max_iter: usize,
+    ar: Array1<f64>,
+    ma: Array1<f64>,
+    mean: f64,
+    sigma2: f64,
+    log_likelihood: f64,
+    // One-step errors of the differenced series, zero for the first p
+    residuals: Array1<f64>,
+    // Centred differenced series, kept to start the forecast recursion
+    centred: Vec<f64>,
+    // Last value of the series after 0, 1, …, d - 1 differences
+    tails: Vec<f64>,
+}
+
+impl Arima {
+    pub fn new(p: usize, d: usize, q: usize) -> Self {
+        Self {
+            p,
+            d,
+            q,
+            method: ArimaMethod::Css,
+            max_iter: 2000,
+            ar: Array1::zeros(0),
+            ma: Array1::zeros(0),
+            mean: 0.0,
+            sigma2: 0.0,
+            log_likelihood: f64::NAN,
+            residuals: Array1::zeros(0),
+            centred: Vec::new(),
+            tails: Vec::new(),
+        }
+    }
+
+    pub fn with_method(mut self, method: ArimaMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    // Nelder-Mead iterations for each optimization
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    pub fn ar(&self) -> &Array1<f64> {
+        &self.ar
+    }
+
+    pub fn ma(&self) -> &Array1<f64> {
+        &self.ma
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    // Innovation variance
+    pub fn sigma2(&self) -> f64 {
+        self.sigma2
+    }
+
+    pub fn residuals(&self) -> &Array1<f64> {
+        &self.residuals
+    }
+
+    // Gaussian log-likelihood at the estimates: conditional for CSS, exact
+    // for MLE
+    pub fn log_likelihood(&self) -> f64 {
+        self.log_likelihood
+    }
+
+    pub fn aic(&self) -> f64 {
+        let n_params = self.p + self.q + 1 + usize::from(self.d == 0);
+        -2.0 * self.log_likelihood + 2.0 * n_params as f64
+    }
+
+    pub fn fit(&mut self, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        let (p, q) = (self.p, self.q);
+        if y.len() <= self.d + p + q + 1 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if y.iter().any(|v| !v.is_finite()) {
+            return Err(LinearRegressionError::InvalidParameter("series must be finite"));
+        }
+
+        let mut series = y.to_vec();
+        let mut tails = Vec::with_capacity(self.d);
+        for _ in 0..self.d {
+            tails.push(series[series.len() - 1]);
+            series = series.windows(2).map(|w| w[1] - w[0]).collect();
+        }
+        let mean = if self.d == 0 { series.iter().sum::<f64>() / series.len() as f64 } else { 0.0 };
+        let centred: Vec<f64> = series.iter().map(|v| v - mean).collect();
+
+        let admissible = |params: &[f64]| {
+            let ma: Vec<f64> = params[p..].iter().map(|t| -t).collect();
+            is_stationary(&params[..p]) && is_stationary(&ma)
+        };
+        let css = |params: &[f64]| {
+            if !admissible(params) {
+                return f64::INFINITY;
+            }
+            let residuals = arma_residuals(&centred, &params[..p], &params[p..]);
+            residuals[p..].iter().map(|e| e * e).sum::<f64>()
+        };
+        let (mut params, mut objective) = nelder_mead(css, &vec![0.0; p + q], 0.1, self.max_iter);
+        if !objective.is_finite() {
+            return Err(LinearRegressionError::NumericalError("CSS objective is not finite"));
+        }
+
+        let m = centred.len();
+        let (sigma2, log_likelihood) = match self.method {
+            ArimaMethod::Css => {
+                let n = (m - p) as f64;
+                let sigma2 = objective / n;
+                (sigma2, -0.5 * n * ((2.0 * std::f64::consts::PI * sigma2).ln() + 1.0))
+            }
+            ArimaMethod::Mle => {
+                let exact = |params: &[f64]| {
+                    if !admissible(params) {
+                        return f64::INFINITY;
+                    }
+                    match kalman_filter(&centred, &params[..p], &params[p..]) {
+                        Some((sum_squares, sum_log_f)) => m as f64 * (sum_squares / m as f64).ln() + sum_log_f,
+                        None => f64::INFINITY,
+                    }
+                };
+                (params, objective) = nelder_mead(exact, &params, 0.05, self.max_iter);
+                let (sum_squares, _) = kalman_filter(&centred, &params[..p], &params[p..])
+                    .ok_or(LinearRegressionError::NumericalError("Kalman filter failed at the estimates"))?;
+                let sigma2 = sum_squares / m as f64;
+                let log_likelihood = -0.5 * (objective + m as f64 * ((2.0 * std::f64::consts::PI).ln() + 1.0));
+                (sigma2, log_likelihood)
+            }
+        };
+        let residuals = arma_residuals(&centred, &params[..p], &params[p..]);
+
+        self.ar = Array1::from(params[..p].to_vec());
+        self.ma = Array1::from(params[p..].to_vec());
+        self.mean = mean;
+        self.sigma2 = sigma2;
+        self.log_likelihood = log_likelihood;
+        self.residuals = Array1::from(residuals);
+        self.centred = centred;
+        self.tails = tails;
+        Ok(())
+    }
+
+    // Point forecasts for the next `horizon` values of the original series
+    pub fn forecast(&self, horizon: usize) -> Result<Array1<f64>, LinearRegressionError> {
+        if self.centred.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+
+        // ARMA recursion with future errors at zero
+        let mut z = self.centred.clone();
+        let mut e = self.residuals.to_vec();
+        let n = z.len();
+        for t in n..n + horizon {
+            let ar: f64 = self.ar.iter().enumerate().map(|(i, phi)| phi * z[t - 1 - i]).sum();
+            let ma: f64 = self.ma.iter().enumerate().filter(|&(j, _)| t > j).map(|(j, th)| th * e[t - 1 - j]).sum();
+            z.push(ar + ma);
+            e.push(0.0);
+        }
+        let mut forecast: Vec<f64> = z[n..].iter().map(|v| v + self.mean).collect();
+
+        // Undo the differencing, innermost first
+        for &last in self.tails.iter().rev() {
+            let mut level = last;
+            for v in forecast.iter_mut() {
+                level += *v;
+                *v = level;
+            }
+        }
+        Ok(Array1::from(forecast))
+    }
+
+    // Forecasts with (1 - alpha) normal intervals. The h-step variance is
+    // σ² Σ_{j<h} ψ_j², with ψ the MA(∞) weights of φ(B)(1 - B)^d against θ(B);
+    // parameter uncertainty is ignored.
+    pub fn forecast_interval(&self, horizon: usize, alpha: f64) -> Result<Forecast, LinearRegressionError> {
+        if !(alpha > 0.0 && alpha < 1.0) {
+            return Err(LinearRegressionError::InvalidParameter("alpha must be in (0, 1)"));
+        }
+        let mean = self.forecast(horizon)?;
+
+        // Coefficients of φ(B)(1 - B)^d as 1 - Σ φ*_i B^i
+        let mut polynomial: Vec<f64> = std::iter::once(1.0).chain(self.ar.iter().map(|phi| -phi)).collect();
+        for _ in 0..self.d {
+            let mut next = vec![0.0; polynomial.len() + 1];
+            for (i, c) in polynomial.iter().enumerate() {
+                next[i] += c;
+                next[i + 1] -= c;
+            }
+            polynomial = next;
+        }
+        let mut psi = vec![1.0; horizon.max(1)];
+        for j in 1..horizon {
+            let theta = if j <= self.q { self.ma[j - 1] } else { 0.0 };
+            psi[j] = theta - (1..polynomial.len().min(j + 1)).map(|i| polynomial[i] * psi[j - i]).sum::<f64>();
+        }
+
+        let z = normal_ppf(1.0 - alpha / 2.0);
+        let mut variance = 0.0;
+        let mut lower = Array1::zeros(horizon);
+        let mut upper = Array1::zeros(horizon);
+        for h in 0..horizon {
+            variance += self.sigma2 * psi[h] * psi[h];
+            let half_width = z * variance.sqrt();
+            lower[h] = mean[h] - half_width;
+            upper[h] = mean[h] + half_width;
+        }
+        Ok(Forecast { mean, lower, upper })
+    }
+}
+
+// e_t = z_t - Σ φ_i z_{t-i} - Σ θ_j e_{t-j} from t = p on, with earlier
+// errors taken as zero
+fn arma_residuals(z: &[f64], ar: &[f64], ma: &[f64]) -> Vec<f64> {
+    let p = ar.len();
+    let mut e = vec![0.0; z.len()];
+    for t in p..z.len() {
+        let fitted: f64 = ar.iter().enumerate().map(|(i, phi)| phi * z[t - 1 - i]).sum::<f64>()
+            + ma.iter().enumerate().filter(|&(j, _)| t > j).map(|(j, th)| th * e[t - 1 - j]).sum::<f64>();
+        e[t] = z[t] - fitted;
+    }
+    e
+}
+
+// Whether 1 - c₁B - … - c_kB^k has all its roots outside the unit circle,
+// by stepping the Durbin-Levinson recursion down to the partial
+// autocorrelations, which must all lie inside (-1, 1)
+fn is_stationary(coefficients: &[f64]) -> bool {
+    let mut a = coefficients.to_vec();
+    while let Some(&r) = a.last() {
+        if r.is_nan() || r.abs() >= 1.0 {
+            return false;
+        }
+        let k = a.len() - 1;
+        a = (0..k).map(|j| (a[j] + r * a[k - 1 - j]) / (1.0 - r * r)).collect();
+    }
+    true
+}
+
+// Kalman filter on the Harvey state-space form with unit innovation
+// variance. Returns Σ v_t² / F_t and Σ ln F_t over the prediction errors v_t
+// with variances F_t, from which σ² is concentrated out of the likelihood.
+fn kalman_filter(z: &[f64], ar: &[f64], ma: &[f64]) -> Option<(f64, f64)> {
+    let r = ar.len().max(ma.len() + 1);
+    let mut transition = Array2::<f64>::zeros((r, r));
+    for (i, &phi) in ar.iter().enumerate() {
+        transition[[i, 0]] = phi;
+    }
+    for i in 0..r - 1 {
+        transition[[i, i + 1]] = 1.0;
+    }
+    let mut loading = Array1::<f64>::zeros(r);
+    loading[0] = 1.0;
+    for (j, &theta) in ma.iter().enumerate() {
+        loading[j + 1] = theta;
+    }
+    let noise = Array2::from_shape_fn((r, r), |(i, j)| loading[i] * loading[j]);
+
+    // Stationary state covariance from vec(P) = (I - T ⊗ T)⁻¹ vec(RR')
+    let kron = Array2::from_shape_fn((r * r, r * r), |(a, b)| {
+        let identity = if a == b { 1.0 } else { 0.0 };
+        identity - transition[[a / r, b / r]] * transition[[a % r, b % r]]
+    });
+    let vec_p = inverse(&kron).ok()?.dot(&Array1::from_iter(noise.iter().copied()));
+    let mut covariance = Array2::from_shape_vec((r, r), vec_p.to_vec()).ok()?;
+
+    let mut state = Array1::<f64>::zeros(r);
+    let (mut sum_squares, mut sum_log_f) = (0.0, 0.0);
+    for &observation in z {
+        let v = observation - state[0];
+        let f = covariance[[0, 0]];
+        if f.is_nan() || f <= 0.0 {
+            return None;
+        }
+        sum_squares += v * v / f;
+        sum_log_f += f.ln();
+
+        let gain = transition.dot(&covariance.column(0)) / f;
+        state = transition.dot(&state) + &gain * v;
+        covariance = transition.dot(&covariance).dot(&transition.t()) + &noise
+            - Array2::from_shape_fn((r, r), |(i, j)| gain[i] * gain[j] * f);
+    }
+    Some((sum_squares, sum_log_f))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    // ARMA(1, 1) with φ = 0.6, θ = 0.3 and unit Gaussian innovations
+    fn simulate(n: usize) -> Array1<f64> {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut normal = || {
+            let (u, v): (f64, f64) = (rng.random_range(1e-12..1.0), rng.random());
+            (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+        };
+        let (mut y, mut e) = (0.0, 0.0);
+        (0..n + 100)
+            .map(|_| {
+                let shock = normal();
+                y = 0.6 * y + shock + 0.3 * e;
+                e = shock;
+                y + 5.0
+            })
+            .skip(100)
+            .collect()
+    }
+
+    #[test]
+    fn test_arma_estimation() -> Result<(), LinearRegressionError> {
+        let y = simulate(800);
+        for method in [ArimaMethod::Css, ArimaMethod::Mle] {
+            let mut model = Arima::new(1, 0, 1).with_method(method);
+            model.fit(&y)?;
+            assert!((model.ar()[0] - 0.6).abs() < 0.1);
+            assert!((model.ma()[0] - 0.3).abs() < 0.1);
+            assert!((model.sigma2() - 1.0).abs() < 0.15);
+            assert!((model.mean() - 5.0).abs() < 0.5);
+        }
+        assert!(Arima::new(1, 0, 1).forecast(3).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_integrated_forecast() -> Result<(), LinearRegressionError> {
+        // A random walk forecasts its last value, with variance growing
+        // linearly in the horizon
+        let walk = simulate(300).mapv(|v| v - 5.0);
+        let walk: Array1<f64> = walk
+            .iter()
+            .scan(0.0, |level, v| {
+                *level += v;
+                Some(*level)
+            })
+            .collect();
+        let mut model = Arima::new(0, 1, 0);
+        model.fit(&walk)?;
+        let forecast = model.forecast_interval(4, 0.05)?;
+        assert!(forecast.mean.iter().all(|&v| (v - walk[299]).abs() < 1e-12));
+        let widths = &forecast.upper - &forecast.lower;
+        assert!((widths[3] / widths[0] - 2.0).abs() < 1e-9);
+
+        let mut model = Arima::new(1, 1, 1).with_method(ArimaMethod::Mle);
+        model.fit(&walk)?;
+        let forecast = model.forecast_interval(10, 0.1)?;
+        assert_eq!(forecast.mean.len(), 10);
+        let widths = &forecast.upper - &forecast.lower;
+        assert!(widths.windows(2).into_iter().all(|w| w[1] > w[0]));
+        Ok(())
+    }
+}
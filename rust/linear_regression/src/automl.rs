@@ -0,0 +1,280 @@
+use crate::data::Dataset;
+use crate::forest::RandomForest;
+use crate::linalg::{cholesky, cholesky_solve};
+use crate::model_selection::cross_val_score;
+use crate::preprocessing::StandardScaler;
+use crate::random::RandomState;
+use crate::traits::{Fit, Predictor, Score};
+use crate::tree::{Criterion, DecisionTree};
+use crate::{LinearRegression, LinearRegressionError};
+use ndarray::{Array1, Array2, Axis};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// One entry of the `auto_fit` portfolio. Linear candidates standardize their
+// inputs (after the polynomial expansion, if any); trees use them as given.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Candidate {
+    // Gradient descent on the crate's own model
+    Linear,
+    // Closed-form L2-penalized least squares
+    Ridge { alpha: f64 },
+    // Ridge on every product of up to `degree` features
+    PolynomialRidge { degree: usize, alpha: f64 },
+    Tree { max_depth: usize },
+    Forest { n_trees: usize },
+}
+
+impl Candidate {
+    // Cheapest first, which is the order `auto_fit` tries them in
+    pub fn portfolio() -> Vec<Candidate> {
+        vec![
+            Self::Linear,
+            Self::Ridge { alpha: 1.0 },
+            Self::PolynomialRidge { degree: 2, alpha: 1.0 },
+            Self::Tree { max_depth: 6 },
+            Self::Forest { n_trees: 50 },
+        ]
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::Linear => "linear".to_string(),
+            Self::Ridge { alpha } => format!("ridge(alpha={})", alpha),
+            Self::PolynomialRidge { degree, alpha } => format!("poly{}+ridge(alpha={})", degree, alpha),
+            Self::Tree { max_depth } => format!("tree(max_depth={})", max_depth),
+            Self::Forest { n_trees } => format!("forest(n_trees={})", n_trees),
+        }
+    }
+}
+
+// Column index lists of every monomial of degree 1 to `degree`, each list
+// non-decreasing so every product appears once
+fn monomials(n_features: usize, degree: usize) -> Vec<Vec<usize>> {
+    let mut all = Vec::new();
+    let mut current: Vec<Vec<usize>> = (0..n_features).map(|j| vec![j]).collect();
+    for _ in 0..degree {
+        all.extend(current.iter().cloned());
+        current = current
+            .iter()
+            .flat_map(|m| (*m.last().unwrap()..n_features).map(move |j| [m.as_slice(), &[j]].concat()))
+            .collect();
+    }
+    all
+}
+
+fn expand(x: &Array2<f64>, terms: &[Vec<usize>]) -> Array2<f64> {
+    Array2::from_shape_fn((x.nrows(), terms.len()), |(i, t)| terms[t].iter().map(|&j| x[[i, j]]).product())
+}
+
+#[derive(Debug, Clone)]
+enum Fitted {
+    Linear { terms: Option<Vec<Vec<usize>>>, scaler: StandardScaler, model: LinearRegression },
+    Tree(DecisionTree),
+    Forest(RandomForest),
+}
+
+// A portfolio candidate with its preprocessing, fitted as one model
+#[derive(Debug, Clone)]
+pub struct AutoModel {
+    candidate: Candidate,
+    fitted: Option<Fitted>,
+}
+
+impl AutoModel {
+    pub fn new(candidate: Candidate) -> Self {
+        Self { candidate, fitted: None }
+    }
+
+    pub fn candidate(&self) -> Candidate {
+        self.candidate
+    }
+
+    fn fit_linear(
+        &self,
+        x: &Array2<f64>,
+        y: &Array1<f64>,
+        terms: Option<Vec<Vec<usize>>>,
+        alpha: Option<f64>,
+    ) -> Result<Fitted, LinearRegressionError> {
+        let expanded = terms.as_ref().map(|terms| expand(x, terms));
+        let (scaler, x_norm) = StandardScaler::fit_transform(expanded.as_ref().unwrap_or(x))?;
+        let model = match alpha {
+            None => {
+                let mut model = LinearRegression::new(x_norm.ncols(), 0.1);
+                model.train(&x_norm, y, 1000)?;
+                model
+            }
+            // Centered inputs and target, so the intercept is the target mean
+            // and stays out of the penalty
+            Some(alpha) => {
+                let mean = y.mean().ok_or(LinearRegressionError::EmptyData)?;
+                let mut gram = x_norm.t().dot(&x_norm);
+                gram.diag_mut().mapv_inplace(|d| d + alpha);
+                let rhs = x_norm.t().dot(&(y - mean)).insert_axis(Axis(1));
+                let weights = cholesky_solve(&cholesky(&gram)?, &rhs).column(0).to_owned();
+                LinearRegression::from_parameters(weights, mean)
+            }
+        };
+        Ok(Fitted::Linear { terms, scaler, model })
+    }
+}
+
+impl Fit for AutoModel {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        let fitted = match self.candidate {
+            Candidate::Linear => self.fit_linear(x, y, None, None)?,
+            Candidate::Ridge { alpha } => self.fit_linear(x, y, None, Some(alpha))?,
+            Candidate::PolynomialRidge { degree, alpha } => {
+                self.fit_linear(x, y, Some(monomials(x.ncols(), degree)), Some(alpha))?
+            }
+            Candidate::Tree { max_depth } => {
+                let mut tree = DecisionTree::new(Criterion::Mse).with_max_depth(max_depth);
+                tree.fit(x, y)?;
+                Fitted::Tree(tree)
+            }
+            Candidate::Forest { n_trees } => {
+                let mut forest = RandomForest::new(Criterion::Mse, n_trees);
+                forest.fit(x, y)?;
+                Fitted::Forest(forest)
+            }
+        };
+        self.fitted = Some(fitted);
+        Ok(())
+    }
+}
+
+impl Predictor for AutoModel {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        match &self.fitted {
+            None => Err(LinearRegressionError::NotFitted),
+            Some(Fitted::Linear { terms: Some(terms), scaler, model }) => {
+                model.predict(&scaler.transform(&expand(x, terms))?)
+            }
+            Some(Fitted::Linear { terms: None, scaler, model }) => model.predict(&scaler.transform(x)?),
+            Some(Fitted::Tree(tree)) => tree.predict(x),
+            Some(Fitted::Forest(forest)) => forest.predict(x),
+        }
+    }
+}
+
+impl Score for AutoModel {}
+
+#[derive(Debug, Clone)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    // Cross-validated R²
+    pub mean_score: f64,
+    pub std_score: f64,
+    // Time spent cross-validating this candidate
+    pub elapsed: Duration,
+}
+
+// Candidates tried by `auto_fit`, best first
+#[derive(Debug, Clone)]
+pub struct Leaderboard {
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name_width = self.entries.iter().map(|e| e.name.chars().count()).max().unwrap_or(0).max(5);
+        writeln!(f, "{:<4} {:<name_width$} {:>10} {:>10} {:>10}", "Rank", "Model", "R²", "± std", "Time (s)")?;
+        for (rank, entry) in self.entries.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<4} {:<name_width$} {:>10.4} {:>10.4} {:>10.3}",
+                rank + 1,
+                entry.name,
+                entry.mean_score,
+                entry.std_score,
+                entry.elapsed.as_secs_f64()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// A decent regression model without any tuning: each portfolio candidate is
+// scored by 5-fold cross-validated R² (fewer folds on tiny datasets), cheapest
+// first, until `budget` runs out; the first is always tried. Folds are
+// shuffled with `state`'s seed. A deterministic `state` (or a budget of
+// `Duration::MAX`) tries them all, so the choice does not depend on the
+// machine. The best one is refitted on the whole dataset.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(n_samples = dataset.n_samples())
+))]
+pub fn auto_fit(
+    dataset: &Dataset,
+    budget: Duration,
+    state: RandomState,
+) -> Result<(AutoModel, Leaderboard), LinearRegressionError> {
+    let (x, y) = (&dataset.x, &dataset.y);
+    if x.nrows() < 2 {
+        return Err(LinearRegressionError::EmptyData);
+    }
+    let n_folds = x.nrows().min(5);
+    let start = Instant::now();
+    let mut entries = Vec::new();
+    for candidate in Candidate::portfolio() {
+        if !entries.is_empty() && !state.is_deterministic() && start.elapsed() >= budget {
+            break;
+        }
+        let tried = Instant::now();
+        // A candidate that cannot be fitted on this data is left out
+        let Ok(scores) = cross_val_score(&AutoModel::new(candidate), x, y, n_folds, state.seed(), None) else {
+            continue;
+        };
+        let mean_score = scores.iter().sum::<f64>() / scores.len() as f64;
+        let std_score = (scores.iter().map(|s| (s - mean_score).powi(2)).sum::<f64>() / scores.len() as f64).sqrt();
+        let entry = LeaderboardEntry { name: candidate.name(), mean_score, std_score, elapsed: tried.elapsed() };
+        #[cfg(feature = "tracing")]
+        tracing::info!(candidate = %entry.name, mean_score, elapsed = ?entry.elapsed, "candidate scored");
+        entries.push((candidate, entry));
+    }
+    // NaN scores rank last
+    entries.sort_by(|(_, a), (_, b)| match (a.mean_score.is_nan(), b.mean_score.is_nan()) {
+        (false, false) => b.mean_score.total_cmp(&a.mean_score),
+        (a_nan, b_nan) => a_nan.cmp(&b_nan),
+    });
+    let best = entries.first().ok_or(LinearRegressionError::NumericalError("no candidate could be fitted"))?.0;
+
+    let mut model = AutoModel::new(best);
+    model.fit(x, y)?;
+    Ok((model, Leaderboard { entries: entries.into_iter().map(|(_, entry)| entry).collect() }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_fit() -> Result<(), LinearRegressionError> {
+        assert_eq!(monomials(2, 2), vec![vec![0], vec![1], vec![0, 0], vec![0, 1], vec![1, 1]]);
+
+        // A product of the two features, which only the polynomial model and
+        // the trees can represent
+        let x = Array2::from_shape_fn((80, 2), |(i, j)| ((i * (j + 3)) % 17) as f64 / 4.0 - 2.0);
+        let y = &x.column(0) * &x.column(1) + 1.0;
+        let dataset = Dataset {
+            x,
+            y,
+            feature_names: vec!["a".to_string(), "b".to_string()],
+            target_name: "y".to_string(),
+        };
+        let (model, leaderboard) = auto_fit(&dataset, Duration::from_secs(60), RandomState::default())?;
+
+        assert_eq!(leaderboard.entries.len(), 5);
+        assert_eq!(model.candidate(), Candidate::PolynomialRidge { degree: 2, alpha: 1.0 });
+        assert!(leaderboard.entries[0].mean_score > 0.99);
+        assert!(model.score(&dataset.x, &dataset.y)? > 0.99);
+        assert!(leaderboard.to_string().lines().nth(1).unwrap().contains("poly2+ridge"));
+
+        // With no time to spare only the first candidate is tried
+        let (_, quick) = auto_fit(&dataset, Duration::ZERO, RandomState::default())?;
+        assert_eq!(quick.entries.len(), 1);
+        Ok(())
+    }
+}
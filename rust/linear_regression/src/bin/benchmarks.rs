@@ -0,0 +1,141 @@
+use clap::Parser;
+use linear_regression::automl::{AutoModel, Candidate};
+use linear_regression::data::Task;
+use linear_regression::discriminant::LinearDiscriminant;
+use linear_regression::prelude::*;
+use ndarray::{Array1, Array2};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Parser)]
+#[command(name = "benchmarks", about = "Time and score every model on a fixed suite of synthetic tasks")]
+struct Args {
+    /// CSV file results are appended to, so runs can be compared over time
+    #[arg(long, default_value = "benchmarks.csv")]
+    out: PathBuf,
+    /// Training set sizes in rows; each task also gets a test set a quarter that size
+    #[arg(long, value_delimiter = ',', default_values_t = [500, 2000, 10000])]
+    sizes: Vec<usize>,
+    #[arg(long, default_value_t = 10)]
+    features: usize,
+    /// Only run models whose name contains this
+    #[arg(long)]
+    filter: Option<String>,
+}
+
+// Features uniform on [-1, 1] and a linear signal plus a smooth
+// nonlinearity and noise; classes are the sign of that score. The same seed
+// gives the same task on every run and machine.
+fn generate(task: Task, n_samples: usize, n_features: usize, seed: u64) -> (Array2<f64>, Array1<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let x = Array2::from_shape_fn((n_samples, n_features), |_| rng.random_range(-1.0..1.0));
+    let weights = Array1::from_shape_fn(n_features, |j| if j % 2 == 0 { 1.0 } else { -0.5 } / (j + 1) as f64);
+    let signal = x.dot(&weights) + x.column(0).mapv(|v| 0.5 * (3.0 * v).sin());
+    let noise = Array1::from_shape_fn(n_samples, |_| rng.random_range(-0.1..0.1));
+    let y = match task {
+        Task::Regression => signal + noise,
+        Task::Classification => (signal + noise).mapv(|v| if v > 0.0 { 1.0 } else { 0.0 }),
+    };
+    (x, y)
+}
+
+// Every model that handles the task, with settings fixed so results stay
+// comparable between runs
+fn models(task: Task) -> Vec<(&'static str, Box<dyn Estimator>)> {
+    match task {
+        Task::Regression => vec![
+            ("linear_regression", Box::new(AutoModel::new(Candidate::Linear))),
+            ("ridge", Box::new(AutoModel::new(Candidate::Ridge { alpha: 1.0 }))),
+            ("decision_tree", Box::new(DecisionTree::new(Criterion::Mse).with_max_depth(8))),
+            ("random_forest", Box::new(RandomForest::new(Criterion::Mse, 50))),
+            ("gradient_boosting", Box::new(GradientBoosting::new(Loss::LeastSquares, 100))),
+            ("knn", Box::new(KnnRegressor::new(5))),
+            ("mlp", Box::new(MlpRegressor::new(vec![32]))),
+            ("linear_svr", Box::new(LinearSvr::new(0.1))),
+        ],
+        Task::Classification => vec![
+            ("decision_tree", Box::new(DecisionTree::new(Criterion::Gini).with_max_depth(8))),
+            ("random_forest", Box::new(RandomForest::new(Criterion::Gini, 50))),
+            ("gradient_boosting", Box::new(GradientBoosting::new(Loss::Logistic, 100))),
+            ("knn", Box::new(KnnClassifier::new(5))),
+            ("mlp", Box::new(MlpClassifier::new(vec![32]))),
+            ("gaussian_nb", Box::new(GaussianNb::new())),
+            ("linear_svc", Box::new(LinearSvc::new())),
+            ("linear_discriminant", Box::new(LinearDiscriminant::new())),
+        ],
+    }
+}
+
+fn main() -> Result<(), MlError> {
+    let args = Args::parse();
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let is_new = !args.out.exists();
+    let file = std::fs::OpenOptions::new().create(true).append(true).open(&args.out)?;
+    let mut writer = csv::Writer::from_writer(file);
+    if is_new {
+        writer.write_record([
+            "timestamp",
+            "task",
+            "n_samples",
+            "n_features",
+            "model",
+            "fit_seconds",
+            "predict_seconds",
+            "metric",
+            "score",
+        ])?;
+    }
+
+    println!("{:<15} {:>8} {:<20} {:>10} {:>10} {:>10}", "Task", "Rows", "Model", "Fit (s)", "Pred (s)", "Score");
+    for &n_samples in &args.sizes {
+        for task in [Task::Regression, Task::Classification] {
+            let (x_train, y_train) = generate(task, n_samples, args.features, n_samples as u64);
+            let (x_test, y_test) = generate(task, (n_samples / 4).max(1), args.features, n_samples as u64 + 1);
+            // Scored by the first of the task's usual metrics: R² or accuracy
+            let metric = task.default_metrics()[0];
+            for (name, mut model) in models(task) {
+                if args.filter.as_ref().is_some_and(|filter| !name.contains(filter.as_str())) {
+                    continue;
+                }
+                // A model that fails on a task is reported and skipped, so
+                // one failure does not lose the rest of the run
+                let started = Instant::now();
+                if let Err(e) = model.fit(&x_train, &y_train) {
+                    eprintln!("{} on {} ({} rows) failed: {}", name, task, n_samples, e);
+                    continue;
+                }
+                let fit_seconds = started.elapsed().as_secs_f64();
+                let started = Instant::now();
+                let predictions = model.predict(&x_test)?;
+                let predict_seconds = started.elapsed().as_secs_f64();
+                let score = metric.evaluate(&predictions, &y_test);
+
+                println!(
+                    "{:<15} {:>8} {:<20} {:>10.4} {:>10.4} {:>10.4}",
+                    task.to_string(),
+                    n_samples,
+                    name,
+                    fit_seconds,
+                    predict_seconds,
+                    score
+                );
+                writer.write_record([
+                    timestamp.to_string(),
+                    task.to_string(),
+                    n_samples.to_string(),
+                    args.features.to_string(),
+                    name.to_string(),
+                    fit_seconds.to_string(),
+                    predict_seconds.to_string(),
+                    metric.name().to_string(),
+                    score.to_string(),
+                ])?;
+            }
+        }
+    }
+    writer.flush()?;
+    println!("Results appended to {}", args.out.display());
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use clap::Parser;
+use linear_regression::experiment::Experiment;
+use linear_regression::pipeline::Pipeline;
+use linear_regression::MlError;
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(name = "serve", about = "Serve predictions from a saved model over HTTP")]
+struct Args {
+    /// Model file written by `linear_regression train`
+    #[arg(long, required_unless_present = "config")]
+    model: Option<PathBuf>,
+    /// Experiment file whose output model to serve, as written by `linear_regression run`
+    #[arg(long, conflicts_with = "model")]
+    config: Option<PathBuf>,
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: String,
+}
+
+// Rows can be given positionally (in training column order) or keyed by feature name
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Row {
+    Values(Vec<f64>),
+    Named(HashMap<String, f64>),
+}
+
+#[derive(Deserialize)]
+struct PredictRequest {
+    rows: Vec<Row>,
+}
+
+#[derive(Serialize)]
+struct PredictResponse {
+    predictions: Vec<f64>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+type ApiError = (StatusCode, Json<ErrorResponse>);
+
+fn bad_request(error: String) -> ApiError {
+    (StatusCode::BAD_REQUEST, Json(ErrorResponse { error }))
+}
+
+// Bad values in the rows are the client's fault; anything else, such as an
+// unfitted or inconsistent model, is the server's
+fn predict_error(error: MlError) -> ApiError {
+    let status = match error {
+        MlError::EmptyData | MlError::InvalidValue { .. } => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(ErrorResponse { error: error.to_string() }))
+}
+
+fn rows_to_array(pipeline: &Pipeline, rows: Vec<Row>) -> Result<Array2<f64>, String> {
+    let n_features = pipeline.feature_names.len();
+    let mut values = Vec::with_capacity(rows.len() * n_features);
+
+    for (i, row) in rows.into_iter().enumerate() {
+        match row {
+            Row::Values(row) => {
+                if row.len() != n_features {
+                    return Err(format!("row {}: expected {} features, found {}", i, n_features, row.len()));
+                }
+                values.extend(row);
+            }
+            Row::Named(row) => {
+                for name in &pipeline.feature_names {
+                    let value = row
+                        .get(name)
+                        .ok_or_else(|| format!("row {}: missing feature '{}'", i, name))?;
+                    values.push(*value);
+                }
+            }
+        }
+    }
+
+    let n_rows = values.len() / n_features.max(1);
+    Array2::from_shape_vec((n_rows, n_features), values).map_err(|e| e.to_string())
+}
+
+async fn health() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "status": "ok" }))
+}
+
+async fn predict(
+    State(pipeline): State<Arc<Pipeline>>,
+    Json(request): Json<PredictRequest>,
+) -> Result<Json<PredictResponse>, ApiError> {
+    let x = rows_to_array(&pipeline, request.rows).map_err(bad_request)?;
+    let predictions = pipeline.predict(&x).map_err(predict_error)?;
+
+    Ok(Json(PredictResponse {
+        predictions: predictions.to_vec(),
+    }))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), MlError> {
+    let args = Args::parse();
+    let model = match (args.model, &args.config) {
+        (Some(model), _) => model,
+        (None, Some(config)) => Experiment::from_file(config)?.output.model,
+        (None, None) => unreachable!("clap requires --model or --config"),
+    };
+    let pipeline = Arc::new(Pipeline::load(&model)?);
+
+    let app = Router::new()
+        .route("/health", get(health))
+        .route("/predict", post(predict))
+        .with_state(pipeline);
+
+    let listener = tokio::net::TcpListener::bind(&args.addr).await?;
+    println!("Serving {} on http://{}", model.display(), args.addr);
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linear_regression::preprocessing::StandardScaler;
+    use linear_regression::LinearRegression;
+    use ndarray::{arr2, Array1};
+
+    fn pipeline() -> Pipeline {
+        Pipeline {
+            feature_names: vec!["sqft".to_string(), "bedrooms".to_string()],
+            target_name: "price".to_string(),
+            splines: None,
+            scaler: StandardScaler { means: Array1::zeros(2), stds: Array1::ones(2) },
+            model: LinearRegression::from_parameters(Array1::from(vec![1.0, 2.0]), 0.0),
+        }
+    }
+
+    fn rows(json: &str) -> Vec<Row> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_rows_to_array() {
+        let pipeline = pipeline();
+        let positional = rows_to_array(&pipeline, rows("[[1.0, 2.0], [3.0, 4.0]]")).unwrap();
+        assert_eq!(positional, arr2(&[[1.0, 2.0], [3.0, 4.0]]));
+        // Named rows are put in training column order
+        let named = rows_to_array(&pipeline, rows(r#"[{"bedrooms": 2.0, "sqft": 1.0, "extra": 9.0}]"#)).unwrap();
+        assert_eq!(named, arr2(&[[1.0, 2.0]]));
+
+        let missing = rows_to_array(&pipeline, rows(r#"[[1.0, 2.0], {"sqft": 1.0}]"#)).unwrap_err();
+        assert_eq!(missing, "row 1: missing feature 'bedrooms'");
+        let short = rows_to_array(&pipeline, rows("[[1.0]]")).unwrap_err();
+        assert_eq!(short, "row 0: expected 2 features, found 1");
+    }
+
+    #[test]
+    fn test_predict_error_status() {
+        let status = |error| predict_error(error).0;
+        assert_eq!(status(MlError::EmptyData), StatusCode::BAD_REQUEST);
+        assert_eq!(status(MlError::NotFitted), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(status(MlError::IncompatibleModel("weights".to_string())), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
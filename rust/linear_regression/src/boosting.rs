@@ -0,0 +1,426 @@
+use crate::traits::{Fit, Predictor, Score};
+use crate::tree::{Criterion, DecisionTree};
+use crate::random::{RandomState, Seeded};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, Axis};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loss {
+    // Regression on squared error
+    LeastSquares,
+    // Binary classification on log loss; the two labels can be any values
+    Logistic,
+}
+
+// Gradient boosted regression trees. Each round fits a shallow tree to the
+// negative gradient of the loss and adds it, shrunk by the learning rate, to
+// the running raw prediction.
+#[derive(Debug, Clone)]
+pub struct GradientBoosting {
+    loss: Loss,
+    n_estimators: usize,
+    learning_rate: f64,
+    subsample: f64,
+    patience: Option<usize>,
+    template: DecisionTree,
+    seed: u64,
+    init: f64,
+    classes: Vec<f64>,
+    trees: Vec<DecisionTree>,
+    train_loss: Vec<f64>,
+    validation_loss: Vec<f64>,
+}
+
+impl GradientBoosting {
+    // Depth-3 trees, learning rate 0.1 and no subsampling by default
+    pub fn new(loss: Loss, n_estimators: usize) -> Self {
+        Self {
+            loss,
+            n_estimators,
+            learning_rate: 0.1,
+            subsample: 1.0,
+            patience: None,
+            template: DecisionTree::new(Criterion::Mse).with_max_depth(3),
+            seed: 0,
+            init: 0.0,
+            classes: Vec::new(),
+            trees: Vec::new(),
+            train_loss: Vec::new(),
+            validation_loss: Vec::new(),
+        }
+    }
+
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    // Fraction of rows, drawn without replacement, that each tree is fitted on
+    pub fn with_subsample(mut self, subsample: f64) -> Self {
+        self.subsample = subsample;
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.template = self.template.with_max_depth(max_depth);
+        self
+    }
+
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.template = self.template.with_min_samples_leaf(min_samples_leaf);
+        self
+    }
+
+    // Stop `fit_with_validation` once the validation loss has not improved for
+    // `patience` rounds, keeping only the trees up to the best round
+    pub fn with_early_stopping(mut self, patience: usize) -> Self {
+        self.patience = Some(patience);
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn trees(&self) -> &[DecisionTree] {
+        &self.trees
+    }
+
+    // The two labels for logistic loss, in ascending order; empty for least squares
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    // Training loss after each kept round
+    pub fn train_loss(&self) -> &[f64] {
+        &self.train_loss
+    }
+
+    // Validation loss after each round that was run, including the ones
+    // discarded by early stopping
+    pub fn validation_loss(&self) -> &[f64] {
+        &self.validation_loss
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        self.boost(x, y, None)
+    }
+
+    pub fn fit_with_validation(
+        &mut self,
+        x: &Array2<f64>,
+        y: &Array1<f64>,
+        x_val: &Array2<f64>,
+        y_val: &Array1<f64>,
+    ) -> Result<(), LinearRegressionError> {
+        if x_val.nrows() != y_val.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x_val.nrows(),
+                found: y_val.len(),
+                context: "number of samples in validation X and y",
+            });
+        }
+        if x_val.ncols() != x.ncols() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.ncols(),
+                found: x_val.ncols(),
+                context: "number of features in validation set",
+            });
+        }
+        self.boost(x, y, Some((x_val, y_val)))
+    }
+
+    // Raw additive score: the prediction itself for least squares, the log-odds
+    // of the larger label for logistic loss
+    pub fn decision_function(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        if self.trees.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        let mut raw = Array1::from_elem(x.nrows(), self.init);
+        for tree in &self.trees {
+            raw.scaled_add(self.learning_rate, &tree.predict(x)?);
+        }
+        Ok(raw)
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let raw = self.decision_function(x)?;
+        Ok(match self.loss {
+            Loss::LeastSquares => raw,
+            Loss::Logistic => raw.mapv(|f| if f > 0.0 { self.classes[1] } else { self.classes[0] }),
+        })
+    }
+
+    // Probabilities of the two labels, columns in `classes()` order
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        if self.loss != Loss::Logistic {
+            return Err(LinearRegressionError::InvalidParameter("predict_proba requires logistic loss"));
+        }
+        let p = self.decision_function(x)?.mapv(sigmoid);
+        let mut proba = Array2::zeros((x.nrows(), 2));
+        proba.column_mut(0).assign(&p.mapv(|p| 1.0 - p));
+        proba.column_mut(1).assign(&p);
+        Ok(proba)
+    }
+
+    fn boost(
+        &mut self,
+        x: &Array2<f64>,
+        y: &Array1<f64>,
+        validation: Option<(&Array2<f64>, &Array1<f64>)>,
+    ) -> Result<(), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.n_estimators == 0 {
+            return Err(LinearRegressionError::InvalidParameter("n_estimators must be at least 1"));
+        }
+        if self.learning_rate.is_nan() || self.learning_rate <= 0.0 {
+            return Err(LinearRegressionError::InvalidParameter("learning_rate must be positive"));
+        }
+        if !(self.subsample > 0.0 && self.subsample <= 1.0) {
+            return Err(LinearRegressionError::InvalidParameter("subsample must be in (0, 1]"));
+        }
+
+        let targets = self.encode_targets(y)?;
+        let val_targets = match validation {
+            Some((_, y_val)) => Some(self.encode_validation(y_val)?),
+            None => None,
+        };
+
+        self.init = match self.loss {
+            Loss::LeastSquares => targets.mean().unwrap(),
+            Loss::Logistic => {
+                let p = targets.mean().unwrap();
+                (p / (1.0 - p)).ln()
+            }
+        };
+        self.trees.clear();
+        self.train_loss.clear();
+        self.validation_loss.clear();
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let n_sub = ((self.subsample * x.nrows() as f64) as usize).max(1);
+        let mut raw = Array1::from_elem(x.nrows(), self.init);
+        let mut val_raw = validation.map(|(x_val, _)| Array1::from_elem(x_val.nrows(), self.init));
+        let mut best = (f64::INFINITY, 0);
+
+        for round in 0..self.n_estimators {
+            let gradient = match self.loss {
+                Loss::LeastSquares => &targets - &raw,
+                Loss::Logistic => &targets - &raw.mapv(sigmoid),
+            };
+
+            let rows: Vec<usize> = if n_sub < x.nrows() {
+                rand::seq::index::sample(&mut rng, x.nrows(), n_sub).into_vec()
+            } else {
+                (0..x.nrows()).collect()
+            };
+            let x_sub = x.select(Axis(0), &rows);
+            let mut tree = self.template.clone();
+            tree.fit(&x_sub, &gradient.select(Axis(0), &rows))?;
+            if self.loss == Loss::Logistic {
+                newton_leaf_values(&mut tree, &x_sub, &rows, &targets, &raw)?;
+            }
+
+            raw.scaled_add(self.learning_rate, &tree.predict(x)?);
+            self.train_loss.push(self.loss_value(&targets, &raw));
+
+            if let (Some((x_val, _)), Some(val_raw), Some(val_targets)) = (validation, &mut val_raw, &val_targets) {
+                val_raw.scaled_add(self.learning_rate, &tree.predict(x_val)?);
+                let loss = self.loss_value(val_targets, val_raw);
+                self.validation_loss.push(loss);
+                if loss < best.0 {
+                    best = (loss, round);
+                }
+            }
+            self.trees.push(tree);
+
+            if let (Some(patience), Some(_)) = (self.patience, validation) {
+                if round - best.1 >= patience {
+                    break;
+                }
+            }
+        }
+
+        if self.patience.is_some() && validation.is_some() {
+            self.trees.truncate(best.1 + 1);
+            self.train_loss.truncate(best.1 + 1);
+        }
+        Ok(())
+    }
+
+    // Targets as used by the loss: unchanged for least squares, 0/1 for the two
+    // logistic labels
+    fn encode_targets(&mut self, y: &Array1<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        match self.loss {
+            Loss::LeastSquares => {
+                self.classes.clear();
+                Ok(y.clone())
+            }
+            Loss::Logistic => {
+                let mut classes = y.to_vec();
+                classes.sort_by(f64::total_cmp);
+                classes.dedup();
+                if classes.len() != 2 {
+                    return Err(LinearRegressionError::InvalidParameter(
+                        "logistic loss needs exactly two classes"
+                    ));
+                }
+                self.classes = classes;
+                self.encode_validation(y)
+            }
+        }
+    }
+
+    fn encode_validation(&self, y: &Array1<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        match self.loss {
+            Loss::LeastSquares => Ok(y.clone()),
+            Loss::Logistic => y
+                .iter()
+                .map(|&label| match self.classes.iter().position(|&c| c == label) {
+                    Some(class) => Ok(class as f64),
+                    None => Err(LinearRegressionError::InvalidParameter("label not seen in training")),
+                })
+                .collect(),
+        }
+    }
+
+    fn loss_value(&self, targets: &Array1<f64>, raw: &Array1<f64>) -> f64 {
+        match self.loss {
+            Loss::LeastSquares => (targets - raw).mapv(|e| e * e).mean().unwrap(),
+            Loss::Logistic => {
+                // log(1 + e^f) - y f, written to stay finite for large |f|
+                let total: f64 = targets
+                    .iter()
+                    .zip(raw.iter())
+                    .map(|(&t, &f)| f.max(0.0) + (-f.abs()).exp().ln_1p() - t * f)
+                    .sum();
+                total / targets.len() as f64
+            }
+        }
+    }
+}
+
+impl Seeded for GradientBoosting {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+fn sigmoid(f: f64) -> f64 {
+    1.0 / (1.0 + (-f).exp())
+}
+
+// Replace each leaf's mean gradient with the Newton step for log loss,
+// sum(y - p) / sum(p (1 - p)) over the rows in the leaf
+fn newton_leaf_values(
+    tree: &mut DecisionTree,
+    x_sub: &Array2<f64>,
+    rows: &[usize],
+    targets: &Array1<f64>,
+    raw: &Array1<f64>,
+) -> Result<(), LinearRegressionError> {
+    let leaves = tree.apply(x_sub)?;
+    let n_nodes = tree.nodes().len();
+    let mut numerator = vec![0.0; n_nodes];
+    let mut denominator = vec![0.0; n_nodes];
+    for (&leaf, &i) in leaves.iter().zip(rows) {
+        let p = sigmoid(raw[i]);
+        numerator[leaf] += targets[i] - p;
+        denominator[leaf] += p * (1.0 - p);
+    }
+    for (id, node) in tree.nodes_mut().iter_mut().enumerate() {
+        if node.is_leaf() {
+            node.value = if denominator[id] > 1e-12 { numerator[id] / denominator[id] } else { 0.0 };
+        }
+    }
+    Ok(())
+}
+
+impl Fit for GradientBoosting {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        GradientBoosting::fit(self, x, y)
+    }
+}
+
+impl Predictor for GradientBoosting {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        GradientBoosting::predict(self, x)
+    }
+}
+
+impl Score for GradientBoosting {
+    fn is_classifier(&self) -> bool {
+        self.loss == Loss::Logistic
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::r2_score;
+
+    #[test]
+    fn test_least_squares_boosting() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((100, 2), |(i, j)| if j == 0 { i as f64 / 10.0 } else { ((i * 13) % 7) as f64 });
+        let y = x.column(0).mapv(|v| v.sin() * 2.0) + x.column(1).mapv(|v| v * 0.3);
+
+        let mut model = GradientBoosting::new(Loss::LeastSquares, 150).with_subsample(0.8).with_seed(3);
+        model.fit(&x, &y)?;
+        assert_eq!(model.trees().len(), 150);
+        assert!(r2_score(&model.predict(&x)?, &y) > 0.98);
+        // Training loss keeps going down (up to subsampling noise)
+        let losses = model.train_loss();
+        assert!(losses[losses.len() - 1] < losses[0] * 0.05);
+        Ok(())
+    }
+
+    #[test]
+    fn test_early_stopping() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((80, 1), |(i, _)| i as f64 / 8.0);
+        let noise = |i: usize, k: usize| (((i * k) % 17) as f64 - 8.0) / 4.0;
+        let y = Array1::from_shape_fn(80, |i| x[[i, 0]].cos() + noise(i, 7));
+        let y_val = Array1::from_shape_fn(80, |i| x[[i, 0]].cos() + noise(i, 5));
+
+        let mut model = GradientBoosting::new(Loss::LeastSquares, 500)
+            .with_learning_rate(0.5)
+            .with_max_depth(4)
+            .with_early_stopping(10);
+        model.fit_with_validation(&x, &y, &x, &y_val)?;
+
+        let kept = model.trees().len();
+        let ran = model.validation_loss().len();
+        assert!(ran < 500);
+        assert_eq!(ran, kept + 10);
+        let best = model.validation_loss()[kept - 1];
+        assert!(model.validation_loss().iter().all(|&loss| loss >= best));
+        Ok(())
+    }
+
+    #[test]
+    fn test_logistic_boosting() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((60, 2), |(i, j)| ((i * (j + 2)) % 11) as f64 / 11.0 + if i % 2 == 0 { 1.0 } else { 0.0 });
+        let y = Array1::from_shape_fn(60, |i| if i % 2 == 0 { 5.0 } else { -1.0 });
+
+        let mut model = GradientBoosting::new(Loss::Logistic, 30);
+        model.fit(&x, &y)?;
+        assert_eq!(model.classes(), &[-1.0, 5.0]);
+        assert_eq!(model.predict(&x)?, y);
+
+        let proba = model.predict_proba(&x)?;
+        assert!(proba[[0, 1]] > 0.9 && proba[[1, 0]] > 0.9);
+        assert!(GradientBoosting::new(Loss::Logistic, 5).fit(&x, &Array1::from_elem(60, 1.0)).is_err());
+        Ok(())
+    }
+}
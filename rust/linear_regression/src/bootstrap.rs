@@ -0,0 +1,161 @@
+use crate::stats::quantile;
+use crate::random::{RandomState, Seeded};
+use crate::{try_map, LinearRegression, LinearRegressionError};
+use ndarray::{Array1, Array2, Axis};
+use rand::Rng;
+
+// Empirical distribution of a statistic over bootstrap resamples: one row per
+// resample, one column per component of the statistic
+#[derive(Debug, Clone)]
+pub struct BootstrapResult {
+    pub samples: Array2<f64>,
+}
+
+impl BootstrapResult {
+    pub fn mean(&self) -> Array1<f64> {
+        self.samples.mean_axis(Axis(0)).unwrap()
+    }
+
+    pub fn std(&self) -> Array1<f64> {
+        self.samples.std_axis(Axis(0), 1.0)
+    }
+
+    // Percentile (1 - alpha) intervals, one row per component with columns [lower, upper]
+    pub fn percentile_interval(&self, alpha: f64) -> Result<Array2<f64>, LinearRegressionError> {
+        if !(alpha > 0.0 && alpha < 1.0) {
+            return Err(LinearRegressionError::InvalidParameter("alpha must be in (0, 1)"));
+        }
+
+        let mut intervals = Array2::zeros((self.samples.ncols(), 2));
+        for (j, column) in self.samples.axis_iter(Axis(1)).enumerate() {
+            let mut sorted = column.to_vec();
+            sorted.sort_by(f64::total_cmp);
+            intervals[[j, 0]] = quantile(&sorted, alpha / 2.0);
+            intervals[[j, 1]] = quantile(&sorted, 1.0 - alpha / 2.0);
+        }
+        Ok(intervals)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bootstrap {
+    pub n_resamples: usize,
+    pub seed: u64,
+}
+
+impl Bootstrap {
+    pub fn new(n_resamples: usize, seed: u64) -> Self {
+        Self { n_resamples, seed }
+    }
+
+    // Evaluate `statistic` on `n_resamples` row resamples drawn with replacement.
+    // Each resample gets its own seed, so results are identical with or without
+    // the `parallel` feature.
+    pub fn run<S>(
+        &self,
+        x: &Array2<f64>,
+        y: &Array1<f64>,
+        statistic: S,
+    ) -> Result<BootstrapResult, LinearRegressionError>
+    where
+        S: Fn(&Array2<f64>, &Array1<f64>) -> Result<Array1<f64>, LinearRegressionError> + Sync,
+    {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 || self.n_resamples == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+
+        let resample = |i: usize| {
+            let mut rng = RandomState::new(self.seed).stream(i as u64).rng();
+            let indices: Vec<usize> = (0..x.nrows()).map(|_| rng.random_range(0..x.nrows())).collect();
+            statistic(&x.select(Axis(0), &indices), &y.select(Axis(0), &indices))
+        };
+
+        let results = try_map((0..self.n_resamples).collect(), resample)?;
+
+        let n_stats = results[0].len();
+        let mut samples = Array2::zeros((self.n_resamples, n_stats));
+        for (mut row, result) in samples.rows_mut().into_iter().zip(results) {
+            if result.len() != n_stats {
+                return Err(LinearRegressionError::DimensionMismatch {
+                    expected: n_stats,
+                    found: result.len(),
+                    context: "length of bootstrap statistic",
+                });
+            }
+            row.assign(&result);
+        }
+
+        Ok(BootstrapResult { samples })
+    }
+
+    // Refit a copy of `model` from freshly initialized weights on each resample
+    // and collect its coefficients, intercept first. Starting from the weights
+    // of an already fitted model would pull every replicate towards the
+    // full-data solution and understate the spread.
+    pub fn coefficients(
+        &self,
+        model: &LinearRegression,
+        x: &Array2<f64>,
+        y: &Array1<f64>,
+        epochs: usize,
+    ) -> Result<BootstrapResult, LinearRegressionError> {
+        self.run(x, y, |x_sample, y_sample| {
+            let mut fitted = model.clone();
+            fitted.initialize(x_sample.ncols())?;
+            fitted.train(x_sample, y_sample, epochs)?;
+
+            let mut coefficients = Array1::zeros(fitted.weights.len() + 1);
+            coefficients[0] = fitted.bias;
+            coefficients.slice_mut(ndarray::s![1..]).assign(&fitted.weights);
+            Ok(coefficients)
+        })
+    }
+}
+
+impl Seeded for Bootstrap {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bootstrap_coefficients() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((40, 1), |(i, _)| i as f64 / 10.0);
+        // Deterministic noise around y = 1 + 2x
+        let y = x.column(0).mapv(|v| 1.0 + 2.0 * v) + Array1::from_shape_fn(40, |i| ((i * 7) as f64).sin() * 0.1);
+
+        let model = LinearRegression::new(1, 0.2);
+        let bootstrap = Bootstrap::new(30, 42);
+        let result = bootstrap.coefficients(&model, &x, &y, 800)?;
+
+        assert_eq!(result.samples.dim(), (30, 2));
+        let mean = result.mean();
+        assert!((mean[0] - 1.0).abs() < 0.1);
+        assert!((mean[1] - 2.0).abs() < 0.05);
+
+        let ci = result.percentile_interval(0.05)?;
+        assert!(ci[[1, 0]] <= mean[1] && mean[1] <= ci[[1, 1]]);
+        assert!(result.std().iter().all(|&s| s > 0.0));
+
+        // Same seed, same resamples
+        let again = bootstrap.coefficients(&model, &x, &y, 800)?;
+        assert_eq!(again.samples, result.samples);
+
+        // A fitted model does not warm-start the replicates
+        let mut fitted = model.clone();
+        fitted.train(&x, &y, 800)?;
+        assert_eq!(bootstrap.coefficients(&fitted, &x, &y, 800)?.samples, result.samples);
+        Ok(())
+    }
+}
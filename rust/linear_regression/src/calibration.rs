@@ -0,0 +1,398 @@
+use crate::traits::{BinaryScore, Fit, Predictor, Score};
+use crate::random::{RandomState, Seeded};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, Axis};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+// How raw scores are mapped to probabilities
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Calibration {
+    // Platt scaling: a logistic curve in the score, two parameters
+    Sigmoid,
+    // Isotonic regression: any non-decreasing step function; more flexible
+    // but wants more data (roughly a thousand rows or more)
+    Isotonic,
+}
+
+#[derive(Debug, Clone)]
+enum Mapping {
+    Sigmoid { a: f64, b: f64 },
+    // Knots of a non-decreasing piecewise-linear curve, ascending in score
+    Isotonic { scores: Vec<f64>, probabilities: Vec<f64> },
+}
+
+impl Mapping {
+    fn apply(&self, score: f64) -> f64 {
+        match self {
+            Mapping::Sigmoid { a, b } => 1.0 / (1.0 + (-(a * score + b)).exp()),
+            Mapping::Isotonic { scores, probabilities } => {
+                let last = scores.len() - 1;
+                if score <= scores[0] {
+                    return probabilities[0];
+                }
+                if score >= scores[last] {
+                    return probabilities[last];
+                }
+                let hi = scores.partition_point(|&s| s <= score);
+                let lo = hi - 1;
+                let t = (score - scores[lo]) / (scores[hi] - scores[lo]);
+                probabilities[lo] + t * (probabilities[hi] - probabilities[lo])
+            }
+        }
+    }
+}
+
+// Platt (1999) with his smoothed targets, fitted by Newton's method with a
+// backtracking line search on the log loss
+fn fit_sigmoid(scores: &[f64], positive: &[bool]) -> Mapping {
+    let n_pos = positive.iter().filter(|&&p| p).count() as f64;
+    let n_neg = positive.len() as f64 - n_pos;
+    let targets: Vec<f64> =
+        positive.iter().map(|&p| if p { (n_pos + 1.0) / (n_pos + 2.0) } else { 1.0 / (n_neg + 2.0) }).collect();
+
+    // ln(1 + e^z) without overflow
+    let softplus = |z: f64| if z > 0.0 { z + (-z).exp().ln_1p() } else { z.exp().ln_1p() };
+    let loss = |a: f64, b: f64| -> f64 {
+        scores.iter().zip(&targets).map(|(&s, &t)| softplus(a * s + b) - t * (a * s + b)).sum()
+    };
+
+    let (mut a, mut b) = (0.0, ((n_pos + 1.0) / (n_neg + 1.0)).ln());
+    let mut current = loss(a, b);
+    for _ in 0..100 {
+        let (mut ga, mut gb, mut haa, mut hab, mut hbb) = (0.0, 0.0, 1e-12, 0.0, 1e-12);
+        for (&s, &t) in scores.iter().zip(&targets) {
+            let p = 1.0 / (1.0 + (-(a * s + b)).exp());
+            let w = p * (1.0 - p);
+            ga += (p - t) * s;
+            gb += p - t;
+            haa += w * s * s;
+            hab += w * s;
+            hbb += w;
+        }
+        if ga.abs() < 1e-10 && gb.abs() < 1e-10 {
+            break;
+        }
+        let det = haa * hbb - hab * hab;
+        let (da, db) = ((hbb * ga - hab * gb) / det, (haa * gb - hab * ga) / det);
+
+        let mut step = 1.0;
+        while step > 1e-10 {
+            let candidate = loss(a - step * da, b - step * db);
+            if candidate < current {
+                a -= step * da;
+                b -= step * db;
+                current = candidate;
+                break;
+            }
+            step /= 2.0;
+        }
+        if step <= 1e-10 {
+            break;
+        }
+    }
+    Mapping::Sigmoid { a, b }
+}
+
+// Pool-adjacent-violators on the points sorted by score, tied scores first
+// merged into one weighted point
+fn fit_isotonic(scores: &[f64], positive: &[bool]) -> Mapping {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&i, &j| scores[i].total_cmp(&scores[j]));
+
+    // (score, weight, sum of targets) per block
+    let mut blocks: Vec<(f64, f64, f64)> = Vec::new();
+    for i in order {
+        let target = if positive[i] { 1.0 } else { 0.0 };
+        match blocks.last_mut() {
+            Some(last) if last.0 == scores[i] => {
+                last.1 += 1.0;
+                last.2 += target;
+            }
+            _ => blocks.push((scores[i], 1.0, target)),
+        }
+    }
+
+    // Merged blocks keep the scores they span so the curve can interpolate
+    let mut pooled: Vec<(f64, f64, f64, f64)> = Vec::new();
+    for (score, weight, total) in blocks {
+        pooled.push((score, score, weight, total));
+        while pooled.len() > 1 {
+            let (lo, hi) = (pooled[pooled.len() - 2], pooled[pooled.len() - 1]);
+            if lo.3 / lo.2 < hi.3 / hi.2 {
+                break;
+            }
+            pooled.pop();
+            *pooled.last_mut().unwrap() = (lo.0, hi.1, lo.2 + hi.2, lo.3 + hi.3);
+        }
+    }
+
+    let mut knots = Vec::new();
+    let mut probabilities = Vec::new();
+    for (first, last, weight, total) in pooled {
+        knots.push(first);
+        probabilities.push(total / weight);
+        if last > first {
+            knots.push(last);
+            probabilities.push(total / weight);
+        }
+    }
+    Mapping::Isotonic { scores: knots, probabilities }
+}
+
+// Calibrates a two-class model's scores into probabilities. The mapping is
+// learned on out-of-fold scores, so it sees the base model's behaviour on
+// data it was not trained on; the base model is then refitted on everything.
+#[derive(Debug, Clone)]
+pub struct CalibratedClassifier<E> {
+    base: E,
+    method: Calibration,
+    n_folds: usize,
+    seed: u64,
+    classes: Vec<f64>,
+    mapping: Option<Mapping>,
+}
+
+impl<E: Fit + BinaryScore + Clone> CalibratedClassifier<E> {
+    pub fn new(base: E, method: Calibration) -> Self {
+        Self {
+            base,
+            method,
+            n_folds: 3,
+            seed: 0,
+            classes: Vec::new(),
+            mapping: None,
+        }
+    }
+
+    // Folds used to produce the held-out scores
+    pub fn with_n_folds(mut self, n_folds: usize) -> Self {
+        self.n_folds = n_folds;
+        self
+    }
+
+    // Seed for shuffling rows into folds
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn estimator(&self) -> &E {
+        &self.base
+    }
+
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        let mut classes = y.to_vec();
+        classes.sort_by(f64::total_cmp);
+        classes.dedup();
+        if classes.len() != 2 {
+            return Err(LinearRegressionError::InvalidParameter("calibration needs exactly two classes"));
+        }
+        if self.n_folds < 2 || self.n_folds > x.nrows() {
+            return Err(LinearRegressionError::InvalidParameter(
+                "n_folds must be between 2 and the number of samples"
+            ));
+        }
+
+        let mut order: Vec<usize> = (0..x.nrows()).collect();
+        order.shuffle(&mut StdRng::seed_from_u64(self.seed));
+        let mut scores = vec![0.0; x.nrows()];
+        for fold in 0..self.n_folds {
+            let (held_out, train): (Vec<usize>, Vec<usize>) =
+                (0..order.len()).partition(|&i| i % self.n_folds == fold);
+            let held_out: Vec<usize> = held_out.into_iter().map(|i| order[i]).collect();
+            let train: Vec<usize> = train.into_iter().map(|i| order[i]).collect();
+
+            let mut model = self.base.clone();
+            model.fit(&x.select(Axis(0), &train), &y.select(Axis(0), &train))?;
+            let fold_scores = model.binary_score(&x.select(Axis(0), &held_out))?;
+            for (&row, &s) in held_out.iter().zip(fold_scores.iter()) {
+                scores[row] = s;
+            }
+        }
+
+        let positive: Vec<bool> = y.iter().map(|&label| label == classes[1]).collect();
+        self.mapping = Some(match self.method {
+            Calibration::Sigmoid => fit_sigmoid(&scores, &positive),
+            Calibration::Isotonic => fit_isotonic(&scores, &positive),
+        });
+        self.base.fit(x, y)?;
+        self.classes = classes;
+        Ok(())
+    }
+
+    // Calibrated probabilities, one column per class in ascending label order
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        let mapping = self.mapping.as_ref().ok_or(LinearRegressionError::NotFitted)?;
+        let scores = self.base.binary_score(x)?;
+        let mut proba = Array2::zeros((x.nrows(), 2));
+        for (i, &s) in scores.iter().enumerate() {
+            let p = mapping.apply(s).clamp(0.0, 1.0);
+            proba[[i, 0]] = 1.0 - p;
+            proba[[i, 1]] = p;
+        }
+        Ok(proba)
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let proba = self.predict_proba(x)?;
+        Ok(proba.column(1).mapv(|p| if p > 0.5 { self.classes[1] } else { self.classes[0] }))
+    }
+}
+
+impl<E> Seeded for CalibratedClassifier<E> {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+impl<E: Fit + BinaryScore + Clone> Fit for CalibratedClassifier<E> {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        CalibratedClassifier::fit(self, x, y)
+    }
+}
+
+impl<E: Fit + BinaryScore + Clone> Predictor for CalibratedClassifier<E> {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        CalibratedClassifier::predict(self, x)
+    }
+}
+
+impl<E: Fit + BinaryScore + Clone> Score for CalibratedClassifier<E> {
+    fn is_classifier(&self) -> bool {
+        true
+    }
+}
+
+// Predicted probability against observed frequency, per probability bin.
+// A calibrated model lies on the diagonal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReliabilityCurve {
+    // Only bins holding at least one prediction are reported
+    pub mean_predicted: Vec<f64>,
+    pub fraction_positive: Vec<f64>,
+    pub counts: Vec<usize>,
+}
+
+// `positive` marks the rows whose label is the class `probabilities` refer
+// to; predictions are grouped into `n_bins` equal-width bins over [0, 1]
+pub fn reliability_curve(
+    positive: &[bool],
+    probabilities: &Array1<f64>,
+    n_bins: usize,
+) -> Result<ReliabilityCurve, LinearRegressionError> {
+    if positive.len() != probabilities.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: probabilities.len(),
+            found: positive.len(),
+            context: "number of labels and probabilities",
+        });
+    }
+    if n_bins == 0 {
+        return Err(LinearRegressionError::InvalidParameter("n_bins must be at least 1"));
+    }
+    if probabilities.iter().any(|&p| !(0.0..=1.0).contains(&p)) {
+        return Err(LinearRegressionError::InvalidParameter("probabilities must be in [0, 1]"));
+    }
+
+    let mut sums = vec![0.0; n_bins];
+    let mut hits = vec![0usize; n_bins];
+    let mut counts = vec![0usize; n_bins];
+    for (&p, &is_positive) in probabilities.iter().zip(positive) {
+        let bin = ((p * n_bins as f64) as usize).min(n_bins - 1);
+        sums[bin] += p;
+        counts[bin] += 1;
+        hits[bin] += is_positive as usize;
+    }
+
+    let mut curve = ReliabilityCurve {
+        mean_predicted: Vec::new(),
+        fraction_positive: Vec::new(),
+        counts: Vec::new(),
+    };
+    for bin in (0..n_bins).filter(|&b| counts[b] > 0) {
+        curve.mean_predicted.push(sums[bin] / counts[bin] as f64);
+        curve.fraction_positive.push(hits[bin] as f64 / counts[bin] as f64);
+        curve.counts.push(counts[bin]);
+    }
+    Ok(curve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svm::LinearSvc;
+    use crate::tree::{Criterion, DecisionTree};
+
+    // One feature with P(y = 1 | x) = sigmoid(x); labels drawn by comparing
+    // against a deterministic, evenly spread sequence
+    fn noisy_labels() -> (Array2<f64>, Array1<f64>) {
+        let x = Array2::from_shape_fn((1000, 1), |(i, _)| (i % 100) as f64 / 10.0 - 5.0);
+        let y = Array1::from_shape_fn(1000, |i| {
+            let p = 1.0 / (1.0 + (-x[[i, 0]]).exp());
+            let u = ((i * 7919) % 1000) as f64 / 1000.0;
+            if u < p { 1.0 } else { 0.0 }
+        });
+        (x, y)
+    }
+
+    #[test]
+    fn test_calibrated_classifier() -> Result<(), LinearRegressionError> {
+        let (x, y) = noisy_labels();
+        let positive: Vec<bool> = y.iter().map(|&v| v == 1.0).collect();
+
+        for method in [Calibration::Sigmoid, Calibration::Isotonic] {
+            let mut model = CalibratedClassifier::new(LinearSvc::new(), method).with_seed(1);
+            model.fit(&x, &y)?;
+            let proba = model.predict_proba(&x)?;
+            assert!(proba.rows().into_iter().all(|row| (row.sum() - 1.0).abs() < 1e-12));
+
+            // Close to the true conditional probabilities, bin by bin
+            let curve = reliability_curve(&positive, &proba.column(1).to_owned(), 5)?;
+            for (predicted, observed) in curve.mean_predicted.iter().zip(&curve.fraction_positive) {
+                assert!((predicted - observed).abs() < 0.1);
+            }
+        }
+
+        // Isotonic calibration keeps the base model's ordering
+        let tree = DecisionTree::new(Criterion::Gini).with_max_depth(3);
+        let mut model = CalibratedClassifier::new(tree, Calibration::Isotonic);
+        model.fit(&x, &y)?;
+        let scores = model.estimator().binary_score(&x)?;
+        let calibrated = model.predict_proba(&x)?.column(1).to_owned();
+        for i in 0..x.nrows() {
+            for j in 0..x.nrows() {
+                assert!(scores[i] > scores[j] || calibrated[i] <= calibrated[j] + 1e-12);
+            }
+        }
+        assert!(CalibratedClassifier::new(LinearSvc::new(), Calibration::Sigmoid).predict(&x).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_reliability_curve() -> Result<(), LinearRegressionError> {
+        let probabilities = Array1::from(vec![0.05, 0.15, 0.1, 0.85, 0.95, 0.9, 1.0]);
+        let positive = [false, false, true, true, true, false, true];
+        let curve = reliability_curve(&positive, &probabilities, 2)?;
+        assert_eq!(curve.counts, vec![3, 4]);
+        assert!((curve.mean_predicted[0] - 0.1).abs() < 1e-12);
+        assert!((curve.fraction_positive[1] - 0.75).abs() < 1e-12);
+        assert!(reliability_curve(&positive, &probabilities.mapv(|p| p * 2.0), 2).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use crate::LinearRegressionError;
+use serde::{Deserialize, Serialize};
+
+// Learning rate as a function of the epoch, starting from the configured rate
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Schedule {
+    Constant,
+    // Multiply by `factor` every `every` epochs
+    Step { every: usize, factor: f64 },
+    // Multiply by `gamma` after each epoch
+    Exponential { gamma: f64 },
+}
+
+impl Schedule {
+    pub fn learning_rate(&self, initial: f64, epoch: usize) -> f64 {
+        match *self {
+            Self::Constant => initial,
+            Self::Step { every, factor } => initial * factor.powi((epoch / every) as i32),
+            Self::Exponential { gamma } => initial * gamma.powi(epoch as i32),
+        }
+    }
+
+    fn validate(&self) -> Result<(), LinearRegressionError> {
+        let in_unit = |v: f64| v > 0.0 && v <= 1.0;
+        match *self {
+            Self::Constant => Ok(()),
+            Self::Step { every: 0, .. } => {
+                Err(LinearRegressionError::InvalidParameter("step schedule needs every >= 1"))
+            }
+            Self::Step { factor, .. } if !in_unit(factor) => {
+                Err(LinearRegressionError::InvalidParameter("step schedule factor must be in (0, 1]"))
+            }
+            Self::Exponential { gamma } if !in_unit(gamma) => {
+                Err(LinearRegressionError::InvalidParameter("exponential schedule gamma must be in (0, 1]"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Stop once the training loss has not improved by more than `min_delta` for
+// `patience` epochs in a row
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EarlyStopping {
+    pub patience: usize,
+    pub min_delta: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verbosity {
+    Silent,
+    // Log the loss at info level every so many epochs through `tracing`.
+    // Builds without the `tracing` feature reject it in `validate` rather
+    // than train silently; the library never writes to stderr itself.
+    Every(usize),
+}
+
+// Settings for `LinearRegression::train_with_config`, checked as a whole by
+// `validate` before any update is made. Deserializes with defaults for
+// missing fields, so configuration files only need what they change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrainConfig {
+    pub epochs: usize,
+    pub learning_rate: f64,
+    // Rows per gradient step, taken in order; None uses every row at once
+    pub batch_size: Option<usize>,
+    // Stop once the gradient norm falls below this
+    pub tol: Option<f64>,
+    pub schedule: Schedule,
+    pub early_stopping: Option<EarlyStopping>,
+    pub verbosity: Verbosity,
+}
+
+impl Default for TrainConfig {
+    fn default() -> Self {
+        Self {
+            epochs: 1000,
+            learning_rate: 0.01,
+            batch_size: None,
+            tol: None,
+            schedule: Schedule::Constant,
+            early_stopping: None,
+            verbosity: Verbosity::Silent,
+        }
+    }
+}
+
+impl TrainConfig {
+    pub fn validate(&self, n_samples: usize) -> Result<(), LinearRegressionError> {
+        if self.epochs == 0 {
+            return Err(LinearRegressionError::InvalidParameter("epochs must be at least 1"));
+        }
+        if !(self.learning_rate.is_finite() && self.learning_rate > 0.0) {
+            return Err(LinearRegressionError::InvalidParameter("learning rate must be positive and finite"));
+        }
+        match self.batch_size {
+            Some(0) => return Err(LinearRegressionError::InvalidParameter("batch_size must be at least 1")),
+            Some(batch_size) if batch_size > n_samples => {
+                return Err(LinearRegressionError::InvalidParameter("batch_size exceeds the number of samples"));
+            }
+            _ => (),
+        }
+        if self.tol.is_some_and(|tol| !(tol.is_finite() && tol >= 0.0)) {
+            return Err(LinearRegressionError::InvalidParameter("tol must be non-negative and finite"));
+        }
+        self.schedule.validate()?;
+        if let Some(stopping) = self.early_stopping {
+            if stopping.patience == 0 {
+                return Err(LinearRegressionError::InvalidParameter("early stopping patience must be at least 1"));
+            }
+            if !(stopping.min_delta.is_finite() && stopping.min_delta >= 0.0) {
+                return Err(LinearRegressionError::InvalidParameter("early stopping min_delta must be non-negative"));
+            }
+        }
+        if self.verbosity == Verbosity::Every(0) {
+            return Err(LinearRegressionError::InvalidParameter("verbosity interval must be at least 1"));
+        }
+        if cfg!(not(feature = "tracing")) && self.verbosity != Verbosity::Silent {
+            return Err(LinearRegressionError::InvalidParameter("verbosity needs the `tracing` feature"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let config = TrainConfig::default();
+        assert!(config.validate(10).is_ok());
+        let invalid = [
+            TrainConfig { epochs: 0, ..config.clone() },
+            TrainConfig { learning_rate: -0.1, ..config.clone() },
+            TrainConfig { batch_size: Some(11), ..config.clone() },
+            TrainConfig { tol: Some(f64::NAN), ..config.clone() },
+            TrainConfig { schedule: Schedule::Step { every: 0, factor: 0.5 }, ..config.clone() },
+            TrainConfig { early_stopping: Some(EarlyStopping { patience: 0, min_delta: 0.0 }), ..config.clone() },
+        ];
+        assert!(invalid.iter().all(|c| c.validate(10).is_err()));
+        let verbose = TrainConfig { verbosity: Verbosity::Every(10), ..config.clone() };
+        assert_eq!(verbose.validate(10).is_ok(), cfg!(feature = "tracing"));
+
+        let schedule = Schedule::Step { every: 10, factor: 0.5 };
+        assert_eq!((schedule.learning_rate(0.1, 9), schedule.learning_rate(0.1, 25)), (0.1, 0.025));
+        let partial: TrainConfig = serde_json::from_str(r#"{"epochs": 5, "batch_size": 4}"#).unwrap();
+        assert_eq!(partial, TrainConfig { epochs: 5, batch_size: Some(4), ..config });
+    }
+}
@@ -0,0 +1,138 @@
+use crate::knn::Distance;
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2};
+
+// Label given to points that belong to no cluster
+pub const NOISE: i64 = -1;
+
+// Density-based clustering: points with at least `min_samples` neighbors
+// (themselves included) within `eps` are core points, clusters are the
+// connected groups of core points plus the border points they reach, and
+// everything else is noise. Neighbors are found by brute force.
+#[derive(Debug, Clone)]
+pub struct Dbscan {
+    eps: f64,
+    min_samples: usize,
+    distance: Distance,
+    labels: Array1<i64>,
+    core_samples: Vec<usize>,
+}
+
+impl Dbscan {
+    pub fn new(eps: f64, min_samples: usize) -> Self {
+        Self {
+            eps,
+            min_samples,
+            distance: Distance::Euclidean,
+            labels: Array1::zeros(0),
+            core_samples: Vec::new(),
+        }
+    }
+
+    pub fn with_distance(mut self, distance: Distance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    // Cluster index of each training row, or `NOISE`
+    pub fn labels(&self) -> &Array1<i64> {
+        &self.labels
+    }
+
+    // Indices of the core points, ascending
+    pub fn core_samples(&self) -> &[usize] {
+        &self.core_samples
+    }
+
+    pub fn n_clusters(&self) -> usize {
+        self.labels.iter().max().map_or(0, |&max| (max + 1) as usize)
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.eps.is_nan() || self.eps <= 0.0 {
+            return Err(LinearRegressionError::InvalidParameter("eps must be positive"));
+        }
+        if self.min_samples == 0 {
+            return Err(LinearRegressionError::InvalidParameter("min_samples must be at least 1"));
+        }
+
+        let neighborhoods: Vec<Vec<usize>> = x
+            .rows()
+            .into_iter()
+            .map(|a| {
+                x.rows()
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, b)| self.distance.between(a, *b) <= self.eps)
+                    .map(|(j, _)| j)
+                    .collect()
+            })
+            .collect();
+        let is_core: Vec<bool> = neighborhoods.iter().map(|n| n.len() >= self.min_samples).collect();
+
+        let mut labels = Array1::from_elem(x.nrows(), NOISE);
+        let mut cluster = 0;
+        let mut frontier = Vec::new();
+        for start in 0..x.nrows() {
+            if !is_core[start] || labels[start] != NOISE {
+                continue;
+            }
+            labels[start] = cluster;
+            frontier.push(start);
+            while let Some(point) = frontier.pop() {
+                for &neighbor in &neighborhoods[point] {
+                    if labels[neighbor] == NOISE {
+                        labels[neighbor] = cluster;
+                        // Border points join the cluster but do not extend it
+                        if is_core[neighbor] {
+                            frontier.push(neighbor);
+                        }
+                    }
+                }
+            }
+            cluster += 1;
+        }
+
+        self.core_samples = (0..x.nrows()).filter(|&i| is_core[i]).collect();
+        self.labels = labels;
+        Ok(())
+    }
+
+    pub fn fit_predict(&mut self, x: &Array2<f64>) -> Result<Array1<i64>, LinearRegressionError> {
+        self.fit(x)?;
+        Ok(self.labels.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_dbscan() -> Result<(), LinearRegressionError> {
+        let x = arr2(&[
+            [0.0, 0.0], [0.3, 0.0], [0.6, 0.0], [0.9, 0.0],
+            [5.0, 5.0], [5.3, 5.0], [5.0, 5.3],
+            [1.2, 0.0],
+            [20.0, 20.0],
+        ]);
+
+        let mut model = Dbscan::new(0.35, 3);
+        let labels = model.fit_predict(&x)?;
+        assert_eq!(model.n_clusters(), 2);
+        assert!(labels.iter().take(4).all(|&l| l == labels[0]));
+        assert!(labels.iter().skip(4).take(3).all(|&l| l == labels[4]));
+        assert_ne!(labels[0], labels[4]);
+        // Reachable from a core point, but not dense enough to be one itself
+        assert_eq!(labels[7], labels[0]);
+        assert!(!model.core_samples().contains(&7));
+        assert_eq!(labels[8], NOISE);
+
+        assert!(Dbscan::new(0.0, 3).fit(&x).is_err());
+        Ok(())
+    }
+}
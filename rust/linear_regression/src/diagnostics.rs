@@ -0,0 +1,474 @@
+use crate::linalg::{inverse, least_squares, symmetric_eigen, with_intercept};
+use crate::metrics::r2_score;
+use crate::stats::{chi_squared_sf, normal_ppf};
+use crate::{LinearRegression, LinearRegressionError};
+use ndarray::{Array1, Array2, Axis};
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+pub struct VifReport {
+    pub vifs: Array1<f64>,
+}
+
+impl VifReport {
+    // Indices of features whose VIF exceeds `threshold` (5 or 10 are common cutoffs)
+    pub fn collinear_features(&self, threshold: f64) -> Vec<usize> {
+        self.vifs
+            .iter()
+            .enumerate()
+            .filter(|(_, &v)| v > threshold)
+            .map(|(j, _)| j)
+            .collect()
+    }
+
+    // One message per collinear feature, naming it from `names` (in column
+    // order) when given and by index otherwise
+    pub fn warnings(&self, threshold: f64, names: Option<&[String]>) -> Vec<String> {
+        self.collinear_features(threshold)
+            .into_iter()
+            .map(|j| {
+                let feature = match names.and_then(|names| names.get(j)) {
+                    Some(name) => format!("'{}'", name),
+                    None => j.to_string(),
+                };
+                format!(
+                    "feature {} has VIF {:.2} (> {}): it is largely explained by the other features, \
+                     so its coefficient may be unstable",
+                    feature, self.vifs[j], threshold
+                )
+            })
+            .collect()
+    }
+}
+
+// Variance inflation factors: regress each feature on all the others and
+// report 1 / (1 - R²). A feature that is an exact linear combination of the
+// others gets an infinite VIF.
+pub fn vif(x: &Array2<f64>) -> Result<VifReport, LinearRegressionError> {
+    if x.ncols() < 2 {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: 2,
+            found: x.ncols(),
+            context: "number of features for VIF (at least)",
+        });
+    }
+    // Each auxiliary regression has ncols parameters with the intercept
+    if x.nrows() <= x.ncols() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.ncols() + 1,
+            found: x.nrows(),
+            context: "number of samples for VIF (at least)",
+        });
+    }
+
+    let mut vifs = Array1::zeros(x.ncols());
+    for j in 0..x.ncols() {
+        let others: Vec<usize> = (0..x.ncols()).filter(|&k| k != j).collect();
+        let design = with_intercept(&x.select(Axis(1), &others));
+        let target = x.column(j).to_owned();
+
+        vifs[j] = match least_squares(&design, &target) {
+            Ok(beta) => {
+                let r2 = r2_score(&design.dot(&beta), &target);
+                if r2 >= 1.0 { f64::INFINITY } else { 1.0 / (1.0 - r2) }
+            }
+            Err(LinearRegressionError::NumericalError(_)) => f64::INFINITY,
+            Err(err) => return Err(err),
+        };
+    }
+
+    Ok(VifReport { vifs })
+}
+
+// 2-norm condition number of the design matrix (including the intercept
+// column), from the extreme eigenvalues of X'X. Large values mean gradient
+// descent will converge slowly or diverge; badly scaled features are the
+// usual cause.
+pub fn condition_number(x: &Array2<f64>) -> Result<f64, LinearRegressionError> {
+    if x.nrows() == 0 {
+        return Err(LinearRegressionError::EmptyData);
+    }
+
+    let design = with_intercept(x);
+    let (eigenvalues, _) = symmetric_eigen(&design.t().dot(&design));
+    let largest = eigenvalues[0];
+    let smallest = eigenvalues[eigenvalues.len() - 1];
+
+    if smallest <= largest * 1e-14 {
+        return Ok(f64::INFINITY);
+    }
+    Ok((largest / smallest).sqrt())
+}
+
+// Per-row influence measures, aligned with the rows of the training data
+#[derive(Debug, Clone)]
+pub struct Influence {
+    // Diagonal of the hat matrix X (X'X)⁻¹ X'
+    pub leverage: Array1<f64>,
+    pub cooks_distance: Array1<f64>,
+}
+
+impl Influence {
+    // Rows with Cook's distance above `threshold` (4 / n is a common rule of thumb)
+    pub fn influential_rows(&self, threshold: f64) -> Vec<usize> {
+        self.cooks_distance
+            .iter()
+            .enumerate()
+            .filter(|(_, &d)| d > threshold)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+pub fn influence(
+    model: &LinearRegression,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+) -> Result<Influence, LinearRegressionError> {
+    if x.nrows() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.nrows(),
+            found: y.len(),
+            context: "number of samples in X and y",
+        });
+    }
+    let n_params = x.ncols() + 1;
+    if x.nrows() <= n_params {
+        return Err(LinearRegressionError::NumericalError(
+            "Not enough samples for residual degrees of freedom"
+        ));
+    }
+
+    let design = with_intercept(x);
+    let xtx_inv = inverse(&design.t().dot(&design))?;
+    let leverage: Array1<f64> = design
+        .rows()
+        .into_iter()
+        .map(|row| row.dot(&xtx_inv.dot(&row)))
+        .collect();
+
+    let residuals = y - &model.predict(x)?;
+    let s2 = residuals.dot(&residuals) / (x.nrows() - n_params) as f64;
+    let cooks_distance = residuals
+        .iter()
+        .zip(leverage.iter())
+        .map(|(&e, &h)| e * e / (n_params as f64 * s2) * h / ((1.0 - h) * (1.0 - h)))
+        .collect();
+
+    Ok(Influence { leverage, cooks_distance })
+}
+
+// One observation of a fit, with everything the usual diagnostic plots need:
+// residuals against fitted values, a normal QQ plot of the standardized
+// residuals, and standardized residuals against leverage
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ResidualRow {
+    pub index: usize,
+    pub actual: f64,
+    pub fitted: f64,
+    pub residual: f64,
+    // Residual over its estimated standard deviation s·√(1 - h)
+    pub standardized_residual: f64,
+    pub leverage: f64,
+    pub cooks_distance: f64,
+    // Normal quantile at this residual's rank among the standardized ones
+    pub theoretical_quantile: f64,
+}
+
+// Tidy per-observation diagnostics, one row per training sample, for
+// plotting outside the crate
+#[derive(Debug, Clone, Serialize)]
+pub struct ResidualData {
+    pub rows: Vec<ResidualRow>,
+}
+
+impl ResidualData {
+    // One line per observation, with the field names of `ResidualRow` as the
+    // header
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), LinearRegressionError> {
+        let mut writer = csv::Writer::from_writer(writer);
+        for row in &self.rows {
+            writer.serialize(row)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    // A JSON array of row objects; undefined values (at leverage 1) are null
+    pub fn write_json<W: Write>(&self, writer: W) -> Result<(), LinearRegressionError> {
+        Ok(serde_json::to_writer_pretty(writer, &self.rows)?)
+    }
+
+    // CSV or JSON, chosen by the extension of `path`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), LinearRegressionError> {
+        let path = path.as_ref();
+        let extension = path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+        let is_csv = match extension.as_deref() {
+            Some("csv") => true,
+            Some("json") => false,
+            _ => {
+                let message = format!("unknown diagnostics format for {}, expected .csv or .json", path.display());
+                return Err(LinearRegressionError::Parse(message));
+            }
+        };
+        let writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        if is_csv {
+            self.write_csv(writer)
+        } else {
+            self.write_json(writer)
+        }
+    }
+}
+
+// Residuals, leverage, Cook's distance and QQ-plot positions for every row.
+// The plotting positions are those of R's `qqnorm`: (r - a) / (n + 1 - 2a)
+// for rank r, with a = 3/8 up to ten observations and 1/2 above.
+pub fn residual_data(
+    model: &LinearRegression,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+) -> Result<ResidualData, LinearRegressionError> {
+    let Influence { leverage, cooks_distance } = influence(model, x, y)?;
+    let fitted = model.predict(x)?;
+    let residuals = y - &fitted;
+    let n = y.len();
+    let s2 = residuals.dot(&residuals) / (n - x.ncols() - 1) as f64;
+    let standardized: Vec<f64> =
+        residuals.iter().zip(leverage.iter()).map(|(&e, &h)| e / (s2 * (1.0 - h)).sqrt()).collect();
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| standardized[a].total_cmp(&standardized[b]));
+    let a = if n <= 10 { 0.375 } else { 0.5 };
+    let mut quantiles = vec![0.0; n];
+    for (rank, &i) in order.iter().enumerate() {
+        quantiles[i] = normal_ppf((rank as f64 + 1.0 - a) / (n as f64 + 1.0 - 2.0 * a));
+    }
+
+    let rows = (0..n)
+        .map(|i| ResidualRow {
+            index: i,
+            actual: y[i],
+            fitted: fitted[i],
+            residual: residuals[i],
+            standardized_residual: standardized[i],
+            leverage: leverage[i],
+            cooks_distance: cooks_distance[i],
+            theoretical_quantile: quantiles[i],
+        })
+        .collect();
+    Ok(ResidualData { rows })
+}
+
+#[derive(Debug, Clone)]
+pub struct HeteroscedasticityTest {
+    // Lagrange multiplier statistic n·R² of the auxiliary regression
+    pub statistic: f64,
+    pub p_value: f64,
+    pub df: usize,
+}
+
+// Breusch–Pagan test (Koenker's studentized form): regress squared residuals
+// on the features. A small p-value means the residual variance depends on X,
+// so weighted least squares or robust errors are worth considering.
+pub fn breusch_pagan(
+    model: &LinearRegression,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+) -> Result<HeteroscedasticityTest, LinearRegressionError> {
+    let squared_residuals = squared_residuals(model, x, y)?;
+    lagrange_multiplier_test(x, &squared_residuals)
+}
+
+// White's test: like Breusch–Pagan but the auxiliary regression also
+// includes squares and pairwise products of the features, so it picks up
+// non-linear forms of heteroscedasticity
+pub fn white_test(
+    model: &LinearRegression,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+) -> Result<HeteroscedasticityTest, LinearRegressionError> {
+    let squared_residuals = squared_residuals(model, x, y)?;
+
+    let k = x.ncols();
+    let n_terms = k + k * (k + 1) / 2;
+    let mut auxiliary = Array2::zeros((x.nrows(), n_terms));
+    for (mut out, row) in auxiliary.rows_mut().into_iter().zip(x.rows()) {
+        let mut col = 0;
+        for j in 0..k {
+            out[col] = row[j];
+            col += 1;
+        }
+        for i in 0..k {
+            for j in i..k {
+                out[col] = row[i] * row[j];
+                col += 1;
+            }
+        }
+    }
+
+    lagrange_multiplier_test(&auxiliary, &squared_residuals)
+}
+
+fn squared_residuals(
+    model: &LinearRegression,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+) -> Result<Array1<f64>, LinearRegressionError> {
+    if x.nrows() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.nrows(),
+            found: y.len(),
+            context: "number of samples in X and y",
+        });
+    }
+    let residuals = y - &model.predict(x)?;
+    Ok(residuals.mapv(|e| e * e))
+}
+
+fn lagrange_multiplier_test(
+    regressors: &Array2<f64>,
+    target: &Array1<f64>,
+) -> Result<HeteroscedasticityTest, LinearRegressionError> {
+    if regressors.nrows() <= regressors.ncols() + 1 {
+        return Err(LinearRegressionError::NumericalError(
+            "Not enough samples for the auxiliary regression"
+        ));
+    }
+
+    let design = with_intercept(regressors);
+    let beta = least_squares(&design, target)?;
+    let r2 = r2_score(&design.dot(&beta), target);
+
+    let df = regressors.ncols();
+    let statistic = regressors.nrows() as f64 * r2;
+    Ok(HeteroscedasticityTest {
+        statistic,
+        p_value: chi_squared_sf(statistic, df as f64),
+        df,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vif_flags_correlated_features() -> Result<(), LinearRegressionError> {
+        // Columns 0 and 1 are nearly collinear, column 2 is unrelated
+        let x = Array2::from_shape_fn((30, 3), |(i, j)| {
+            let t = i as f64;
+            match j {
+                0 => t,
+                1 => 2.0 * t + (t * 1.3).sin() * 0.5,
+                _ => (t * 2.7).cos(),
+            }
+        });
+
+        let report = vif(&x)?;
+        assert!(report.vifs[0] > 10.0 && report.vifs[1] > 10.0);
+        assert!(report.vifs[2] < 2.0);
+        assert_eq!(report.collinear_features(10.0), vec![0, 1]);
+        assert_eq!(report.warnings(10.0, None).len(), 2);
+        let names = ["a".to_string(), "b".to_string(), "c".to_string()];
+        assert!(report.warnings(10.0, Some(&names))[1].starts_with("feature 'b' has VIF"));
+        assert!(report.warnings(10.0, None)[1].starts_with("feature 1 has VIF"));
+        let short = vif(&x.slice(ndarray::s![..3, ..]).to_owned());
+        assert!(matches!(short, Err(LinearRegressionError::DimensionMismatch { expected: 4, found: 3, .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_condition_number() -> Result<(), LinearRegressionError> {
+        // Centered, unit-scale columns are perfectly conditioned
+        let x = ndarray::arr2(&[[1.0, 1.0], [1.0, -1.0], [-1.0, 1.0], [-1.0, -1.0]]);
+        assert!((condition_number(&x)? - 1.0).abs() < 1e-10);
+
+        // Unscaled square footage next to the intercept is not
+        let raw = ndarray::arr2(&[[1200.0], [1500.0], [2000.0], [1100.0]]);
+        assert!(condition_number(&raw)? > 1e3);
+
+        let collinear = ndarray::arr2(&[[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]]);
+        assert!(condition_number(&collinear)?.is_infinite());
+        Ok(())
+    }
+
+    #[test]
+    fn test_influence_flags_outlier() -> Result<(), LinearRegressionError> {
+        // y = 2x + 1 with the last row pulled far off the line at high leverage
+        let x = Array2::from_shape_fn((8, 1), |(i, _)| if i == 7 { 20.0 } else { i as f64 });
+        let mut y = x.column(0).mapv(|v| 2.0 * v + 1.0);
+        y[3] += 0.5;
+        y[7] += 15.0;
+
+        let design = with_intercept(&x);
+        let beta = least_squares(&design, &y)?;
+        let model = LinearRegression::from_parameters(Array1::from(vec![beta[1]]), beta[0]);
+
+        let result = influence(&model, &x, &y)?;
+        // Leverages sum to the number of parameters
+        assert!((result.leverage.sum() - 2.0).abs() < 1e-10);
+        assert_eq!(result.influential_rows(4.0 / 8.0), vec![7]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_residual_data_export() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((8, 1), |(i, _)| i as f64);
+        let y = Array1::from_shape_fn(8, |i| 2.0 * i as f64 + 1.0 + [0.3, -0.2, 0.1, -0.4, 0.2, 0.5, -0.1, -0.3][i]);
+        let beta = least_squares(&with_intercept(&x), &y)?;
+        let model = LinearRegression::from_parameters(Array1::from(vec![beta[1]]), beta[0]);
+
+        let data = residual_data(&model, &x, &y)?;
+        let influence = influence(&model, &x, &y)?;
+        for (row, (&h, &d)) in data.rows.iter().zip(influence.leverage.iter().zip(influence.cooks_distance.iter())) {
+            assert!((row.actual - row.fitted - row.residual).abs() < 1e-12);
+            assert_eq!((row.leverage, row.cooks_distance), (h, d));
+            // Cook's distance from the standardized residual, with 2 parameters
+            let cooks = row.standardized_residual.powi(2) * h / (2.0 * (1.0 - h));
+            assert!((cooks - d).abs() < 1e-10);
+        }
+        // The largest standardized residual gets the largest quantile, and
+        // the quantiles are symmetric around zero
+        let largest = data.rows.iter().max_by(|a, b| a.standardized_residual.total_cmp(&b.standardized_residual));
+        assert_eq!(largest.unwrap().index, 5);
+        assert!(data.rows.iter().map(|r| r.theoretical_quantile).sum::<f64>().abs() < 1e-6);
+
+        let mut csv = Vec::new();
+        data.write_csv(&mut csv)?;
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with(
+            "index,actual,fitted,residual,standardized_residual,leverage,cooks_distance,theoretical_quantile\n"
+        ));
+        assert_eq!(csv.lines().count(), 9);
+        let mut json = Vec::new();
+        data.write_json(&mut json)?;
+        let parsed: serde_json::Value = serde_json::from_slice(&json)?;
+        assert_eq!(parsed.as_array().map(Vec::len), Some(8));
+        assert!(data.save(std::env::temp_dir().join("residuals.txt")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_heteroscedasticity_tests() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((60, 1), |(i, _)| i as f64 / 6.0);
+        let noise = Array1::from_shape_fn(60, |i| (i as f64 * 2.3).sin());
+        let model = LinearRegression::from_parameters(Array1::from(vec![2.0]), 1.0);
+
+        // Constant-variance errors
+        let y_flat = x.column(0).mapv(|v| 2.0 * v + 1.0) + &noise * 0.5;
+        assert!(breusch_pagan(&model, &x, &y_flat)?.p_value > 0.1);
+
+        // Error spread grows with x
+        let y_fan = x.column(0).mapv(|v| 2.0 * v + 1.0) + &noise * &x.column(0);
+        let bp = breusch_pagan(&model, &x, &y_fan)?;
+        assert_eq!(bp.df, 1);
+        assert!(bp.p_value < 0.01);
+
+        let white = white_test(&model, &x, &y_fan)?;
+        assert_eq!(white.df, 2);
+        assert!(white.p_value < 0.01);
+        Ok(())
+    }
+}
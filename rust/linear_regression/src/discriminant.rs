@@ -0,0 +1,331 @@
+use crate::linalg::{inverse, symmetric_eigen};
+use crate::traits::{Fit, Predictor, Score};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, Axis};
+
+// Linear discriminant analysis: Gaussian classes sharing one covariance
+// matrix. Doubles as a supervised projection onto the directions that best
+// separate the class means relative to the within-class spread.
+#[derive(Debug, Clone)]
+pub struct LinearDiscriminant {
+    priors: Option<Vec<f64>>,
+    shrinkage: Option<f64>,
+    n_components: Option<usize>,
+    classes: Vec<f64>,
+    class_priors: Array1<f64>,
+    // One row per class
+    means: Array2<f64>,
+    covariance: Array2<f64>,
+    // Σ⁻¹ μ_k as rows, and the matching per-class constants
+    coef: Array2<f64>,
+    intercept: Array1<f64>,
+    // Discriminant axes as columns, strongest first
+    scalings: Array2<f64>,
+    explained_variance_ratio: Array1<f64>,
+    overall_mean: Array1<f64>,
+}
+
+impl LinearDiscriminant {
+    pub fn new() -> Self {
+        Self {
+            priors: None,
+            shrinkage: None,
+            n_components: None,
+            classes: Vec::new(),
+            class_priors: Array1::zeros(0),
+            means: Array2::zeros((0, 0)),
+            covariance: Array2::zeros((0, 0)),
+            coef: Array2::zeros((0, 0)),
+            intercept: Array1::zeros(0),
+            scalings: Array2::zeros((0, 0)),
+            explained_variance_ratio: Array1::zeros(0),
+            overall_mean: Array1::zeros(0),
+        }
+    }
+
+    // Class priors in ascending label order; by default, the class frequencies
+    pub fn with_priors(mut self, priors: Vec<f64>) -> Self {
+        self.priors = Some(priors);
+        self
+    }
+
+    // Shrink the covariance towards a scaled identity,
+    // (1 - λ) Σ + λ (trace(Σ) / p) I, which keeps it well conditioned when
+    // there are few samples per feature
+    pub fn with_shrinkage(mut self, shrinkage: f64) -> Self {
+        self.shrinkage = Some(shrinkage);
+        self
+    }
+
+    // Number of discriminant axes kept by `transform`, at most
+    // min(n_classes - 1, n_features); all of them by default
+    pub fn with_n_components(mut self, n_components: usize) -> Self {
+        self.n_components = Some(n_components);
+        self
+    }
+
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    pub fn priors(&self) -> &Array1<f64> {
+        &self.class_priors
+    }
+
+    pub fn means(&self) -> &Array2<f64> {
+        &self.means
+    }
+
+    // Shared within-class covariance, after shrinkage
+    pub fn covariance(&self) -> &Array2<f64> {
+        &self.covariance
+    }
+
+    pub fn scalings(&self) -> &Array2<f64> {
+        &self.scalings
+    }
+
+    // Share of the between-class variance captured by each kept axis
+    pub fn explained_variance_ratio(&self) -> &Array1<f64> {
+        &self.explained_variance_ratio
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+
+        let mut classes = y.to_vec();
+        classes.sort_by(f64::total_cmp);
+        classes.dedup();
+        if classes.len() < 2 {
+            return Err(LinearRegressionError::InvalidParameter("need at least two classes"));
+        }
+        let members: Vec<Vec<usize>> = classes
+            .iter()
+            .map(|c| (0..y.len()).filter(|&i| y[i] == *c).collect())
+            .collect();
+
+        let priors: Array1<f64> = match &self.priors {
+            Some(priors) if priors.len() != classes.len() => {
+                return Err(LinearRegressionError::DimensionMismatch {
+                    expected: classes.len(),
+                    found: priors.len(),
+                    context: "number of class priors",
+                })
+            }
+            Some(priors) if priors.iter().any(|&p| p.is_nan() || p <= 0.0) => {
+                return Err(LinearRegressionError::InvalidParameter("priors must be positive"))
+            }
+            Some(priors) => {
+                let total: f64 = priors.iter().sum();
+                priors.iter().map(|p| p / total).collect()
+            }
+            None => members.iter().map(|rows| rows.len() as f64 / x.nrows() as f64).collect(),
+        };
+
+        // Pooled within-class covariance (maximum likelihood)
+        let p = x.ncols();
+        let mut means = Array2::zeros((classes.len(), p));
+        let mut covariance = Array2::<f64>::zeros((p, p));
+        for (k, rows) in members.iter().enumerate() {
+            let x_k = x.select(Axis(0), rows);
+            let mean = x_k.mean_axis(Axis(0)).unwrap();
+            let centered = &x_k - &mean;
+            covariance += &centered.t().dot(&centered);
+            means.row_mut(k).assign(&mean);
+        }
+        covariance /= x.nrows() as f64;
+
+        if let Some(lambda) = self.shrinkage {
+            if !(0.0..=1.0).contains(&lambda) {
+                return Err(LinearRegressionError::InvalidParameter("shrinkage must be in [0, 1]"));
+            }
+            let mu = covariance.diag().sum() / p as f64;
+            covariance *= 1.0 - lambda;
+            covariance.diag_mut().mapv_inplace(|v| v + lambda * mu);
+        }
+
+        let precision = inverse(&covariance)
+            .map_err(|_| LinearRegressionError::NumericalError("singular covariance; try shrinkage"))?;
+        let coef = means.dot(&precision);
+        let intercept = Array1::from_shape_fn(classes.len(), |k| {
+            -0.5 * coef.row(k).dot(&means.row(k)) + priors[k].ln()
+        });
+
+        // Whiten with the within-class covariance, then take the principal
+        // axes of the class means in the whitened space
+        let (values, vectors) = symmetric_eigen(&covariance);
+        let whitening = &vectors / &values.mapv(|v| v.max(f64::MIN_POSITIVE).sqrt());
+        let overall_mean = priors.dot(&means);
+        let centered_means = (&means - &overall_mean).dot(&whitening) * &priors.mapv(f64::sqrt).insert_axis(Axis(1));
+        let (between, axes) = symmetric_eigen(&centered_means.t().dot(&centered_means));
+
+        let max_components = (classes.len() - 1).min(p);
+        let n_components = match self.n_components {
+            Some(n) if n == 0 || n > max_components => {
+                return Err(LinearRegressionError::InvalidParameter(
+                    "n_components must be between 1 and min(n_classes - 1, n_features)"
+                ))
+            }
+            Some(n) => n,
+            None => max_components,
+        };
+        let total: f64 = between.iter().take(max_components).map(|v| v.max(0.0)).sum();
+        self.explained_variance_ratio = between
+            .iter()
+            .take(n_components)
+            .map(|v| if total > 0.0 { v.max(0.0) / total } else { 0.0 })
+            .collect();
+        self.scalings = whitening.dot(&axes.slice(ndarray::s![.., ..n_components]));
+
+        self.classes = classes;
+        self.class_priors = priors;
+        self.means = means;
+        self.covariance = covariance;
+        self.coef = coef;
+        self.intercept = intercept;
+        self.overall_mean = overall_mean;
+        Ok(())
+    }
+
+    // Per-class linear discriminant scores, up to a shared constant the log
+    // of the joint density; one column per class
+    pub fn decision_function(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        self.check_features(x)?;
+        Ok(x.dot(&self.coef.t()) + &self.intercept)
+    }
+
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        let mut scores = self.decision_function(x)?;
+        for mut row in scores.rows_mut() {
+            let max = row.fold(f64::NEG_INFINITY, |m, &v| m.max(v));
+            row.mapv_inplace(|v| (v - max).exp());
+            let total = row.sum();
+            row /= total;
+        }
+        Ok(scores)
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let scores = self.decision_function(x)?;
+        Ok(scores
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let best = (1..row.len()).fold(0, |best, c| if row[c] > row[best] { c } else { best });
+                self.classes[best]
+            })
+            .collect())
+    }
+
+    // Project rows onto the discriminant axes
+    pub fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        self.check_features(x)?;
+        Ok((x - &self.overall_mean).dot(&self.scalings))
+    }
+
+    fn check_features(&self, x: &Array2<f64>) -> Result<(), LinearRegressionError> {
+        if self.classes.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        if x.ncols() != self.means.ncols() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.means.ncols(),
+                found: x.ncols(),
+                context: "number of features in prediction",
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for LinearDiscriminant {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fit for LinearDiscriminant {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        LinearDiscriminant::fit(self, x, y)
+    }
+}
+
+impl Predictor for LinearDiscriminant {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        LinearDiscriminant::predict(self, x)
+    }
+}
+
+impl Score for LinearDiscriminant {
+    fn is_classifier(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn three_classes() -> (Array2<f64>, Array1<f64>) {
+        // Classes offset along x0 with correlated within-class noise
+        let x = Array2::from_shape_fn((30, 2), |(i, j)| {
+            let class = (i / 10) as f64;
+            let noise = ((i * 7) % 10) as f64 / 10.0 - 0.45;
+            if j == 0 { 3.0 * class + noise } else { noise + ((i * 3) % 5) as f64 / 10.0 }
+        });
+        let y = Array1::from_shape_fn(30, |i| (i / 10) as f64);
+        (x, y)
+    }
+
+    #[test]
+    fn test_lda_classification() -> Result<(), LinearRegressionError> {
+        let (x, y) = three_classes();
+        let mut lda = LinearDiscriminant::new();
+        lda.fit(&x, &y)?;
+        assert_eq!(lda.predict(&x)?, y);
+        assert!(lda.priors().iter().all(|&p| (p - 1.0 / 3.0).abs() < 1e-12));
+
+        let proba = lda.predict_proba(&x)?;
+        assert!(proba.rows().into_iter().all(|row| (row.sum() - 1.0).abs() < 1e-12));
+
+        // Full shrinkage leaves a scaled identity covariance
+        let mut shrunk = LinearDiscriminant::new().with_shrinkage(1.0);
+        shrunk.fit(&x, &y)?;
+        let cov = shrunk.covariance();
+        assert!(cov[[0, 1]].abs() < 1e-12 && (cov[[0, 0]] - cov[[1, 1]]).abs() < 1e-12);
+
+        assert!(LinearDiscriminant::new().with_priors(vec![1.0]).fit(&x, &y).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lda_transform() -> Result<(), LinearRegressionError> {
+        let (x, y) = three_classes();
+        let mut lda = LinearDiscriminant::new().with_n_components(1);
+        lda.fit(&x, &y)?;
+        let z = lda.transform(&x)?;
+        assert_eq!(z.ncols(), 1);
+        assert!(lda.explained_variance_ratio()[0] > 0.9);
+
+        // The classes come out in order along the single axis (up to sign)
+        let class_mean = |k: usize| z.slice(ndarray::s![k * 10..(k + 1) * 10, 0]).mean().unwrap();
+        let (a, b, c) = (class_mean(0), class_mean(1), class_mean(2));
+        assert!((a < b && b < c) || (a > b && b > c));
+        // On the whitened axis the within-class variance is one
+        let within: f64 = (0..3)
+            .map(|k| z.slice(ndarray::s![k * 10..(k + 1) * 10, 0]).var(0.0))
+            .sum::<f64>() / 3.0;
+        assert!((within - 1.0).abs() < 1e-9);
+        assert!(LinearDiscriminant::new().with_n_components(3).fit(&x, &y).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,590 @@
+use crate::forest::MaxFeatures;
+use crate::metrics::r2_score;
+use crate::traits::{Estimator, Fit, Predictor, Score};
+use crate::random::{RandomState, Seeded};
+use crate::{try_map, LinearRegressionError};
+use ndarray::{Array1, Array2, Axis};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+fn check_training(x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+    if x.nrows() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.nrows(),
+            found: y.len(),
+            context: "number of samples in X and y",
+        });
+    }
+    if x.nrows() == 0 {
+        return Err(LinearRegressionError::EmptyData);
+    }
+    Ok(())
+}
+
+// Stacked generalization (Wolpert 1992): the base models' out-of-fold
+// predictions become the features of a final model, which learns how much to
+// trust each of them. For classifiers the meta-features are the predicted
+// labels, so the final model should be a classifier too.
+#[derive(Clone)]
+pub struct Stacking<F> {
+    estimators: Vec<Box<dyn Estimator>>,
+    final_estimator: F,
+    n_folds: usize,
+    seed: u64,
+    fitted: bool,
+}
+
+impl<F: Fit + Predictor> Stacking<F> {
+    pub fn new(estimators: Vec<Box<dyn Estimator>>, final_estimator: F) -> Self {
+        Self {
+            estimators,
+            final_estimator,
+            n_folds: 5,
+            seed: 0,
+            fitted: false,
+        }
+    }
+
+    // Folds used to produce the out-of-fold meta-features
+    pub fn with_n_folds(mut self, n_folds: usize) -> Self {
+        self.n_folds = n_folds;
+        self
+    }
+
+    // Seed for shuffling rows into folds
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    // Base models, refitted on all the training data
+    pub fn estimators(&self) -> &[Box<dyn Estimator>] {
+        &self.estimators
+    }
+
+    pub fn final_estimator(&self) -> &F {
+        &self.final_estimator
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        check_training(x, y)?;
+        if self.estimators.is_empty() {
+            return Err(LinearRegressionError::InvalidParameter("need at least one base estimator"));
+        }
+        if self.n_folds < 2 || self.n_folds > x.nrows() {
+            return Err(LinearRegressionError::InvalidParameter(
+                "n_folds must be between 2 and the number of samples"
+            ));
+        }
+
+        let mut order: Vec<usize> = (0..x.nrows()).collect();
+        order.shuffle(&mut StdRng::seed_from_u64(self.seed));
+
+        // Each row's meta-features come from models that never saw it
+        let mut meta = Array2::zeros((x.nrows(), self.estimators.len()));
+        for fold in 0..self.n_folds {
+            let (held_out, train): (Vec<usize>, Vec<usize>) =
+                (0..order.len()).partition(|&i| i % self.n_folds == fold);
+            let held_out: Vec<usize> = held_out.into_iter().map(|i| order[i]).collect();
+            let train: Vec<usize> = train.into_iter().map(|i| order[i]).collect();
+            let (x_train, y_train) = (x.select(Axis(0), &train), y.select(Axis(0), &train));
+            let x_held_out = x.select(Axis(0), &held_out);
+
+            for (m, estimator) in self.estimators.iter().enumerate() {
+                let mut model = estimator.clone();
+                model.fit(&x_train, &y_train)?;
+                let predictions = model.predict(&x_held_out)?;
+                for (&row, &p) in held_out.iter().zip(predictions.iter()) {
+                    meta[[row, m]] = p;
+                }
+            }
+        }
+
+        self.final_estimator.fit(&meta, y)?;
+        for estimator in self.estimators.iter_mut() {
+            estimator.fit(x, y)?;
+        }
+        self.fitted = true;
+        Ok(())
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        self.final_estimator.predict(&self.meta_features(x)?)
+    }
+
+    // Base model predictions, one column per estimator
+    pub fn meta_features(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        if !self.fitted {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        let mut meta = Array2::zeros((x.nrows(), self.estimators.len()));
+        for (m, estimator) in self.estimators.iter().enumerate() {
+            meta.column_mut(m).assign(&estimator.predict(x)?);
+        }
+        Ok(meta)
+    }
+}
+
+impl<F> Seeded for Stacking<F> {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+impl<F: Fit + Predictor> Fit for Stacking<F> {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        Stacking::fit(self, x, y)
+    }
+}
+
+impl<F: Fit + Predictor> Predictor for Stacking<F> {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        Stacking::predict(self, x)
+    }
+}
+
+impl<F: Fit + Score> Score for Stacking<F> {
+    fn is_classifier(&self) -> bool {
+        self.final_estimator.is_classifier()
+    }
+}
+
+// Per-estimator weights, checked against the number of estimators; equal
+// weights when none were given
+fn resolve_weights(weights: &Option<Vec<f64>>, n: usize) -> Result<Vec<f64>, LinearRegressionError> {
+    if n == 0 {
+        return Err(LinearRegressionError::InvalidParameter("need at least one base estimator"));
+    }
+    match weights {
+        None => Ok(vec![1.0; n]),
+        Some(weights) if weights.len() != n => Err(LinearRegressionError::DimensionMismatch {
+            expected: n,
+            found: weights.len(),
+            context: "number of voting weights",
+        }),
+        Some(weights) if weights.iter().any(|&w| w.is_nan() || w < 0.0) => {
+            Err(LinearRegressionError::InvalidParameter("weights must be non-negative"))
+        }
+        Some(weights) if weights.iter().sum::<f64>() <= 0.0 => {
+            Err(LinearRegressionError::InvalidParameter("weights must not all be zero"))
+        }
+        Some(weights) => Ok(weights.clone()),
+    }
+}
+
+macro_rules! voting_ensemble {
+    ($name:ident) => {
+        impl $name {
+            // The estimators may already be fitted, in which case `predict`
+            // can be called straight away; `fit` refits all of them
+            pub fn new(estimators: Vec<Box<dyn Estimator>>) -> Self {
+                Self { estimators, weights: None }
+            }
+
+            // One weight per estimator; equal weights by default
+            pub fn with_weights(mut self, weights: Vec<f64>) -> Self {
+                self.weights = Some(weights);
+                self
+            }
+
+            pub fn estimators(&self) -> &[Box<dyn Estimator>] {
+                &self.estimators
+            }
+
+            pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+                check_training(x, y)?;
+                resolve_weights(&self.weights, self.estimators.len())?;
+                for estimator in self.estimators.iter_mut() {
+                    estimator.fit(x, y)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl Fit for $name {
+            fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+                $name::fit(self, x, y)
+            }
+        }
+
+        impl Predictor for $name {
+            fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+                $name::predict(self, x)
+            }
+        }
+    };
+}
+
+// Weighted average of the estimators' predictions
+#[derive(Clone)]
+pub struct VotingRegressor {
+    estimators: Vec<Box<dyn Estimator>>,
+    weights: Option<Vec<f64>>,
+}
+
+voting_ensemble!(VotingRegressor);
+
+impl Score for VotingRegressor {}
+
+impl VotingRegressor {
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let weights = resolve_weights(&self.weights, self.estimators.len())?;
+        let mut total = Array1::zeros(x.nrows());
+        for (estimator, &w) in self.estimators.iter().zip(weights.iter()) {
+            total.scaled_add(w, &estimator.predict(x)?);
+        }
+        Ok(total / weights.iter().sum::<f64>())
+    }
+}
+
+// Weighted majority vote over the estimators' predicted labels (hard
+// voting); ties go to the smallest label
+#[derive(Clone)]
+pub struct VotingClassifier {
+    estimators: Vec<Box<dyn Estimator>>,
+    weights: Option<Vec<f64>>,
+}
+
+voting_ensemble!(VotingClassifier);
+
+impl Score for VotingClassifier {
+    fn is_classifier(&self) -> bool {
+        true
+    }
+}
+
+impl VotingClassifier {
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let weights = resolve_weights(&self.weights, self.estimators.len())?;
+        let votes: Vec<Array1<f64>> = self.estimators.iter().map(|e| e.predict(x)).collect::<Result<_, _>>()?;
+        Ok(Array1::from_shape_fn(x.nrows(), |i| {
+            let mut tally = Vec::new();
+            for (labels, &w) in votes.iter().zip(weights.iter()) {
+                add_vote(&mut tally, labels[i], w);
+            }
+            winner(&mut tally)
+        }))
+    }
+}
+
+// Vote tallies are short (label, total weight) lists
+fn add_vote(tally: &mut Vec<(f64, f64)>, label: f64, weight: f64) {
+    match tally.iter_mut().find(|(l, _)| *l == label) {
+        Some((_, total)) => *total += weight,
+        None => tally.push((label, weight)),
+    }
+}
+
+// Label with the most weight, ties going to the smallest label
+fn winner(tally: &mut [(f64, f64)]) -> f64 {
+    tally.sort_by(|a, b| a.0.total_cmp(&b.0));
+    tally.iter().fold(tally[0], |best, &t| if t.1 > best.1 { t } else { best }).0
+}
+
+// Bootstrap aggregating (Breiman 1996) around any estimator: each clone is
+// trained on a resample of the rows and, optionally, a random subset of the
+// columns. Predictions are averaged, or majority-voted for classifiers.
+#[derive(Debug, Clone)]
+pub struct Bagging<E> {
+    base: E,
+    n_estimators: usize,
+    max_samples: f64,
+    max_features: MaxFeatures,
+    bootstrap: bool,
+    majority_vote: bool,
+    seed: u64,
+    estimators: Vec<E>,
+    // Column indices each estimator was trained on, ascending
+    features: Vec<Vec<usize>>,
+    n_features: usize,
+    oob_prediction: Array1<f64>,
+    oob_score: Option<f64>,
+}
+
+impl<E: Fit + Predictor + Clone + Send + Sync> Bagging<E> {
+    pub fn new(base: E, n_estimators: usize) -> Self {
+        Self {
+            base,
+            n_estimators,
+            max_samples: 1.0,
+            max_features: MaxFeatures::All,
+            bootstrap: true,
+            majority_vote: false,
+            seed: 0,
+            estimators: Vec::new(),
+            features: Vec::new(),
+            n_features: 0,
+            oob_prediction: Array1::zeros(0),
+            oob_score: None,
+        }
+    }
+
+    // Rows drawn for each estimator, as a fraction of the training rows
+    pub fn with_max_samples(mut self, max_samples: f64) -> Self {
+        self.max_samples = max_samples;
+        self
+    }
+
+    // Columns given to each estimator (fixed per estimator, unlike the
+    // per-split sampling of a random forest)
+    pub fn with_max_features(mut self, max_features: MaxFeatures) -> Self {
+        self.max_features = max_features;
+        self
+    }
+
+    // Sample rows with replacement (the default) or without (pasting)
+    pub fn with_bootstrap(mut self, bootstrap: bool) -> Self {
+        self.bootstrap = bootstrap;
+        self
+    }
+
+    // Combine class labels by majority vote instead of averaging
+    pub fn with_majority_vote(mut self) -> Self {
+        self.majority_vote = true;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn estimators(&self) -> &[E] {
+        &self.estimators
+    }
+
+    pub fn features(&self) -> &[Vec<usize>] {
+        &self.features
+    }
+
+    // Aggregated prediction for each training row from the estimators that
+    // did not see it; NaN for rows that every estimator saw
+    pub fn oob_prediction(&self) -> &Array1<f64> {
+        &self.oob_prediction
+    }
+
+    // R² (or accuracy with majority voting) of the out-of-bag predictions,
+    // an almost free estimate of generalization; None if no row was ever
+    // left out
+    pub fn oob_score(&self) -> Option<f64> {
+        self.oob_score
+    }
+
+    // Estimators are trained with their own seeds, so results are identical
+    // with or without the `parallel` feature
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        check_training(x, y)?;
+        if self.n_estimators == 0 {
+            return Err(LinearRegressionError::InvalidParameter("n_estimators must be at least 1"));
+        }
+        if self.max_samples.is_nan() || self.max_samples <= 0.0 || (!self.bootstrap && self.max_samples > 1.0) {
+            return Err(LinearRegressionError::InvalidParameter(
+                "max_samples must be positive, and at most 1 without bootstrap"
+            ));
+        }
+        let n = x.nrows();
+        let n_rows = ((self.max_samples * n as f64).round() as usize).max(1);
+        let n_columns = self.max_features.resolve(x.ncols())?;
+
+        let train = |i: usize| -> Result<(E, Vec<usize>, Vec<bool>), LinearRegressionError> {
+            let mut rng = RandomState::new(self.seed).stream(i as u64).rng();
+            let rows: Vec<usize> = if self.bootstrap {
+                (0..n_rows).map(|_| rng.random_range(0..n)).collect()
+            } else {
+                rand::seq::index::sample(&mut rng, n, n_rows).into_vec()
+            };
+            let mut columns = rand::seq::index::sample(&mut rng, x.ncols(), n_columns).into_vec();
+            columns.sort_unstable();
+
+            let mut estimator = self.base.clone();
+            estimator.fit(&x.select(Axis(0), &rows).select(Axis(1), &columns), &y.select(Axis(0), &rows))?;
+            let mut in_bag = vec![false; n];
+            for &r in &rows {
+                in_bag[r] = true;
+            }
+            Ok((estimator, columns, in_bag))
+        };
+
+        let fitted = try_map((0..self.n_estimators).collect(), train)?;
+
+        // Out-of-bag aggregation, row by row over the estimators that left it out
+        let mut sums = vec![0.0; n];
+        let mut counts = vec![0usize; n];
+        let mut tallies = vec![Vec::new(); n];
+        for (estimator, columns, in_bag) in &fitted {
+            let rows: Vec<usize> = (0..n).filter(|&r| !in_bag[r]).collect();
+            if rows.is_empty() {
+                continue;
+            }
+            let predictions = estimator.predict(&x.select(Axis(0), &rows).select(Axis(1), columns))?;
+            for (&r, &p) in rows.iter().zip(predictions.iter()) {
+                sums[r] += p;
+                counts[r] += 1;
+                add_vote(&mut tallies[r], p, 1.0);
+            }
+        }
+        self.oob_prediction = Array1::from_shape_fn(n, |r| match counts[r] {
+            0 => f64::NAN,
+            _ if self.majority_vote => winner(&mut tallies[r]),
+            count => sums[r] / count as f64,
+        });
+        let covered: Vec<usize> = (0..n).filter(|&r| counts[r] > 0).collect();
+        self.oob_score = if covered.is_empty() {
+            None
+        } else if self.majority_vote {
+            let correct = covered.iter().filter(|&&r| self.oob_prediction[r] == y[r]).count();
+            Some(correct as f64 / covered.len() as f64)
+        } else {
+            Some(r2_score(&self.oob_prediction.select(Axis(0), &covered), &y.select(Axis(0), &covered)))
+        };
+
+        self.n_features = x.ncols();
+        (self.estimators, self.features) = fitted.into_iter().map(|(e, c, _)| (e, c)).unzip();
+        Ok(())
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        if self.estimators.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        if x.ncols() != self.n_features {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.n_features,
+                found: x.ncols(),
+                context: "number of features in prediction",
+            });
+        }
+        let predictions: Vec<Array1<f64>> = self
+            .estimators
+            .iter()
+            .zip(self.features.iter())
+            .map(|(estimator, columns)| estimator.predict(&x.select(Axis(1), columns)))
+            .collect::<Result<_, _>>()?;
+
+        if self.majority_vote {
+            return Ok(Array1::from_shape_fn(x.nrows(), |i| {
+                let mut tally = Vec::new();
+                for p in &predictions {
+                    add_vote(&mut tally, p[i], 1.0);
+                }
+                winner(&mut tally)
+            }));
+        }
+        let total = predictions.iter().fold(Array1::zeros(x.nrows()), |acc, p| acc + p);
+        Ok(total / self.estimators.len() as f64)
+    }
+}
+
+impl<E> Seeded for Bagging<E> {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+impl<E: Fit + Predictor + Clone + Send + Sync> Fit for Bagging<E> {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        Bagging::fit(self, x, y)
+    }
+}
+
+impl<E: Fit + Predictor + Clone + Send + Sync> Predictor for Bagging<E> {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        Bagging::predict(self, x)
+    }
+}
+
+impl<E: Fit + Predictor + Clone + Send + Sync> Score for Bagging<E> {
+    fn is_classifier(&self) -> bool {
+        self.majority_vote
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knn::KnnRegressor;
+    use crate::tree::{Criterion, DecisionTree};
+    use crate::LinearRegression;
+
+    #[test]
+    fn test_stacking() -> Result<(), LinearRegressionError> {
+        // A linear trend plus a step: each base model captures part of it
+        let x = Array2::from_shape_fn((80, 2), |(i, j)| if j == 0 { i as f64 / 8.0 } else { ((i * 13) % 7) as f64 });
+        let y = Array1::from_shape_fn(80, |i| 0.5 * x[[i, 0]] + if x[[i, 1]] > 3.0 { 2.0 } else { 0.0 });
+
+        let mut stack = Stacking::new(
+            vec![
+                Box::new(DecisionTree::new(Criterion::Mse).with_max_depth(4)),
+                Box::new(KnnRegressor::new(3)),
+                Box::new(LinearRegression::new(2, 0.01).with_epochs(5000)),
+            ],
+            LinearRegression::new(3, 0.01).with_epochs(5000),
+        )
+        .with_seed(2);
+        stack.fit(&x, &y)?;
+        assert_eq!(stack.meta_features(&x)?.ncols(), 3);
+        assert!(r2_score(&stack.predict(&x)?, &y) > 0.95);
+
+        // A boxed copy of the whole stack is itself an estimator
+        let copy: Box<dyn Estimator> = Box::new(stack.clone());
+        assert_eq!(copy.predict(&x)?, stack.predict(&x)?);
+        assert!(stack.with_n_folds(1).fit(&x, &y).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_voting() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((40, 1), |(i, _)| i as f64);
+        let y = x.column(0).mapv(|v| 0.5 * v);
+
+        // Already-fitted models can be blended without refitting
+        let mut linear = LinearRegression::new(1, 0.001).with_epochs(2000);
+        linear.fit(&x, &y)?;
+        let mut tree = DecisionTree::new(Criterion::Mse).with_max_depth(2);
+        tree.fit(&x, &y)?;
+        let blend =
+            VotingRegressor::new(vec![Box::new(linear.clone()), Box::new(tree.clone())]).with_weights(vec![3.0, 1.0]);
+        let expected = (linear.predict(&x)? * 3.0 + tree.predict(&x)?) / 4.0;
+        assert!(blend.predict(&x)?.iter().zip(expected.iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+
+        let labels = x.column(0).mapv(|v| if v < 20.0 { 0.0 } else { 1.0 });
+        let mut vote = VotingClassifier::new(vec![
+            Box::new(DecisionTree::new(Criterion::Gini).with_max_depth(1)),
+            Box::new(crate::knn::KnnClassifier::new(3)),
+            Box::new(crate::naive_bayes::GaussianNb::new()),
+        ]);
+        vote.fit(&x, &labels)?;
+        assert_eq!(vote.predict(&x)?, labels);
+        assert!(vote.with_weights(vec![1.0]).predict(&x).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bagging() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((100, 3), |(i, j)| ((i * (j + 2) * 7) % 23) as f64 / 4.0);
+        let y = Array1::from_shape_fn(100, |i| (x[[i, 0]] * 0.8).sin() * 2.0 + 0.3 * x[[i, 1]]);
+
+        let mut bagged = Bagging::new(DecisionTree::new(Criterion::Mse), 30).with_seed(5);
+        bagged.fit(&x, &y)?;
+        assert_eq!(bagged.estimators().len(), 30);
+        assert!(r2_score(&bagged.predict(&x)?, &y) > 0.9);
+        // With 30 bootstrap draws essentially every row is out of bag somewhere
+        assert!(bagged.oob_prediction().iter().all(|p| p.is_finite()));
+        assert!(bagged.oob_score().unwrap() > 0.5);
+
+        let labels = y.mapv(|v| if v > 1.0 { 1.0 } else { 0.0 });
+        let mut voted = Bagging::new(crate::knn::KnnClassifier::new(3), 15)
+            .with_max_features(MaxFeatures::Count(2))
+            .with_majority_vote()
+            .with_seed(5);
+        voted.fit(&x, &labels)?;
+        assert!(voted.features().iter().all(|columns| columns.len() == 2));
+        assert!(voted.oob_score().unwrap() > 0.6);
+        // Without bootstrap at full size every estimator sees every row
+        let mut pasted = voted.with_bootstrap(false);
+        pasted.fit(&x, &labels)?;
+        assert!(pasted.oob_score().is_none());
+        Ok(())
+    }
+}
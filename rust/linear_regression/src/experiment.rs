@@ -0,0 +1,182 @@
+use crate::data::Dataset;
+use crate::history::TrainingHistory;
+use crate::pipeline::Pipeline;
+use crate::spline::SplineBasis;
+use crate::LinearRegressionError;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// An experiment described in a TOML file, e.g.
+//
+//     [data]
+//     path = "housing.csv"
+//     target = "price"
+//
+//     [preprocessing]
+//     splines = { basis = "NaturalCubic", n_knots = 5 }
+//
+//     [model]
+//     type = "linear"
+//     learning_rate = 0.05
+//     epochs = 2000
+//
+//     [output]
+//     model = "model.json"
+//     history = "history.csv"
+//
+// Relative paths are taken from the directory of the file, so an experiment
+// directory can be moved or checked in as a whole. Unknown keys are errors
+// rather than silently ignored typos.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Experiment {
+    pub data: DataConfig,
+    #[serde(default)]
+    pub preprocessing: PreprocessingConfig,
+    #[serde(default)]
+    pub model: ModelConfig,
+    pub output: OutputConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DataConfig {
+    pub path: PathBuf,
+    pub target: String,
+}
+
+// Features are always standardized; splines, when given, expand them first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PreprocessingConfig {
+    pub splines: Option<SplineConfig>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SplineConfig {
+    pub basis: SplineBasis,
+    pub n_knots: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ModelConfig {
+    // Gradient descent linear regression, the model saved pipelines hold
+    Linear {
+        #[serde(default = "default_learning_rate")]
+        learning_rate: f64,
+        #[serde(default = "default_epochs")]
+        epochs: usize,
+    },
+}
+
+fn default_learning_rate() -> f64 {
+    0.01
+}
+
+fn default_epochs() -> usize {
+    1000
+}
+
+impl Default for ModelConfig {
+    fn default() -> Self {
+        Self::Linear { learning_rate: default_learning_rate(), epochs: default_epochs() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputConfig {
+    // Where the fitted pipeline is saved, and where `serve --config` loads it
+    pub model: PathBuf,
+    // Per-epoch training history as CSV
+    pub history: Option<PathBuf>,
+}
+
+impl Experiment {
+    // Paths are left as written
+    pub fn from_toml_str(text: &str) -> Result<Self, LinearRegressionError> {
+        toml::from_str(text).map_err(|e| LinearRegressionError::Parse(e.to_string()))
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, LinearRegressionError> {
+        let path = path.as_ref();
+        let mut experiment = Self::from_toml_str(&std::fs::read_to_string(path)?)?;
+        if let Some(dir) = path.parent() {
+            experiment.resolve_paths(dir);
+        }
+        Ok(experiment)
+    }
+
+    fn resolve_paths(&mut self, dir: &Path) {
+        let resolve = |p: &mut PathBuf| {
+            if p.is_relative() {
+                *p = dir.join(&*p);
+            }
+        };
+        resolve(&mut self.data.path);
+        resolve(&mut self.output.model);
+        if let Some(history) = &mut self.output.history {
+            resolve(history);
+        }
+    }
+
+    // Load the data, fit the pipeline and write the outputs
+    pub fn run(&self) -> Result<(Pipeline, TrainingHistory), LinearRegressionError> {
+        let dataset = Dataset::from_csv(&self.data.path, &self.data.target)?;
+        let ModelConfig::Linear { learning_rate, epochs } = self.model;
+        let (pipeline, history) = match self.preprocessing.splines {
+            Some(SplineConfig { basis, n_knots }) => {
+                Pipeline::fit_with_splines(&dataset, basis, n_knots, learning_rate, epochs)?
+            }
+            None => Pipeline::fit(&dataset, learning_rate, epochs)?,
+        };
+        pipeline.save(&self.output.model)?;
+        if let Some(path) = &self.output.history {
+            history.save_csv(path)?;
+        }
+        Ok((pipeline, history))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_experiment() -> Result<(), LinearRegressionError> {
+        let dir = std::env::temp_dir().join("linear_regression_experiment_test");
+        std::fs::create_dir_all(&dir)?;
+        let rows: Vec<String> = (0..20).map(|i| format!("{},{}", i, 3 * i + 2)).collect();
+        std::fs::write(dir.join("data.csv"), format!("x,y\n{}\n", rows.join("\n")))?;
+        let config = r#"
+            [data]
+            path = "data.csv"
+            target = "y"
+
+            [model]
+            type = "linear"
+            learning_rate = 0.1
+
+            [output]
+            model = "model.json"
+            history = "history.csv"
+        "#;
+        std::fs::write(dir.join("experiment.toml"), config)?;
+
+        let experiment = Experiment::from_file(dir.join("experiment.toml"))?;
+        assert_eq!(experiment.model, ModelConfig::Linear { learning_rate: 0.1, epochs: 1000 });
+        assert_eq!(experiment.output.model, dir.join("model.json"));
+        let (pipeline, history) = experiment.run()?;
+        assert_eq!(history.len(), 1000);
+        assert!((pipeline.predict_one(&[10.0])? - 32.0).abs() < 1e-3);
+        assert!(Pipeline::load(dir.join("model.json")).is_ok() && dir.join("history.csv").exists());
+        std::fs::remove_dir_all(&dir)?;
+
+        // Misspelled keys are caught
+        let typo = config.replace("learning_rate", "learnig_rate");
+        assert!(matches!(Experiment::from_toml_str(&typo), Err(LinearRegressionError::Parse(_))));
+        Ok(())
+    }
+}
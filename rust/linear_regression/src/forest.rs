@@ -0,0 +1,348 @@
+use crate::traits::{positive_column, BinaryScore, Fit, Predictor, Score};
+use crate::tree::{Criterion, DecisionTree, Splitter};
+use crate::random::{RandomState, Seeded};
+use crate::{try_map, LinearRegressionError};
+use ndarray::{Array1, Array2, Axis};
+use rand::Rng;
+
+// Number of features searched at each split
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaxFeatures {
+    All,
+    Sqrt,
+    Log2,
+    Count(usize),
+    // Fraction of the features, rounded down but at least one
+    Fraction(f64),
+}
+
+impl MaxFeatures {
+    pub(crate) fn resolve(&self, n_features: usize) -> Result<usize, LinearRegressionError> {
+        let n = n_features as f64;
+        let k = match *self {
+            Self::All => n_features,
+            Self::Sqrt => n.sqrt() as usize,
+            Self::Log2 => n.log2() as usize,
+            Self::Count(k) => k,
+            Self::Fraction(f) if f > 0.0 && f <= 1.0 => (f * n) as usize,
+            Self::Fraction(_) => {
+                return Err(LinearRegressionError::InvalidParameter("max_features fraction must be in (0, 1]"))
+            }
+        };
+        if k == 0 && matches!(self, Self::Count(_)) {
+            return Err(LinearRegressionError::InvalidParameter("max_features must be at least 1"));
+        }
+        Ok(k.clamp(1, n_features.max(1)))
+    }
+}
+
+// Bagged ensemble of decision trees, each grown on a bootstrap resample with
+// a random subset of features considered at every split. Regression averages
+// the trees' predictions; classification averages their class probabilities.
+#[derive(Debug, Clone)]
+pub struct RandomForest {
+    template: DecisionTree,
+    n_trees: usize,
+    max_features: MaxFeatures,
+    bootstrap: bool,
+    seed: u64,
+    classes: Vec<f64>,
+    trees: Vec<DecisionTree>,
+}
+
+impl RandomForest {
+    // Defaults follow the usual choices: sqrt(n_features) per split for
+    // classification, every feature for regression
+    pub fn new(criterion: Criterion, n_trees: usize) -> Self {
+        let max_features = if criterion.is_classification() { MaxFeatures::Sqrt } else { MaxFeatures::All };
+        Self {
+            template: DecisionTree::new(criterion),
+            n_trees,
+            max_features,
+            bootstrap: true,
+            seed: 0,
+            classes: Vec::new(),
+            trees: Vec::new(),
+        }
+    }
+
+    // Extremely randomized trees (Geurts et al. 2006): each split draws one
+    // random threshold per candidate feature instead of searching them all,
+    // and every tree sees the full training set. Much cheaper per split on
+    // wide data, and the extra randomness usually lowers variance further.
+    pub fn extra_trees(criterion: Criterion, n_trees: usize) -> Self {
+        let mut forest = Self::new(criterion, n_trees);
+        forest.template = forest.template.with_splitter(Splitter::Random);
+        forest.bootstrap = false;
+        forest
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.template = self.template.with_max_depth(max_depth);
+        self
+    }
+
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.template = self.template.with_min_samples_leaf(min_samples_leaf);
+        self
+    }
+
+    pub fn with_min_impurity_decrease(mut self, min_impurity_decrease: f64) -> Self {
+        self.template = self.template.with_min_impurity_decrease(min_impurity_decrease);
+        self
+    }
+
+    pub fn with_max_features(mut self, max_features: MaxFeatures) -> Self {
+        self.max_features = max_features;
+        self
+    }
+
+    // Without bootstrap every tree sees all rows and only the feature
+    // subsampling makes them differ
+    pub fn with_bootstrap(mut self, bootstrap: bool) -> Self {
+        self.bootstrap = bootstrap;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    // Passed on to every tree, so they can be exported with names too
+    pub fn with_feature_names(mut self, names: Vec<String>) -> Self {
+        self.template = self.template.with_feature_names(names);
+        self
+    }
+
+    pub fn feature_names(&self) -> Option<&[String]> {
+        self.template.feature_names()
+    }
+
+    pub fn trees(&self) -> &[DecisionTree] {
+        &self.trees
+    }
+
+    // Distinct labels seen in training, in ascending order; empty for regression
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    // Trees are grown with their own seeds, so results are identical with or
+    // without the `parallel` feature
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.n_trees == 0 {
+            return Err(LinearRegressionError::InvalidParameter("n_trees must be at least 1"));
+        }
+        let max_features = self.max_features.resolve(x.ncols())?;
+
+        let grow = |i: usize| {
+            let mut rng = RandomState::new(self.seed).stream(i as u64).rng();
+            let mut tree = self.template.clone().with_max_features(max_features).with_seed(rng.random());
+            if self.bootstrap {
+                let indices: Vec<usize> = (0..x.nrows()).map(|_| rng.random_range(0..x.nrows())).collect();
+                tree.fit(&x.select(Axis(0), &indices), &y.select(Axis(0), &indices))?;
+            } else {
+                tree.fit(x, y)?;
+            }
+            Ok(tree)
+        };
+
+        let trees = try_map((0..self.n_trees).collect(), grow)?;
+
+        self.classes = if self.template.criterion().is_classification() {
+            let mut classes = y.to_vec();
+            classes.sort_by(f64::total_cmp);
+            classes.dedup();
+            classes
+        } else {
+            Vec::new()
+        };
+        self.trees = trees;
+        Ok(())
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        if self.template.criterion().is_classification() {
+            let proba = self.predict_proba(x)?;
+            return Ok(proba
+                .rows()
+                .into_iter()
+                .map(|row| {
+                    let best = (1..row.len()).fold(0, |best, c| if row[c] > row[best] { c } else { best });
+                    self.classes[best]
+                })
+                .collect());
+        }
+
+        let mut sum = Array1::zeros(x.nrows());
+        for tree in self.fitted_trees()? {
+            sum += &tree.predict(x)?;
+        }
+        Ok(sum / self.trees.len() as f64)
+    }
+
+    // Mean class probabilities over the trees, one column per class in
+    // `classes()` order. A class missing from a tree's bootstrap sample gets
+    // probability zero from that tree.
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        if !self.template.criterion().is_classification() {
+            return Err(LinearRegressionError::InvalidParameter(
+                "predict_proba requires a classification criterion"
+            ));
+        }
+
+        let mut proba = Array2::zeros((x.nrows(), self.classes.len()));
+        for tree in self.fitted_trees()? {
+            let tree_proba = tree.predict_proba(x)?;
+            for (j, label) in tree.classes().iter().enumerate() {
+                let column = self.classes.binary_search_by(|c| c.total_cmp(label)).unwrap();
+                proba.column_mut(column).scaled_add(1.0, &tree_proba.column(j));
+            }
+        }
+        Ok(proba / self.trees.len() as f64)
+    }
+
+    // Mean of the trees' normalized impurity importances
+    pub fn feature_importances(&self) -> Result<Array1<f64>, LinearRegressionError> {
+        let trees = self.fitted_trees()?;
+        let mut importances = Array1::zeros(trees[0].n_features());
+        for tree in trees {
+            importances += &tree.feature_importances();
+        }
+        let total = importances.sum();
+        if total > 0.0 {
+            importances /= total;
+        }
+        Ok(importances)
+    }
+
+    // (name, importance) pairs in column order, named like the linear
+    // model's `coefficients`
+    pub fn named_feature_importances(&self) -> Result<Vec<(String, f64)>, LinearRegressionError> {
+        let importances = self.feature_importances()?;
+        let names = crate::column_names(self.feature_names(), importances.len());
+        Ok(names.into_iter().zip(importances).collect())
+    }
+
+    fn fitted_trees(&self) -> Result<&[DecisionTree], LinearRegressionError> {
+        if self.trees.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        Ok(&self.trees)
+    }
+}
+
+impl Seeded for RandomForest {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+impl Fit for RandomForest {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        RandomForest::fit(self, x, y)
+    }
+}
+
+impl Predictor for RandomForest {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        RandomForest::predict(self, x)
+    }
+}
+
+impl Score for RandomForest {
+    fn is_classifier(&self) -> bool {
+        self.template.criterion().is_classification()
+    }
+}
+
+impl BinaryScore for RandomForest {
+    fn binary_score(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        positive_column(self.predict_proba(x)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::r2_score;
+
+    #[test]
+    fn test_forest_regression() -> Result<(), LinearRegressionError> {
+        // Only the first column matters; the second is deterministic noise
+        let x = Array2::from_shape_fn((120, 2), |(i, j)| if j == 0 { i as f64 / 12.0 } else { ((i * 37) % 11) as f64 });
+        let y = x.column(0).mapv(|v| (v * 0.8).sin() * 3.0);
+
+        let mut forest = RandomForest::new(Criterion::Mse, 25).with_seed(7);
+        forest.fit(&x, &y)?;
+        assert_eq!(forest.trees().len(), 25);
+        assert!(r2_score(&forest.predict(&x)?, &y) > 0.95);
+
+        let importances = forest.feature_importances()?;
+        assert!((importances.sum() - 1.0).abs() < 1e-12);
+        assert!(importances[0] > 0.9);
+
+        let names = vec!["signal".to_string(), "noise".to_string()];
+        let mut named = RandomForest::new(Criterion::Mse, 25).with_seed(7).with_feature_names(names);
+        named.fit(&x, &y)?;
+        let pairs = named.named_feature_importances()?;
+        assert_eq!((pairs[0].0.as_str(), pairs[0].1), ("signal", importances[0]));
+        assert_eq!(named.trees()[0].importance_for("noise"), Some(named.trees()[0].feature_importances()[1]));
+        assert!(named.trees()[0].export_text(None)?.starts_with("|--- signal <= "));
+
+        // Same seed, same forest, however the seed is given
+        let mut again = RandomForest::new(Criterion::Mse, 25).with_random_state(RandomState::new(7));
+        again.fit(&x, &y)?;
+        assert_eq!(again.predict(&x)?, forest.predict(&x)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_forest_classification() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((60, 4), |(i, j)| {
+            let offset = if i < 30 { 0.0 } else { 3.0 };
+            offset + ((i * (j + 3)) % 7) as f64 / 7.0
+        });
+        let y = Array1::from_shape_fn(60, |i| if i < 30 { 1.0 } else { 2.0 });
+
+        let mut forest = RandomForest::new(Criterion::Gini, 15).with_max_depth(3).with_seed(1);
+        forest.fit(&x, &y)?;
+        assert_eq!(forest.classes(), &[1.0, 2.0]);
+        assert_eq!(forest.predict(&x)?, y);
+
+        let proba = forest.predict_proba(&x)?;
+        assert!(proba.rows().into_iter().all(|row| (row.sum() - 1.0).abs() < 1e-12));
+        assert!(RandomForest::new(Criterion::Gini, 0).fit(&x, &y).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_extra_trees() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((120, 2), |(i, j)| if j == 0 { i as f64 / 12.0 } else { ((i * 37) % 11) as f64 });
+        let y = x.column(0).mapv(|v| (v * 0.8).sin() * 3.0);
+
+        let mut forest = RandomForest::extra_trees(Criterion::Mse, 25).with_seed(3);
+        forest.fit(&x, &y)?;
+        assert!(r2_score(&forest.predict(&x)?, &y) > 0.95);
+        let importances = forest.feature_importances()?;
+        assert!((importances.sum() - 1.0).abs() < 1e-12);
+        assert!(importances[0] > importances[1]);
+
+        let labels = y.mapv(|v| if v > 0.0 { 1.0 } else { 0.0 });
+        let mut classifier = RandomForest::extra_trees(Criterion::Entropy, 25).with_seed(3);
+        classifier.fit(&x, &labels)?;
+        assert_eq!(classifier.predict(&x)?, labels);
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+use crate::linalg::{cholesky, cholesky_solve};
+use crate::optim::{Adam, Optimizer};
+use crate::traits::{Fit, Predictor, Score};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, Axis};
+
+fn squared_distances(a: &Array2<f64>, b: &Array2<f64>) -> Array2<f64> {
+    Array2::from_shape_fn((a.nrows(), b.nrows()), |(i, j)| {
+        a.row(i).iter().zip(b.row(j).iter()).map(|(p, q)| (p - q) * (p - q)).sum()
+    })
+}
+
+// Kernel matrix, its Cholesky factor, K⁻¹ y and the log marginal likelihood
+// for hyperparameters θ = (ln ℓ, ln σ_f², ln σ_n²)
+struct Evaluation {
+    factor: Array2<f64>,
+    alpha: Array1<f64>,
+    log_likelihood: f64,
+    gradient: [f64; 3],
+}
+
+fn evaluate(d2: &Array2<f64>, y: &Array1<f64>, theta: &[f64]) -> Result<Evaluation, LinearRegressionError> {
+    let n = y.len();
+    let (length_scale, signal, noise) = (theta[0].exp(), theta[1].exp(), theta[2].exp());
+    let correlation = d2.mapv(|d| (-d / (2.0 * length_scale * length_scale)).exp());
+    let mut k = &correlation * signal;
+    k.diag_mut().mapv_inplace(|v| v + noise);
+
+    let factor = cholesky(&k)?;
+    let alpha = cholesky_solve(&factor, &y.clone().insert_axis(Axis(1))).remove_axis(Axis(1));
+    let log_det: f64 = factor.diag().iter().map(|v| v.ln()).sum();
+    let log_likelihood = -0.5 * y.dot(&alpha) - log_det - 0.5 * n as f64 * (2.0 * std::f64::consts::PI).ln();
+
+    // ∂/∂θ_j = ½ tr((ααᵀ - K⁻¹) ∂K/∂θ_j)
+    let k_inv = cholesky_solve(&factor, &Array2::eye(n));
+    let mut gradient = [0.0; 3];
+    for i in 0..n {
+        for j in 0..n {
+            let w = alpha[i] * alpha[j] - k_inv[[i, j]];
+            let dk_signal = signal * correlation[[i, j]];
+            gradient[0] += w * dk_signal * d2[[i, j]] / (length_scale * length_scale);
+            gradient[1] += w * dk_signal;
+            if i == j {
+                gradient[2] += w * noise;
+            }
+        }
+    }
+    for g in gradient.iter_mut() {
+        *g *= 0.5;
+    }
+
+    Ok(Evaluation { factor, alpha, log_likelihood, gradient })
+}
+
+// Gaussian process regression with an RBF plus white-noise kernel,
+// k(x, x') = σ_f² exp(-|x - x'|² / (2ℓ²)) + σ_n² [x = x'].
+// Hyperparameters are tuned by gradient ascent on the log marginal
+// likelihood (Adam in log space). Training is O(n³), so this suits small
+// data sets where calibrated uncertainty matters.
+#[derive(Debug, Clone)]
+pub struct GaussianProcess {
+    length_scale: f64,
+    signal_variance: f64,
+    noise_variance: f64,
+    max_iter: usize,
+    learning_rate: f64,
+    x_train: Array2<f64>,
+    y_mean: f64,
+    factor: Array2<f64>,
+    alpha: Array1<f64>,
+    log_marginal_likelihood: f64,
+}
+
+impl GaussianProcess {
+    pub fn new() -> Self {
+        Self {
+            length_scale: 1.0,
+            signal_variance: 1.0,
+            noise_variance: 1e-2,
+            max_iter: 100,
+            learning_rate: 0.05,
+            x_train: Array2::zeros((0, 0)),
+            y_mean: 0.0,
+            factor: Array2::zeros((0, 0)),
+            alpha: Array1::zeros(0),
+            log_marginal_likelihood: f64::NEG_INFINITY,
+        }
+    }
+
+    // Starting values for the optimizer, or the final ones with zero iterations
+    pub fn with_length_scale(mut self, length_scale: f64) -> Self {
+        self.length_scale = length_scale;
+        self
+    }
+
+    pub fn with_signal_variance(mut self, signal_variance: f64) -> Self {
+        self.signal_variance = signal_variance;
+        self
+    }
+
+    pub fn with_noise_variance(mut self, noise_variance: f64) -> Self {
+        self.noise_variance = noise_variance;
+        self
+    }
+
+    // Gradient steps on the marginal likelihood; 0 keeps the kernel as given
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    pub fn length_scale(&self) -> f64 {
+        self.length_scale
+    }
+
+    pub fn signal_variance(&self) -> f64 {
+        self.signal_variance
+    }
+
+    pub fn noise_variance(&self) -> f64 {
+        self.noise_variance
+    }
+
+    // At the fitted hyperparameters, for the mean-centred targets
+    pub fn log_marginal_likelihood(&self) -> f64 {
+        self.log_marginal_likelihood
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if [self.length_scale, self.signal_variance, self.noise_variance]
+            .iter()
+            .any(|&v| v.is_nan() || v <= 0.0)
+        {
+            return Err(LinearRegressionError::InvalidParameter("kernel parameters must be positive"));
+        }
+
+        let y_mean = y.mean().unwrap();
+        let centered = y - y_mean;
+        let d2 = squared_distances(x, x);
+
+        let mut theta = [self.length_scale.ln(), self.signal_variance.ln(), self.noise_variance.ln()];
+        let mut optimizer = Adam::new(self.learning_rate);
+        for _ in 0..self.max_iter {
+            let ascent = evaluate(&d2, &centered, &theta)?.gradient;
+            let descent = ascent.map(|g| -g);
+            optimizer.step(0, &mut theta, &descent);
+            // Keep a little noise so K stays comfortably positive definite
+            theta[2] = theta[2].max(1e-10f64.ln());
+        }
+
+        let fitted = evaluate(&d2, &centered, &theta)?;
+        self.length_scale = theta[0].exp();
+        self.signal_variance = theta[1].exp();
+        self.noise_variance = theta[2].exp();
+        self.x_train = x.clone();
+        self.y_mean = y_mean;
+        self.factor = fitted.factor;
+        self.alpha = fitted.alpha;
+        self.log_marginal_likelihood = fitted.log_likelihood;
+        Ok(())
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        Ok(self.predict_with_variance(x)?.0)
+    }
+
+    // Posterior mean and variance of the latent function at each row; add
+    // `noise_variance()` for the variance of a new noisy observation
+    pub fn predict_with_variance(
+        &self,
+        x: &Array2<f64>,
+    ) -> Result<(Array1<f64>, Array1<f64>), LinearRegressionError> {
+        if self.x_train.nrows() == 0 {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        if x.ncols() != self.x_train.ncols() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.x_train.ncols(),
+                found: x.ncols(),
+                context: "number of features in prediction",
+            });
+        }
+
+        let scale = 2.0 * self.length_scale * self.length_scale;
+        let cross = squared_distances(x, &self.x_train).mapv(|d| self.signal_variance * (-d / scale).exp());
+        let mean = cross.dot(&self.alpha) + self.y_mean;
+        let v = cholesky_solve(&self.factor, &cross.t().to_owned());
+        let variance = Array1::from_shape_fn(x.nrows(), |j| {
+            (self.signal_variance - cross.row(j).dot(&v.column(j))).max(0.0)
+        });
+        Ok((mean, variance))
+    }
+}
+
+impl Default for GaussianProcess {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fit for GaussianProcess {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        GaussianProcess::fit(self, x, y)
+    }
+}
+
+impl Predictor for GaussianProcess {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        GaussianProcess::predict(self, x)
+    }
+}
+
+impl Score for GaussianProcess {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gaussian_process() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((20, 1), |(i, _)| i as f64 * 0.3);
+        let y = x.column(0).mapv(f64::sin);
+
+        let mut fixed = GaussianProcess::new().with_max_iter(0);
+        fixed.fit(&x, &y)?;
+        let mut gp = GaussianProcess::new();
+        gp.fit(&x, &y)?;
+        assert!(gp.log_marginal_likelihood() > fixed.log_marginal_likelihood());
+
+        // Interpolates between the training points with low uncertainty...
+        let between = Array2::from_shape_fn((5, 1), |(i, _)| 0.15 + i as f64 * 1.2);
+        let (mean, variance) = gp.predict_with_variance(&between)?;
+        let truth = between.column(0).mapv(f64::sin);
+        assert!(mean.iter().zip(truth.iter()).all(|(m, t)| (m - t).abs() < 0.05));
+        assert!(variance.iter().all(|&v| v < 0.01));
+
+        // ...and falls back to the prior far from the data
+        let (far_mean, far_variance) = gp.predict_with_variance(&Array2::from_elem((1, 1), 50.0))?;
+        assert!((far_mean[0] - y.mean().unwrap()).abs() < 1e-6);
+        assert!((far_variance[0] - gp.signal_variance()).abs() < 1e-6);
+        Ok(())
+    }
+}
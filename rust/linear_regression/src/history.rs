@@ -0,0 +1,139 @@
+use crate::{Float, LinearRegressionError};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+// Per-epoch record of an iterative fit. All vectors have one entry per epoch
+// (or per chunk for streamed training), in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainingHistory<F = f64> {
+    // Mean squared error on the training data before each update
+    pub train_loss: Vec<F>,
+    // Same on held-out data, when a validation set was given
+    pub validation_loss: Option<Vec<F>>,
+    // Step size used for each update
+    pub learning_rate: Vec<F>,
+    // Euclidean norm of the full gradient (weights and bias)
+    pub gradient_norm: Vec<F>,
+    // Wall-clock time since training started, taken at the end of each epoch
+    pub elapsed: Vec<Duration>,
+}
+
+impl<F: Float> TrainingHistory<F> {
+    pub fn new() -> Self {
+        Self {
+            train_loss: Vec::new(),
+            validation_loss: None,
+            learning_rate: Vec::new(),
+            gradient_norm: Vec::new(),
+            elapsed: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_capacity(epochs: usize) -> Self {
+        Self {
+            train_loss: Vec::with_capacity(epochs),
+            validation_loss: None,
+            learning_rate: Vec::with_capacity(epochs),
+            gradient_norm: Vec::with_capacity(epochs),
+            elapsed: Vec::with_capacity(epochs),
+        }
+    }
+
+    pub(crate) fn record(&mut self, train_loss: F, learning_rate: F, gradient_norm: F, elapsed: Duration) {
+        self.train_loss.push(train_loss);
+        self.learning_rate.push(learning_rate);
+        self.gradient_norm.push(gradient_norm);
+        self.elapsed.push(elapsed);
+    }
+
+    pub fn len(&self) -> usize {
+        self.train_loss.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.train_loss.is_empty()
+    }
+
+    pub fn initial_loss(&self) -> Option<F> {
+        self.train_loss.first().copied()
+    }
+
+    pub fn final_loss(&self) -> Option<F> {
+        self.train_loss.last().copied()
+    }
+
+    // Epoch with the lowest validation loss, or the lowest training loss
+    // without a validation set; the earliest wins ties
+    pub fn best_epoch(&self) -> Option<usize> {
+        let losses = self.validation_loss.as_ref().unwrap_or(&self.train_loss);
+        (0..losses.len()).filter(|&i| !losses[i].is_nan()).fold(None, |best, i| match best {
+            Some(b) if losses[b] <= losses[i] => Some(b),
+            _ => Some(i),
+        })
+    }
+
+    // One line per epoch under the header
+    // epoch,train_loss,validation_loss,learning_rate,gradient_norm,elapsed_seconds
+    // with an empty validation column when there is no validation set
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), LinearRegressionError> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record([
+            "epoch",
+            "train_loss",
+            "validation_loss",
+            "learning_rate",
+            "gradient_norm",
+            "elapsed_seconds",
+        ])?;
+        for epoch in 0..self.len() {
+            let validation = self.validation_loss.as_ref().map(|v| v[epoch].to_string()).unwrap_or_default();
+            writer.write_record([
+                epoch.to_string(),
+                self.train_loss[epoch].to_string(),
+                validation,
+                self.learning_rate[epoch].to_string(),
+                self.gradient_norm[epoch].to_string(),
+                self.elapsed[epoch].as_secs_f64().to_string(),
+            ])?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), LinearRegressionError> {
+        self.write_csv(std::fs::File::create(path)?)
+    }
+}
+
+impl<F: Float> Default for TrainingHistory<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_training_history() -> Result<(), LinearRegressionError> {
+        let mut history = TrainingHistory::new();
+        for (i, loss) in [4.0, 2.0, 1.0, 1.5].into_iter().enumerate() {
+            history.record(loss, 0.1, loss / 2.0, Duration::from_millis(i as u64));
+        }
+        assert_eq!((history.initial_loss(), history.final_loss()), (Some(4.0), Some(1.5)));
+        assert_eq!(history.best_epoch(), Some(2));
+        history.validation_loss = Some(vec![5.0, 3.0, 3.5, 3.0]);
+        assert_eq!(history.best_epoch(), Some(1));
+
+        let mut buffer = Vec::new();
+        history.write_csv(&mut buffer)?;
+        let text = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "epoch,train_loss,validation_loss,learning_rate,gradient_norm,elapsed_seconds");
+        assert_eq!(lines[2], "1,2,3,0.1,1,0.001");
+        Ok(())
+    }
+}
@@ -0,0 +1,379 @@
+use crate::random::{RandomState, Seeded};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, Axis};
+use rand::distr::weighted::WeightedIndex;
+use rand::distr::Distribution;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Hidden Markov model with discrete observations: hidden states 0..n_states
+// emit symbols 0..n_symbols. Sequences are slices of symbols.
+#[derive(Debug, Clone)]
+pub struct HiddenMarkovModel {
+    n_states: usize,
+    n_symbols: usize,
+    max_iter: usize,
+    tol: f64,
+    seed: u64,
+    start: Array1<f64>,
+    // transition[[i, j]] = P(next state j | state i)
+    transition: Array2<f64>,
+    // emission[[i, k]] = P(symbol k | state i)
+    emission: Array2<f64>,
+    history: Vec<f64>,
+}
+
+// Scaled forward-backward quantities for one sequence
+struct Posterior {
+    log_likelihood: f64,
+    // P(state at t | sequence), one row per time step
+    gamma: Array2<f64>,
+    // Expected transition counts summed over time
+    xi: Array2<f64>,
+}
+
+impl HiddenMarkovModel {
+    pub fn new(n_states: usize, n_symbols: usize) -> Self {
+        Self {
+            n_states,
+            n_symbols,
+            max_iter: 100,
+            tol: 1e-6,
+            seed: 0,
+            start: Array1::zeros(0),
+            transition: Array2::zeros((0, 0)),
+            emission: Array2::zeros((0, 0)),
+            history: Vec::new(),
+        }
+    }
+
+    // A model with known parameters, ready for decoding without training
+    pub fn from_parameters(
+        start: Array1<f64>,
+        transition: Array2<f64>,
+        emission: Array2<f64>,
+    ) -> Result<Self, LinearRegressionError> {
+        let n = start.len();
+        if transition.dim() != (n, n) {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: n,
+                found: transition.nrows(),
+                context: "transition matrix must be n_states x n_states",
+            });
+        }
+        if emission.nrows() != n {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: n,
+                found: emission.nrows(),
+                context: "rows of emission matrix",
+            });
+        }
+        let stochastic = |row: ndarray::ArrayView1<f64>| {
+            row.iter().all(|&p| p >= 0.0) && (row.sum() - 1.0).abs() < 1e-8
+        };
+        if !stochastic(start.view())
+            || !transition.rows().into_iter().all(stochastic)
+            || !emission.rows().into_iter().all(stochastic)
+        {
+            return Err(LinearRegressionError::InvalidParameter(
+                "probabilities must be non-negative and sum to one"
+            ));
+        }
+        let mut model = Self::new(n, emission.ncols());
+        model.start = start;
+        model.transition = transition;
+        model.emission = emission;
+        Ok(model)
+    }
+
+    // Baum-Welch iterations
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    // Stop once the total log-likelihood improves by less than this
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    // Seed for the random initial parameters of `fit`
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn start_probabilities(&self) -> &Array1<f64> {
+        &self.start
+    }
+
+    pub fn transition(&self) -> &Array2<f64> {
+        &self.transition
+    }
+
+    pub fn emission(&self) -> &Array2<f64> {
+        &self.emission
+    }
+
+    // Total log-likelihood of the training sequences after each iteration
+    pub fn history(&self) -> &[f64] {
+        &self.history
+    }
+
+    // Baum-Welch (EM) from random stochastic matrices. Each iteration
+    // cannot decrease the likelihood, but the optimum found is local.
+    pub fn fit(&mut self, sequences: &[Vec<usize>]) -> Result<(), LinearRegressionError> {
+        if sequences.is_empty() || sequences.iter().any(|s| s.is_empty()) {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.n_states == 0 || self.n_symbols == 0 {
+            return Err(LinearRegressionError::InvalidParameter("need at least one state and one symbol"));
+        }
+        if sequences.iter().flatten().any(|&o| o >= self.n_symbols) {
+            return Err(LinearRegressionError::InvalidParameter("symbol out of range"));
+        }
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut random_rows = |rows: usize, cols: usize| {
+            let mut m = Array2::from_shape_fn((rows, cols), |_| rng.random_range(0.1..1.0));
+            for mut row in m.rows_mut() {
+                let total = row.sum();
+                row /= total;
+            }
+            m
+        };
+        self.start = random_rows(1, self.n_states).remove_axis(Axis(0));
+        self.transition = random_rows(self.n_states, self.n_states);
+        self.emission = random_rows(self.n_states, self.n_symbols);
+
+        self.history.clear();
+        for _ in 0..self.max_iter {
+            let mut start = Array1::<f64>::zeros(self.n_states);
+            let mut transitions = Array2::<f64>::zeros((self.n_states, self.n_states));
+            let mut emissions = Array2::<f64>::zeros((self.n_states, self.n_symbols));
+            let mut total = 0.0;
+            for sequence in sequences {
+                let posterior = self.posterior(sequence);
+                total += posterior.log_likelihood;
+                start += &posterior.gamma.row(0);
+                transitions += &posterior.xi;
+                for (gamma, &o) in posterior.gamma.rows().into_iter().zip(sequence) {
+                    emissions.column_mut(o).scaled_add(1.0, &gamma);
+                }
+            }
+
+            // Rows with no expected visits keep their previous values
+            let normalize = |counts: &mut Array2<f64>, old: &Array2<f64>| {
+                for (mut row, old) in counts.rows_mut().into_iter().zip(old.rows()) {
+                    let sum = row.sum();
+                    if sum > 0.0 { row /= sum } else { row.assign(&old) }
+                }
+            };
+            normalize(&mut transitions, &self.transition);
+            normalize(&mut emissions, &self.emission);
+            self.start = &start / start.sum();
+            self.transition = transitions;
+            self.emission = emissions;
+
+            let converged = self.history.last().is_some_and(|&previous| total - previous < self.tol);
+            self.history.push(total);
+            if converged {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    // log P(sequence) by the scaled forward algorithm
+    pub fn log_likelihood(&self, sequence: &[usize]) -> Result<f64, LinearRegressionError> {
+        self.check(sequence)?;
+        Ok(self.forward(sequence).2)
+    }
+
+    // Most likely state path and its joint log-probability with the sequence
+    pub fn viterbi(&self, sequence: &[usize]) -> Result<(Vec<usize>, f64), LinearRegressionError> {
+        self.check(sequence)?;
+        let n = self.n_states;
+        let log_transition = self.transition.mapv(f64::ln);
+        let mut score: Array1<f64> =
+            Array1::from_shape_fn(n, |i| self.start[i].ln() + self.emission[[i, sequence[0]]].ln());
+        let mut back = Array2::<usize>::zeros((sequence.len(), n));
+
+        for (t, &o) in sequence.iter().enumerate().skip(1) {
+            let mut next = Array1::zeros(n);
+            for j in 0..n {
+                let (best, value) = (0..n)
+                    .map(|i| (i, score[i] + log_transition[[i, j]]))
+                    .fold((0, f64::NEG_INFINITY), |best, (i, v)| if v > best.1 { (i, v) } else { best });
+                back[[t, j]] = best;
+                next[j] = value + self.emission[[j, o]].ln();
+            }
+            score = next;
+        }
+
+        let (mut state, log_probability) = score
+            .iter()
+            .enumerate()
+            .fold((0, f64::NEG_INFINITY), |best, (i, &v)| if v > best.1 { (i, v) } else { best });
+        let mut path = vec![state; sequence.len()];
+        for t in (1..sequence.len()).rev() {
+            state = back[[t, state]];
+            path[t - 1] = state;
+        }
+        Ok((path, log_probability))
+    }
+
+    // Draw a state path and its observations of the given length
+    pub fn sample(&self, length: usize, seed: u64) -> Result<(Vec<usize>, Vec<usize>), LinearRegressionError> {
+        if self.start.is_empty() {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        let invalid = |_| LinearRegressionError::InvalidParameter("probabilities must not all be zero");
+        let mut rng = StdRng::seed_from_u64(seed);
+        let rows = |m: &Array2<f64>| -> Result<Vec<WeightedIndex<f64>>, LinearRegressionError> {
+            m.rows().into_iter().map(|row| WeightedIndex::new(row.iter().copied()).map_err(invalid)).collect()
+        };
+        let emit = rows(&self.emission)?;
+        let step = rows(&self.transition)?;
+
+        let mut states = Vec::with_capacity(length);
+        let mut symbols = Vec::with_capacity(length);
+        let mut state = WeightedIndex::new(self.start.iter().copied()).map_err(invalid)?.sample(&mut rng);
+        for _ in 0..length {
+            states.push(state);
+            symbols.push(emit[state].sample(&mut rng));
+            state = step[state].sample(&mut rng);
+        }
+        Ok((states, symbols))
+    }
+
+    fn check(&self, sequence: &[usize]) -> Result<(), LinearRegressionError> {
+        if self.start.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        if sequence.is_empty() {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if sequence.iter().any(|&o| o >= self.n_symbols) {
+            return Err(LinearRegressionError::InvalidParameter("symbol out of range"));
+        }
+        Ok(())
+    }
+
+    // Forward variables normalized at every step, the normalizers, and the
+    // log-likelihood (the sum of their logs)
+    fn forward(&self, sequence: &[usize]) -> (Array2<f64>, Vec<f64>, f64) {
+        let n = self.n_states;
+        let mut alpha = Array2::zeros((sequence.len(), n));
+        let mut scales = Vec::with_capacity(sequence.len());
+        for (t, &o) in sequence.iter().enumerate() {
+            let mut c = 0.0;
+            for j in 0..n {
+                let predicted = if t == 0 {
+                    self.start[j]
+                } else {
+                    (0..n).map(|i| alpha[[t - 1, i]] * self.transition[[i, j]]).sum()
+                };
+                alpha[[t, j]] = predicted * self.emission[[j, o]];
+                c += alpha[[t, j]];
+            }
+            // An impossible observation would make every later step 0/0
+            let c = if c > 0.0 { c } else { f64::MIN_POSITIVE };
+            alpha.row_mut(t).mapv_inplace(|v| v / c);
+            scales.push(c);
+        }
+        let log_likelihood = scales.iter().map(|c| c.ln()).sum();
+        (alpha, scales, log_likelihood)
+    }
+
+    fn posterior(&self, sequence: &[usize]) -> Posterior {
+        let n = self.n_states;
+        let (alpha, scales, log_likelihood) = self.forward(sequence);
+        let len = sequence.len();
+        let mut beta = Array2::ones((len, n));
+        let mut xi = Array2::zeros((n, n));
+        let mut weighted = vec![0.0; n];
+        for t in (0..len - 1).rev() {
+            // b_j(o_{t+1}) β_{t+1}(j) / c_{t+1}
+            for (j, w) in weighted.iter_mut().enumerate() {
+                *w = self.emission[[j, sequence[t + 1]]] * beta[[t + 1, j]] / scales[t + 1];
+            }
+            for i in 0..n {
+                let mut b = 0.0;
+                for (j, &w) in weighted.iter().enumerate() {
+                    let term = self.transition[[i, j]] * w;
+                    xi[[i, j]] += alpha[[t, i]] * term;
+                    b += term;
+                }
+                beta[[t, i]] = b;
+            }
+        }
+        Posterior { log_likelihood, gamma: alpha * beta, xi }
+    }
+}
+
+impl Seeded for HiddenMarkovModel {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    // The healthy/fever example: states (healthy, fever), observations
+    // (normal, cold, dizzy)
+    fn clinic() -> Result<HiddenMarkovModel, LinearRegressionError> {
+        HiddenMarkovModel::from_parameters(
+            Array1::from(vec![0.6, 0.4]),
+            arr2(&[[0.7, 0.3], [0.4, 0.6]]),
+            arr2(&[[0.5, 0.4, 0.1], [0.1, 0.3, 0.6]]),
+        )
+    }
+
+    #[test]
+    fn test_hmm_decoding() -> Result<(), LinearRegressionError> {
+        let model = clinic()?;
+        let sequence = [0, 1, 2];
+        let (path, log_probability) = model.viterbi(&sequence)?;
+        assert_eq!(path, vec![0, 0, 1]);
+        assert!((log_probability - 0.01512f64.ln()).abs() < 1e-12);
+
+        // Likelihood by brute force over all 2^3 state paths
+        let mut total = 0.0;
+        for code in 0..8usize {
+            let s: Vec<usize> = (0..3).map(|t| (code >> t) & 1).collect();
+            let mut p = model.start_probabilities()[s[0]] * model.emission()[[s[0], sequence[0]]];
+            for t in 1..3 {
+                p *= model.transition()[[s[t - 1], s[t]]] * model.emission()[[s[t], sequence[t]]];
+            }
+            total += p;
+        }
+        assert!((model.log_likelihood(&sequence)? - total.ln()).abs() < 1e-12);
+        assert!(model.viterbi(&[3]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_baum_welch() -> Result<(), LinearRegressionError> {
+        let truth = clinic()?;
+        let sequences: Vec<Vec<usize>> =
+            (0..20).map(|s| truth.sample(50, s).map(|(_, o)| o)).collect::<Result<_, _>>()?;
+
+        let mut model = HiddenMarkovModel::new(2, 3).with_seed(3);
+        model.fit(&sequences)?;
+        let history = model.history();
+        assert!(history.windows(2).all(|w| w[1] >= w[0] - 1e-9));
+
+        // EM climbs well above its random start, to within a couple of nats
+        // of the generating model on its own data
+        let fitted: f64 = sequences.iter().map(|s| model.log_likelihood(s)).sum::<Result<f64, _>>()?;
+        let generating: f64 = sequences.iter().map(|s| truth.log_likelihood(s)).sum::<Result<f64, _>>()?;
+        assert!(fitted > history[0] + 1.0 && fitted > generating - 2.0);
+        assert!(model.transition().rows().into_iter().all(|row| (row.sum() - 1.0).abs() < 1e-12));
+        Ok(())
+    }
+}
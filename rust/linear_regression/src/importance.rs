@@ -0,0 +1,86 @@
+use crate::traits::Predictor;
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, Axis};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+#[derive(Debug, Clone)]
+pub struct PermutationImportance {
+    // Score of the model on the unshuffled data
+    pub baseline: f64,
+    // Score drop for each feature (rows) and repeat (columns)
+    pub importances: Array2<f64>,
+    pub mean: Array1<f64>,
+    pub std: Array1<f64>,
+}
+
+// Shuffle one column at a time and measure how much `metric` degrades.
+// `metric(predictions, y)` must be a score where higher is better, such as
+// `r2_score`; negate loss metrics like MSE before passing them in.
+pub fn permutation_importance<P, M>(
+    model: &P,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+    metric: M,
+    n_repeats: usize,
+    seed: u64,
+) -> Result<PermutationImportance, LinearRegressionError>
+where
+    P: Predictor + ?Sized,
+    M: Fn(&Array1<f64>, &Array1<f64>) -> f64,
+{
+    if x.nrows() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.nrows(),
+            found: y.len(),
+            context: "number of samples in X and y",
+        });
+    }
+    if n_repeats == 0 {
+        return Err(LinearRegressionError::InvalidParameter("n_repeats must be at least 1"));
+    }
+
+    let baseline = metric(&model.predict(x)?, y);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut importances = Array2::zeros((x.ncols(), n_repeats));
+    let mut shuffled = x.clone();
+    let mut order: Vec<usize> = (0..x.nrows()).collect();
+
+    for j in 0..x.ncols() {
+        for r in 0..n_repeats {
+            order.shuffle(&mut rng);
+            let permuted = x.column(j).select(Axis(0), &order);
+            shuffled.column_mut(j).assign(&permuted);
+            importances[[j, r]] = baseline - metric(&model.predict(&shuffled)?, y);
+        }
+        shuffled.column_mut(j).assign(&x.column(j));
+    }
+
+    let mean = importances.mean_axis(Axis(1)).unwrap();
+    let std = importances.std_axis(Axis(1), 0.0);
+    Ok(PermutationImportance { baseline, importances, mean, std })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::r2_score;
+    use crate::LinearRegression;
+
+    #[test]
+    fn test_permutation_importance_ranks_features() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((50, 3), |(i, j)| ((i * (j + 2)) as f64 * 0.7).sin());
+        let model = LinearRegression::from_parameters(Array1::from(vec![3.0, 0.5, 0.0]), 0.0);
+        let y = model.predict(&x)?;
+
+        let result = permutation_importance(&model, &x, &y, r2_score, 5, 7)?;
+        assert!((result.baseline - 1.0).abs() < 1e-12);
+        assert!(result.mean[0] > result.mean[1]);
+        assert!(result.mean[1] > 0.0);
+        // A feature with zero weight cannot change the predictions
+        assert!(result.mean[2].abs() < 1e-12);
+        assert_eq!(result.importances.dim(), (3, 5));
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+use crate::linalg::{inverse, with_intercept};
+use crate::stats::{student_t_ppf, student_t_two_sided_p};
+use crate::{LinearRegression, LinearRegressionError};
+use ndarray::{Array1, Array2};
+
+// Classical OLS inference for a fitted model. Every per-coefficient array is
+// ordered intercept first, followed by the features in column order.
+//
+// The covariance is sigma² (X'X)⁻¹ with X including the intercept column, so
+// the results are only meaningful once gradient descent has converged to the
+// least-squares solution.
+#[derive(Debug, Clone)]
+pub struct CoefficientInference {
+    pub coefficients: Array1<f64>,
+    pub covariance: Array2<f64>,
+    pub std_errors: Array1<f64>,
+    pub t_values: Array1<f64>,
+    pub p_values: Array1<f64>,
+    // Residual variance estimate RSS / df_resid
+    pub sigma2: f64,
+    pub df_resid: usize,
+}
+
+impl CoefficientInference {
+    pub fn new(
+        model: &LinearRegression,
+        x: &Array2<f64>,
+        y: &Array1<f64>,
+    ) -> Result<Self, LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+
+        let n_params = x.ncols() + 1;
+        if x.nrows() <= n_params {
+            return Err(LinearRegressionError::NumericalError(
+                "Not enough samples for residual degrees of freedom"
+            ));
+        }
+        let df_resid = x.nrows() - n_params;
+
+        let residuals = y - &model.predict(x)?;
+        let sigma2 = residuals.dot(&residuals) / df_resid as f64;
+
+        let design = with_intercept(x);
+        let covariance = inverse(&design.t().dot(&design))? * sigma2;
+
+        let mut coefficients = Array1::zeros(n_params);
+        coefficients[0] = model.bias;
+        coefficients.slice_mut(ndarray::s![1..]).assign(&model.weights);
+
+        let std_errors = covariance.diag().mapv(f64::sqrt);
+        let t_values = &coefficients / &std_errors;
+        let p_values = t_values.mapv(|t| student_t_two_sided_p(t, df_resid as f64));
+
+        Ok(Self {
+            coefficients,
+            covariance,
+            std_errors,
+            t_values,
+            p_values,
+            sigma2,
+            df_resid,
+        })
+    }
+
+    // (1 - alpha) confidence intervals, one row per coefficient with columns
+    // [lower, upper], intercept first
+    pub fn conf_int(&self, alpha: f64) -> Result<Array2<f64>, LinearRegressionError> {
+        if !(alpha > 0.0 && alpha < 1.0) {
+            return Err(LinearRegressionError::InvalidParameter("alpha must be in (0, 1)"));
+        }
+
+        let t_crit = student_t_ppf(1.0 - alpha / 2.0, self.df_resid as f64);
+        let mut intervals = Array2::zeros((self.coefficients.len(), 2));
+        for (i, (&coef, &se)) in self.coefficients.iter().zip(self.std_errors.iter()).enumerate() {
+            intervals[[i, 0]] = coef - t_crit * se;
+            intervals[[i, 1]] = coef + t_crit * se;
+        }
+        Ok(intervals)
+    }
+
+    // (1 - alpha) prediction intervals for new rows. The variance of each new
+    // observation is the residual variance plus x̃ Cov x̃' from the parameters.
+    pub fn predict_interval(
+        &self,
+        x: &Array2<f64>,
+        alpha: f64,
+    ) -> Result<PredictionInterval, LinearRegressionError> {
+        if !(alpha > 0.0 && alpha < 1.0) {
+            return Err(LinearRegressionError::InvalidParameter("alpha must be in (0, 1)"));
+        }
+        if x.ncols() + 1 != self.coefficients.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.coefficients.len() - 1,
+                found: x.ncols(),
+                context: "number of features in prediction",
+            });
+        }
+
+        let design = with_intercept(x);
+        let mean = design.dot(&self.coefficients);
+        let t_crit = student_t_ppf(1.0 - alpha / 2.0, self.df_resid as f64);
+
+        let half_widths: Array1<f64> = design
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let parameter_var = row.dot(&self.covariance.dot(&row));
+                t_crit * (self.sigma2 + parameter_var).sqrt()
+            })
+            .collect();
+
+        Ok(PredictionInterval {
+            lower: &mean - &half_widths,
+            upper: &mean + &half_widths,
+            mean,
+        })
+    }
+}
+
+// Point predictions from `CoefficientInference::predict_interval` with their interval bounds
+#[derive(Debug, Clone)]
+pub struct PredictionInterval {
+    pub mean: Array1<f64>,
+    pub lower: Array1<f64>,
+    pub upper: Array1<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_simple_regression_standard_errors() -> Result<(), LinearRegressionError> {
+        let x = arr2(&[[1.0], [2.0], [3.0], [4.0], [5.0]]);
+        let y = Array1::from(vec![2.9, 5.1, 7.2, 8.8, 11.0]);
+
+        // Closed-form OLS fit: slope 1.99, intercept 1.03
+        let model = LinearRegression::from_parameters(Array1::from(vec![1.99]), 1.03);
+
+        let inference = CoefficientInference::new(&model, &x, &y)?;
+
+        // se(slope) = sqrt(s² / Sxx), se(intercept) = sqrt(s² (1/n + x̄² / Sxx))
+        let residuals = &y - &model.predict(&x)?;
+        let s2 = residuals.dot(&residuals) / 3.0;
+        assert!((inference.sigma2 - s2).abs() < 1e-12);
+        assert!((inference.std_errors[1] - (s2 / 10.0).sqrt()).abs() < 1e-10);
+        assert!((inference.std_errors[0] - (s2 * (0.2 + 9.0 / 10.0)).sqrt()).abs() < 1e-10);
+        assert_eq!(inference.df_resid, 3);
+        assert!(inference.p_values[1] < 1e-3);
+
+        // 95% interval uses t(0.975, 3) = 3.182446
+        let ci = inference.conf_int(0.05)?;
+        assert!((ci[[1, 0]] - (1.99 - 3.182_446_305 * inference.std_errors[1])).abs() < 1e-7);
+        assert!((ci[[1, 1]] - (1.99 + 3.182_446_305 * inference.std_errors[1])).abs() < 1e-7);
+        assert!(ci[[0, 0]] < 1.03 && ci[[0, 1]] > 1.03);
+        assert!(inference.conf_int(1.5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_predict_interval() -> Result<(), LinearRegressionError> {
+        let x = arr2(&[[1.0], [2.0], [3.0], [4.0], [5.0]]);
+        let y = Array1::from(vec![2.9, 5.1, 7.2, 8.8, 11.0]);
+        let model = LinearRegression::from_parameters(Array1::from(vec![1.99]), 1.03);
+        let inference = CoefficientInference::new(&model, &x, &y)?;
+
+        let x_new = arr2(&[[3.0], [10.0]]);
+        let interval = inference.predict_interval(&x_new, 0.05)?;
+        assert!((interval.mean[0] - 7.0).abs() < 1e-12);
+
+        // Simple regression: var = s² (1 + 1/n + (x0 - x̄)² / Sxx)
+        let half = 3.182_446_305 * (inference.sigma2 * (1.0 + 0.2)).sqrt();
+        assert!((interval.upper[0] - interval.mean[0] - half).abs() < 1e-7);
+        // Extrapolating further from x̄ widens the interval
+        assert!(interval.upper[1] - interval.lower[1] > interval.upper[0] - interval.lower[0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,271 @@
+use crate::stats::quantile;
+use crate::random::{RandomState, Seeded};
+use crate::{try_map, LinearRegressionError};
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+
+// Average path length of an unsuccessful search in a binary search tree with
+// `n` keys, used to normalize depths and to extend paths that end in a leaf
+// still holding several samples
+fn average_path_length(n: usize) -> f64 {
+    match n {
+        0 | 1 => 0.0,
+        2 => 1.0,
+        _ => {
+            let n = n as f64;
+            2.0 * ((n - 1.0).ln() + EULER_GAMMA) - 2.0 * (n - 1.0) / n
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum IsolationNode {
+    // Rows with `x[feature] < threshold` go to `left`
+    Split { feature: usize, threshold: f64, left: usize, right: usize },
+    Leaf { size: usize },
+}
+
+// Isolation forest (Liu et al. 2008): anomalies are few and different, so
+// random axis-aligned cuts separate them from the rest in fewer steps. Each
+// tree is grown on a small subsample until every point is isolated or a
+// depth limit of log2(subsample size) is reached.
+#[derive(Debug, Clone)]
+pub struct IsolationForest {
+    n_trees: usize,
+    max_samples: usize,
+    contamination: f64,
+    seed: u64,
+    n_features: usize,
+    sample_size: usize,
+    trees: Vec<Vec<IsolationNode>>,
+    threshold: f64,
+}
+
+impl IsolationForest {
+    pub fn new(n_trees: usize) -> Self {
+        Self {
+            n_trees,
+            max_samples: 256,
+            contamination: 0.1,
+            seed: 0,
+            n_features: 0,
+            sample_size: 0,
+            trees: Vec::new(),
+            threshold: f64::INFINITY,
+        }
+    }
+
+    // Rows drawn (without replacement) for each tree, capped at the number
+    // of training rows
+    pub fn with_max_samples(mut self, max_samples: usize) -> Self {
+        self.max_samples = max_samples;
+        self
+    }
+
+    // Expected share of outliers in the training data; sets the score
+    // threshold above which rows are flagged
+    pub fn with_contamination(mut self, contamination: f64) -> Self {
+        self.contamination = contamination;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    // Scores above this are flagged as outliers
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    // Trees are grown with their own seeds, so results are identical with or
+    // without the `parallel` feature
+    pub fn fit(&mut self, x: &Array2<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.n_trees == 0 {
+            return Err(LinearRegressionError::InvalidParameter("n_trees must be at least 1"));
+        }
+        if self.max_samples < 2 {
+            return Err(LinearRegressionError::InvalidParameter("max_samples must be at least 2"));
+        }
+        if self.contamination.is_nan() || self.contamination <= 0.0 || self.contamination >= 1.0 {
+            return Err(LinearRegressionError::InvalidParameter("contamination must be in (0, 1)"));
+        }
+
+        let sample_size = self.max_samples.min(x.nrows());
+        let max_depth = (sample_size as f64).log2().ceil() as usize;
+        let grow = |i: usize| {
+            let mut rng = RandomState::new(self.seed).stream(i as u64).rng();
+            let mut rows = rand::seq::index::sample(&mut rng, x.nrows(), sample_size).into_vec();
+            let mut nodes = Vec::new();
+            grow_tree(x, &mut rows, 0, max_depth, &mut rng, &mut nodes);
+            Ok(nodes)
+        };
+
+        self.trees = try_map((0..self.n_trees).collect(), grow)?;
+        self.n_features = x.ncols();
+        self.sample_size = sample_size;
+
+        let mut scores = self.score_samples(x)?.to_vec();
+        scores.sort_by(f64::total_cmp);
+        self.threshold = quantile(&scores, 1.0 - self.contamination);
+        Ok(())
+    }
+
+    // Anomaly score 2^(-E[h(x)] / c(n)) in (0, 1], where h is the path
+    // length to isolate a row and c(n) its average over random trees on the
+    // subsample size. Near 1 is anomalous; well below 0.5 is ordinary.
+    pub fn score_samples(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        if self.trees.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        if x.ncols() != self.n_features {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.n_features,
+                found: x.ncols(),
+                context: "number of features in prediction",
+            });
+        }
+        let normalizer = average_path_length(self.sample_size);
+        Ok(x.rows()
+            .into_iter()
+            .map(|row| {
+                let total: f64 = self.trees.iter().map(|nodes| path_length(nodes, row)).sum();
+                let mean = total / self.trees.len() as f64;
+                2f64.powf(-mean / normalizer)
+            })
+            .collect())
+    }
+
+    // True for rows scoring above the contamination threshold
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<bool>, LinearRegressionError> {
+        Ok(self.score_samples(x)?.mapv(|score| score > self.threshold))
+    }
+
+    pub fn fit_predict(&mut self, x: &Array2<f64>) -> Result<Array1<bool>, LinearRegressionError> {
+        self.fit(x)?;
+        self.predict(x)
+    }
+
+    // Keep only the rows not flagged as outliers, e.g. before training a
+    // regression on `(x, y)`
+    pub fn remove_outliers(
+        &self,
+        x: &Array2<f64>,
+        y: &Array1<f64>,
+    ) -> Result<(Array2<f64>, Array1<f64>), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        let outliers = self.predict(x)?;
+        let inliers: Vec<usize> = (0..x.nrows()).filter(|&i| !outliers[i]).collect();
+        Ok((x.select(Axis(0), &inliers), y.select(Axis(0), &inliers)))
+    }
+}
+
+impl Seeded for IsolationForest {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+// Grow the subtree for `rows` and return its index in `nodes`
+fn grow_tree(
+    x: &Array2<f64>,
+    rows: &mut [usize],
+    depth: usize,
+    max_depth: usize,
+    rng: &mut StdRng,
+    nodes: &mut Vec<IsolationNode>,
+) -> usize {
+    let index = nodes.len();
+    nodes.push(IsolationNode::Leaf { size: rows.len() });
+    if rows.len() <= 1 || depth >= max_depth {
+        return index;
+    }
+
+    // Only features that still vary within the node can separate its rows
+    let ranges: Vec<(usize, f64, f64)> = (0..x.ncols())
+        .filter_map(|feature| {
+            let (min, max) = rows.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &i| {
+                (lo.min(x[[i, feature]]), hi.max(x[[i, feature]]))
+            });
+            (min < max).then_some((feature, min, max))
+        })
+        .collect();
+    if ranges.is_empty() {
+        return index;
+    }
+    let (feature, min, max) = ranges[rng.random_range(0..ranges.len())];
+    // Drawn from (min, max] so the minimum always goes left and the split
+    // is never empty on either side
+    let threshold = max - rng.random_range(0.0..max - min);
+
+    let mut n_left = 0;
+    for i in 0..rows.len() {
+        if x[[rows[i], feature]] < threshold {
+            rows.swap(i, n_left);
+            n_left += 1;
+        }
+    }
+    let (left_rows, right_rows) = rows.split_at_mut(n_left);
+    let left = grow_tree(x, left_rows, depth + 1, max_depth, rng, nodes);
+    let right = grow_tree(x, right_rows, depth + 1, max_depth, rng, nodes);
+    nodes[index] = IsolationNode::Split { feature, threshold, left, right };
+    index
+}
+
+fn path_length(nodes: &[IsolationNode], row: ArrayView1<f64>) -> f64 {
+    let mut index = 0;
+    let mut depth = 0.0;
+    loop {
+        match nodes[index] {
+            IsolationNode::Split { feature, threshold, left, right } => {
+                index = if row[feature] < threshold { left } else { right };
+                depth += 1.0;
+            }
+            IsolationNode::Leaf { size } => return depth + average_path_length(size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isolation_forest() -> Result<(), LinearRegressionError> {
+        // A tight grid with two far-away points appended
+        let mut x = Array2::from_shape_fn((100, 2), |(i, j)| if j == 0 { (i % 10) as f64 } else { (i / 10) as f64 } / 10.0);
+        x.push_row(ArrayView1::from(&[8.0, 8.0])).unwrap();
+        x.push_row(ArrayView1::from(&[-6.0, 7.0])).unwrap();
+        let y = Array1::from_shape_fn(102, |i| i as f64);
+
+        let mut forest = IsolationForest::new(100).with_contamination(0.02).with_seed(4);
+        let flagged = forest.fit_predict(&x)?;
+        assert!(flagged[100] && flagged[101]);
+        // The threshold interpolates between order statistics, so about 2%
+        // of the rows end up above it
+        assert!(flagged.iter().filter(|&&f| f).count() <= 3);
+
+        let scores = forest.score_samples(&x)?;
+        assert!(scores.iter().all(|&s| s > 0.0 && s <= 1.0));
+        assert!(scores[100] > 0.6 && scores[45] < 0.5);
+
+        let (x_clean, y_clean) = forest.remove_outliers(&x, &y)?;
+        assert_eq!(x_clean.nrows(), y_clean.len());
+        assert!(x_clean.nrows() >= 99 && y_clean.iter().all(|&v| v < 100.0));
+        assert!(IsolationForest::new(10).with_contamination(0.0).fit(&x).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use crate::random::{RandomState, Seeded};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn squared_distance(a: ArrayView1<f64>, b: ArrayView1<f64>) -> f64 {
+    a.iter().zip(b.iter()).map(|(p, q)| (p - q) * (p - q)).sum()
+}
+
+// Lloyd's algorithm with k-means++ seeding
+#[derive(Debug, Clone)]
+pub struct KMeans {
+    k: usize,
+    max_iter: usize,
+    tol: f64,
+    seed: u64,
+    centroids: Array2<f64>,
+    inertia: f64,
+    n_iter: usize,
+}
+
+impl KMeans {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            max_iter: 300,
+            tol: 1e-4,
+            seed: 0,
+            centroids: Array2::zeros((0, 0)),
+            inertia: 0.0,
+            n_iter: 0,
+        }
+    }
+
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    // Stop once the total squared centroid movement in an iteration falls
+    // below `tol` times the mean feature variance, so the tolerance does not
+    // depend on the scale of the data
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    // One row per cluster
+    pub fn centroids(&self) -> &Array2<f64> {
+        &self.centroids
+    }
+
+    // Sum of squared distances from each training point to its centroid
+    pub fn inertia(&self) -> f64 {
+        self.inertia
+    }
+
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.k == 0 {
+            return Err(LinearRegressionError::InvalidParameter("k must be at least 1"));
+        }
+        if self.k > x.nrows() {
+            return Err(LinearRegressionError::InvalidParameter("k must not exceed the number of samples"));
+        }
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut centroids = plus_plus_init(x, self.k, &mut rng);
+        let threshold = self.tol * x.var_axis(Axis(0), 0.0).mean().unwrap_or(0.0);
+        let mut labels = vec![0; x.nrows()];
+
+        self.n_iter = 0;
+        for _ in 0..self.max_iter {
+            self.n_iter += 1;
+            let distances = assign(x, &centroids, &mut labels);
+
+            let mut sums = Array2::<f64>::zeros(centroids.raw_dim());
+            let mut counts = vec![0usize; self.k];
+            for (row, &label) in x.rows().into_iter().zip(labels.iter()) {
+                sums.row_mut(label).scaled_add(1.0, &row);
+                counts[label] += 1;
+            }
+
+            let mut updated = centroids.clone();
+            for (c, &count) in counts.iter().enumerate() {
+                if count > 0 {
+                    updated.row_mut(c).assign(&(&sums.row(c) / count as f64));
+                } else {
+                    // Re-seed an empty cluster at the point worst served by its centroid
+                    let far = (0..x.nrows()).max_by(|&i, &j| distances[i].total_cmp(&distances[j])).unwrap();
+                    updated.row_mut(c).assign(&x.row(far));
+                }
+            }
+
+            let shift: f64 = (&updated - &centroids).iter().map(|d| d * d).sum();
+            centroids = updated;
+            if shift <= threshold {
+                break;
+            }
+        }
+
+        self.inertia = assign(x, &centroids, &mut labels).sum();
+        self.centroids = centroids;
+        Ok(())
+    }
+
+    // Index of the nearest centroid for each row
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<usize>, LinearRegressionError> {
+        if self.centroids.nrows() == 0 {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        if x.ncols() != self.centroids.ncols() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.centroids.ncols(),
+                found: x.ncols(),
+                context: "number of features in prediction",
+            });
+        }
+        let mut labels = vec![0; x.nrows()];
+        assign(x, &self.centroids, &mut labels);
+        Ok(Array1::from(labels))
+    }
+
+    pub fn fit_predict(&mut self, x: &Array2<f64>) -> Result<Array1<usize>, LinearRegressionError> {
+        self.fit(x)?;
+        self.predict(x)
+    }
+}
+
+impl Seeded for KMeans {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+// k-means++: each new centroid is drawn with probability proportional to the
+// squared distance from the nearest centroid chosen so far
+fn plus_plus_init(x: &Array2<f64>, k: usize, rng: &mut StdRng) -> Array2<f64> {
+    let mut centroids = Array2::zeros((k, x.ncols()));
+    centroids.row_mut(0).assign(&x.row(rng.random_range(0..x.nrows())));
+    let mut nearest: Vec<f64> = x.rows().into_iter().map(|row| squared_distance(row, centroids.row(0))).collect();
+
+    for c in 1..k {
+        let total: f64 = nearest.iter().sum();
+        let chosen = if total > 0.0 {
+            let mut target = rng.random_range(0.0..total);
+            nearest
+                .iter()
+                .position(|&d| {
+                    target -= d;
+                    target < 0.0
+                })
+                .unwrap_or(x.nrows() - 1)
+        } else {
+            // Every point already coincides with a centroid
+            rng.random_range(0..x.nrows())
+        };
+        centroids.row_mut(c).assign(&x.row(chosen));
+        for (d, row) in nearest.iter_mut().zip(x.rows()) {
+            *d = d.min(squared_distance(row, centroids.row(c)));
+        }
+    }
+    centroids
+}
+
+// Write the nearest centroid of each row into `labels` and return the
+// squared distances to it
+fn assign(x: &Array2<f64>, centroids: &Array2<f64>, labels: &mut [usize]) -> Array1<f64> {
+    x.rows()
+        .into_iter()
+        .zip(labels.iter_mut())
+        .map(|(row, label)| {
+            let (best, distance) = centroids
+                .rows()
+                .into_iter()
+                .map(|centroid| squared_distance(row, centroid))
+                .enumerate()
+                .fold((0, f64::INFINITY), |best, (c, d)| if d < best.1 { (c, d) } else { best });
+            *label = best;
+            distance
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_kmeans() -> Result<(), LinearRegressionError> {
+        let x = arr2(&[
+            [0.0, 0.0], [0.5, 0.0], [0.0, 0.5],
+            [10.0, 10.0], [10.5, 10.0], [10.0, 10.5],
+            [0.0, 10.0], [0.5, 10.0], [0.0, 10.5],
+        ]);
+
+        let mut model = KMeans::new(3).with_seed(5);
+        let labels = model.fit_predict(&x)?;
+        for group in labels.as_slice().unwrap().chunks(3) {
+            assert!(group.iter().all(|&l| l == group[0]));
+        }
+        assert_ne!(labels[0], labels[3]);
+        assert_ne!(labels[3], labels[6]);
+        assert_ne!(labels[0], labels[6]);
+
+        // Each cluster's centroid is offset (1/6, 1/6) from its corner, giving
+        // squared distances 2/36 + 5/36 + 5/36 = 1/3 per cluster
+        assert!((model.inertia() - 1.0).abs() < 1e-12);
+        assert_eq!(model.predict(&arr2(&[[9.0, 9.5]]))?[0], labels[3]);
+        assert!(KMeans::new(10).fit(&x).is_err());
+        Ok(())
+    }
+}
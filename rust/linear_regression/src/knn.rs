@@ -0,0 +1,323 @@
+use crate::traits::{Fit, Predictor, Score};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, ArrayView1};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distance {
+    Euclidean,
+    Manhattan,
+    // One minus the cosine similarity; a zero vector is at distance 1 from everything
+    Cosine,
+}
+
+impl Distance {
+    pub fn between(&self, a: ArrayView1<f64>, b: ArrayView1<f64>) -> f64 {
+        match self {
+            Self::Euclidean => a.iter().zip(b.iter()).map(|(p, q)| (p - q) * (p - q)).sum::<f64>().sqrt(),
+            Self::Manhattan => a.iter().zip(b.iter()).map(|(p, q)| (p - q).abs()).sum(),
+            Self::Cosine => {
+                let norms = a.dot(&a).sqrt() * b.dot(&b).sqrt();
+                if norms == 0.0 {
+                    1.0
+                } else {
+                    1.0 - a.dot(&b) / norms
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weighting {
+    Uniform,
+    // Weight each neighbor by 1 / distance. Exact matches, if any, take all the weight.
+    Distance,
+}
+
+// Training data and settings shared by the regressor and the classifier.
+// Search is brute force over every stored row.
+#[derive(Debug, Clone)]
+struct Neighbors {
+    k: usize,
+    distance: Distance,
+    weighting: Weighting,
+    x: Array2<f64>,
+    y: Array1<f64>,
+}
+
+impl Neighbors {
+    fn new(k: usize) -> Self {
+        Self {
+            k,
+            distance: Distance::Euclidean,
+            weighting: Weighting::Uniform,
+            x: Array2::zeros((0, 0)),
+            y: Array1::zeros(0),
+        }
+    }
+
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.k == 0 {
+            return Err(LinearRegressionError::InvalidParameter("k must be at least 1"));
+        }
+        if self.k > x.nrows() {
+            return Err(LinearRegressionError::InvalidParameter(
+                "k must not exceed the number of training samples"
+            ));
+        }
+
+        self.x = x.clone();
+        self.y = y.clone();
+        Ok(())
+    }
+
+    // For each row of `x`, the targets of its k nearest training rows paired
+    // with their vote weights
+    fn neighbors(&self, x: &Array2<f64>) -> Result<Vec<Vec<(f64, f64)>>, LinearRegressionError> {
+        if self.y.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        if x.ncols() != self.x.ncols() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.x.ncols(),
+                found: x.ncols(),
+                context: "number of features in prediction",
+            });
+        }
+
+        let mut distances = Vec::with_capacity(self.x.nrows());
+        let result = x
+            .rows()
+            .into_iter()
+            .map(|query| {
+                distances.clear();
+                distances.extend(
+                    self.x.rows().into_iter().enumerate().map(|(i, row)| (self.distance.between(query, row), i))
+                );
+                // Ties are broken by training row order
+                let by_distance = |a: &(f64, usize), b: &(f64, usize)| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1));
+                if self.k < distances.len() {
+                    distances.select_nth_unstable_by(self.k - 1, by_distance);
+                    distances.truncate(self.k);
+                }
+                distances.sort_by(by_distance);
+
+                let exact_match = distances[0].0 == 0.0;
+                distances
+                    .iter()
+                    .map(|&(d, i)| {
+                        let weight = match self.weighting {
+                            Weighting::Uniform => 1.0,
+                            Weighting::Distance if exact_match => if d == 0.0 { 1.0 } else { 0.0 },
+                            Weighting::Distance => 1.0 / d,
+                        };
+                        (self.y[i], weight)
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(result)
+    }
+}
+
+// Predicts the (weighted) mean target of the k nearest training rows
+#[derive(Debug, Clone)]
+pub struct KnnRegressor {
+    inner: Neighbors,
+}
+
+impl KnnRegressor {
+    pub fn new(k: usize) -> Self {
+        Self { inner: Neighbors::new(k) }
+    }
+
+    pub fn with_distance(mut self, distance: Distance) -> Self {
+        self.inner.distance = distance;
+        self
+    }
+
+    pub fn with_weighting(mut self, weighting: Weighting) -> Self {
+        self.inner.weighting = weighting;
+        self
+    }
+
+    pub fn k(&self) -> usize {
+        self.inner.k
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        self.inner.fit(x, y)
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let neighbors = self.inner.neighbors(x)?;
+        Ok(neighbors
+            .iter()
+            .map(|votes| {
+                let total: f64 = votes.iter().map(|&(_, w)| w).sum();
+                votes.iter().map(|&(y, w)| y * w).sum::<f64>() / total
+            })
+            .collect())
+    }
+}
+
+// Predicts the class label with the largest (weighted) vote among the k
+// nearest training rows. Labels are class ids stored as f64; ties go to the
+// smallest label.
+#[derive(Debug, Clone)]
+pub struct KnnClassifier {
+    inner: Neighbors,
+    classes: Vec<f64>,
+}
+
+impl KnnClassifier {
+    pub fn new(k: usize) -> Self {
+        Self {
+            inner: Neighbors::new(k),
+            classes: Vec::new(),
+        }
+    }
+
+    pub fn with_distance(mut self, distance: Distance) -> Self {
+        self.inner.distance = distance;
+        self
+    }
+
+    pub fn with_weighting(mut self, weighting: Weighting) -> Self {
+        self.inner.weighting = weighting;
+        self
+    }
+
+    pub fn k(&self) -> usize {
+        self.inner.k
+    }
+
+    // Distinct labels seen in training, in ascending order
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        self.inner.fit(x, y)?;
+        let mut classes = y.to_vec();
+        classes.sort_by(f64::total_cmp);
+        classes.dedup();
+        self.classes = classes;
+        Ok(())
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let neighbors = self.inner.neighbors(x)?;
+        let mut scores = vec![0.0; self.classes.len()];
+        Ok(neighbors
+            .iter()
+            .map(|votes| {
+                scores.fill(0.0);
+                for &(label, weight) in votes {
+                    let class = self.classes.binary_search_by(|c| c.total_cmp(&label)).unwrap();
+                    scores[class] += weight;
+                }
+                let best = (1..scores.len()).fold(0, |best, c| if scores[c] > scores[best] { c } else { best });
+                self.classes[best]
+            })
+            .collect())
+    }
+}
+
+impl Fit for KnnRegressor {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        KnnRegressor::fit(self, x, y)
+    }
+}
+
+impl Predictor for KnnRegressor {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        KnnRegressor::predict(self, x)
+    }
+}
+
+impl Score for KnnRegressor {}
+
+impl Fit for KnnClassifier {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        KnnClassifier::fit(self, x, y)
+    }
+}
+
+impl Predictor for KnnClassifier {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        KnnClassifier::predict(self, x)
+    }
+}
+
+impl Score for KnnClassifier {
+    fn is_classifier(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_knn_regressor() -> Result<(), LinearRegressionError> {
+        let x = arr2(&[[0.0], [1.0], [2.0], [3.0], [10.0]]);
+        let y = Array1::from(vec![0.0, 1.0, 2.0, 3.0, 10.0]);
+
+        let mut model = KnnRegressor::new(2);
+        model.fit(&x, &y)?;
+        let predictions = model.predict(&arr2(&[[0.4], [9.0]]))?;
+        assert!((predictions[0] - 0.5).abs() < 1e-12);
+        assert!((predictions[1] - 6.5).abs() < 1e-12);
+
+        // Distance weighting: 0.4 is 0.4 from 0 and 0.6 from 1
+        let mut weighted = KnnRegressor::new(2).with_weighting(Weighting::Distance);
+        weighted.fit(&x, &y)?;
+        let predictions = weighted.predict(&arr2(&[[0.4], [2.0]]))?;
+        assert!((predictions[0] - 0.4).abs() < 1e-12);
+        // An exact match takes all the weight
+        assert_eq!(predictions[1], 2.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_knn_classifier() -> Result<(), LinearRegressionError> {
+        let x = arr2(&[[1.0, 0.1], [2.0, 0.3], [3.0, 0.2], [0.1, 1.0], [0.2, 3.0], [0.3, 2.0]]);
+        let y = Array1::from(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+        let queries = arr2(&[[5.0, 0.5], [0.5, 5.0]]);
+
+        for distance in [Distance::Euclidean, Distance::Manhattan, Distance::Cosine] {
+            let mut model = KnnClassifier::new(3).with_distance(distance);
+            model.fit(&x, &y)?;
+            assert_eq!(model.classes(), &[0.0, 1.0]);
+            assert_eq!(model.predict(&queries)?, Array1::from(vec![0.0, 1.0]));
+        }
+
+        // Cosine only looks at direction, so scale does not matter
+        let cosine = Distance::Cosine;
+        let a = Array1::from(vec![1.0, 2.0]);
+        assert!(cosine.between(a.view(), (&a * 10.0).view()).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_knn_invalid() {
+        let x = arr2(&[[0.0], [1.0]]);
+        let y = Array1::from(vec![0.0, 1.0]);
+        assert!(KnnRegressor::new(0).fit(&x, &y).is_err());
+        assert!(KnnRegressor::new(3).fit(&x, &y).is_err());
+        assert!(KnnClassifier::new(1).predict(&x).is_err());
+    }
+}
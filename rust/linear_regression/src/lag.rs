@@ -0,0 +1,183 @@
+use crate::data::Dataset;
+use crate::traits::Predictor;
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+
+// Turns a time-ordered target into a supervised data set for autoregressive
+// forecasting. The row for time t holds only values up to t - 1:
+// - lag k: y[t - k]
+// - rolling window w: mean and population std of y[t - w..t]
+// - difference order d: the d-th difference ending at y[t - 1]
+// and its target is y[t]. The first `lookback()` steps have no full history
+// and yield no rows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LagFeatures {
+    pub lags: Vec<usize>,
+    pub windows: Vec<usize>,
+    pub differences: Vec<usize>,
+}
+
+impl LagFeatures {
+    pub fn new(lags: Vec<usize>, windows: Vec<usize>, differences: Vec<usize>) -> Result<Self, LinearRegressionError> {
+        if lags.is_empty() && windows.is_empty() && differences.is_empty() {
+            return Err(LinearRegressionError::InvalidParameter("no lag, window or difference features requested"));
+        }
+        if lags.contains(&0) {
+            return Err(LinearRegressionError::InvalidParameter("lags must be at least 1"));
+        }
+        if windows.iter().any(|&w| w < 2) {
+            return Err(LinearRegressionError::InvalidParameter("rolling windows must span at least two steps"));
+        }
+        if differences.contains(&0) {
+            return Err(LinearRegressionError::InvalidParameter("difference orders must be at least 1"));
+        }
+        Ok(Self { lags, windows, differences })
+    }
+
+    // Past values needed for one row
+    pub fn lookback(&self) -> usize {
+        let lags = self.lags.iter().copied();
+        let windows = self.windows.iter().copied();
+        let differences = self.differences.iter().map(|d| d + 1);
+        lags.chain(windows).chain(differences).max().unwrap_or(0)
+    }
+
+    // Column names in output order: lags, then rolling mean and std per
+    // window, then differences
+    pub fn feature_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.lags.iter().map(|k| format!("lag_{}", k)).collect();
+        for w in &self.windows {
+            names.push(format!("rolling_mean_{}", w));
+            names.push(format!("rolling_std_{}", w));
+        }
+        names.extend(self.differences.iter().map(|d| format!("diff_{}", d)));
+        names
+    }
+
+    pub fn n_output_features(&self) -> usize {
+        self.lags.len() + 2 * self.windows.len() + self.differences.len()
+    }
+
+    // One row per time step from `lookback()` on, with the series value at
+    // that step as the target
+    pub fn transform(&self, y: &Array1<f64>) -> Result<Dataset, LinearRegressionError> {
+        let lookback = self.lookback();
+        if y.len() <= lookback {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        let series = y.to_vec();
+        let n_rows = series.len() - lookback;
+        let mut x = Array2::zeros((n_rows, self.n_output_features()));
+        for (row, t) in (lookback..series.len()).enumerate() {
+            self.fill_row(&series[..t], x.row_mut(row).as_slice_mut().unwrap());
+        }
+
+        Ok(Dataset {
+            x,
+            y: Array1::from(series[lookback..].to_vec()),
+            feature_names: self.feature_names(),
+            target_name: "y".to_string(),
+        })
+    }
+
+    // Feature row for the step right after the end of `history`
+    pub fn next_features(&self, history: &Array1<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        if history.len() < self.lookback() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.lookback(),
+                found: history.len(),
+                context: "length of history for lag features",
+            });
+        }
+        let mut x = Array2::zeros((1, self.n_output_features()));
+        self.fill_row(&history.to_vec(), x.row_mut(0).as_slice_mut().unwrap());
+        Ok(x)
+    }
+
+    // Recursive multi-step forecast: predict one step, append it to the
+    // history and repeat, so later steps build on earlier predictions
+    pub fn forecast<P: Predictor + ?Sized>(
+        &self,
+        model: &P,
+        history: &Array1<f64>,
+        horizon: usize,
+    ) -> Result<Array1<f64>, LinearRegressionError> {
+        let mut extended = history.to_vec();
+        for _ in 0..horizon {
+            let x = self.next_features(&Array1::from(extended.clone()))?;
+            extended.push(model.predict(&x)?[0]);
+        }
+        Ok(Array1::from(extended[history.len()..].to_vec()))
+    }
+
+    // `past` ends just before the step being described
+    fn fill_row(&self, past: &[f64], out: &mut [f64]) {
+        let n = past.len();
+        let mut column = 0;
+        for &k in &self.lags {
+            out[column] = past[n - k];
+            column += 1;
+        }
+        for &w in &self.windows {
+            let window = &past[n - w..];
+            let mean = window.iter().sum::<f64>() / w as f64;
+            let variance = window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / w as f64;
+            out[column] = mean;
+            out[column + 1] = variance.sqrt();
+            column += 2;
+        }
+        for &d in &self.differences {
+            let mut values = past[n - d - 1..].to_vec();
+            for _ in 0..d {
+                values = values.windows(2).map(|v| v[1] - v[0]).collect();
+            }
+            out[column] = values[0];
+            column += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LinearRegression;
+
+    #[test]
+    fn test_lag_features() -> Result<(), LinearRegressionError> {
+        let y = Array1::from(vec![1.0, 4.0, 9.0, 16.0, 25.0, 36.0]);
+        let features = LagFeatures::new(vec![1, 2], vec![3], vec![1, 2])?;
+        assert_eq!(features.lookback(), 3);
+        let dataset = features.transform(&y)?;
+        assert_eq!(dataset.feature_names, ["lag_1", "lag_2", "rolling_mean_3", "rolling_std_3", "diff_1", "diff_2"]);
+        assert_eq!(dataset.y.to_vec(), vec![16.0, 25.0, 36.0]);
+        // Row for y = 16 sees [1, 4, 9]
+        let first = dataset.x.row(0);
+        assert_eq!(first.to_vec()[..3], [9.0, 4.0, 14.0 / 3.0]);
+        assert!((first[3] - (98.0f64 / 9.0).sqrt()).abs() < 1e-12);
+        assert_eq!(first.to_vec()[4..], [5.0, 2.0]);
+        assert!(LagFeatures::new(vec![0], vec![], vec![]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_autoregressive_forecast() -> Result<(), LinearRegressionError> {
+        // y[t] = 0.5 y[t - 1] + 1 settles at 2; a fitted AR(1) should follow
+        let y: Array1<f64> = (0..12).scan(-2.0, |v, _| {
+            *v = 0.5 * *v + 1.0;
+            Some(*v)
+        }).collect();
+        let features = LagFeatures::new(vec![1], vec![], vec![])?;
+        let dataset = features.transform(&y)?;
+        let mut model = LinearRegression::new(1, 0.1);
+        model.train(&dataset.x, &dataset.y, 20_000)?;
+        assert!((model.weights[0] - 0.5).abs() < 1e-3 && (model.bias - 1.0).abs() < 1e-3);
+
+        let history = Array1::from(vec![10.0]);
+        let forecast = features.forecast(&model, &history, 3)?;
+        for (got, want) in forecast.iter().zip([6.0, 4.0, 3.0]) {
+            assert!((got - want).abs() < 1e-2);
+        }
+        Ok(())
+    }
+}
@@ -1,49 +1,362 @@
-use ndarray::{Array1, Array2};
-use std::error::Error;
+use ndarray::linalg::general_mat_vec_mul;
+use ndarray::{Array1, Array2, ArrayBase, ArrayView1, ArrayView2, ArrayViewMut1, Data, Ix1, Ix2, NdFloat, Zip};
+use num_traits::FromPrimitive;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use config::TrainConfig;
+use history::TrainingHistory;
 
-#[derive(Debug)]
-pub struct LinearRegression {
-    pub weights: Array1<f64>,
-    pub bias: f64,
-    learning_rate: f64,
+#[cfg(feature = "blas")]
+extern crate blas_src;
+
+pub mod adaboost;
+pub mod arima;
+pub mod automl;
+pub mod boosting;
+pub mod bootstrap;
+pub mod calibration;
+pub mod config;
+pub mod count;
+pub mod dbscan;
+pub mod diagnostics;
+pub mod discriminant;
+pub mod ensemble;
+pub mod experiment;
+pub mod forest;
+pub mod gaussian_process;
+pub mod history;
+pub mod hmm;
+pub mod importance;
+pub mod inference;
+pub mod isolation;
+pub mod kmeans;
+pub mod knn;
+pub mod lag;
+mod linalg;
+pub mod lr_finder;
+pub mod mlp;
+pub mod model_selection;
+pub mod naive_bayes;
+pub mod online;
+pub mod optim;
+pub mod ordinal;
+pub mod pca;
+pub mod pipeline;
+#[cfg(feature = "plotting")]
+pub mod plotting;
+pub mod prelude;
+pub mod recommender;
+#[cfg(not(feature = "blas"))]
+mod simd;
+pub mod smoothing;
+pub mod spline;
+mod stats;
+pub mod survival;
+pub mod svm;
+pub mod summary;
+pub mod traits;
+pub mod tree;
+
+// Shared with the other crates of the workspace
+pub use ml_core::{data, loader, metrics, preprocessing, random};
+
+// Element types the models can be trained in (f32 or f64)
+pub trait Float: NdFloat + FromPrimitive + std::iter::Sum {}
+
+impl<T: NdFloat + FromPrimitive + std::iter::Sum> Float for T {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearRegression<F = f64> {
+    pub weights: Array1<F>,
+    pub bias: F,
+    learning_rate: F,
+    // Epochs run by `Fit::fit`; `train` takes its own count
+    #[serde(default = "default_epochs")]
+    epochs: usize,
+    // Column names for the weights, in order, when known
+    #[serde(default)]
+    feature_names: Option<Vec<String>>,
+    // Set by training or by explicit initial weights; prediction needs it.
+    // Models saved before this field existed were trained ones.
+    #[serde(default = "default_fitted")]
+    fitted: bool,
+    // Where `fit` and `reset_weights` start the weights from
+    #[serde(default)]
+    weight_init: WeightInit,
+}
+
+fn default_epochs() -> usize {
+    1000
+}
+
+fn default_fitted() -> bool {
+    true
+}
+
+// The error type of the whole crate, shared with the rest of the workspace.
+// `LinearRegressionError` is its older name and the same type.
+pub use ml_core::{ErrorKind, MlError};
+pub use ml_core::MlError as LinearRegressionError;
+
+// `names` when there is one per column, otherwise x0, x1, ...
+pub(crate) fn column_names(names: Option<&[String]>, n_columns: usize) -> Vec<String> {
+    match names {
+        Some(names) if names.len() == n_columns => names.to_vec(),
+        _ => (0..n_columns).map(|j| format!("x{}", j)).collect(),
+    }
+}
+
+// `f` on every item, on the rayon thread pool with the `parallel` feature.
+// Results are collected in the order of the items and callers reduce them
+// sequentially afterwards, and every item carries its own seeds and folds,
+// so seeded results are the same bit for bit with or without the pool.
+pub(crate) fn try_map<T, R, M>(items: Vec<T>, f: M) -> Result<Vec<R>, LinearRegressionError>
+where
+    T: Send,
+    R: Send,
+    M: Fn(T) -> Result<R, LinearRegressionError> + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items.into_par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    items.into_iter().map(f).collect()
 }
 
-#[derive(Debug)]
-pub enum LinearRegressionError {
-    DimensionMismatch {
-        expected: usize,
-        found: usize,
-        context: &'static str,
-    },
-    EmptyData,
-    NumericalError(&'static str),
+// Writes `target = w0*x0 + w1*x1 + ... + b`, with the formatter's precision
+// applied to every number
+pub(crate) fn write_equation(
+    f: &mut std::fmt::Formatter,
+    target: &str,
+    terms: &[(String, f64)],
+    intercept: f64,
+) -> std::fmt::Result {
+    let precision = f.precision();
+    let number = |v: f64| match precision {
+        Some(precision) => format!("{:.*}", precision, v),
+        None => v.to_string(),
+    };
+    write!(f, "{} =", target)?;
+    for (i, (name, weight)) in terms.iter().enumerate() {
+        match (i, *weight < 0.0) {
+            (0, false) => write!(f, " {}*{}", number(*weight), name)?,
+            (0, true) => write!(f, " -{}*{}", number(-weight), name)?,
+            (_, false) => write!(f, " + {}*{}", number(*weight), name)?,
+            (_, true) => write!(f, " - {}*{}", number(-weight), name)?,
+        }
+    }
+    match (terms.is_empty(), intercept < 0.0) {
+        (true, _) => write!(f, " {}", number(intercept)),
+        (false, false) => write!(f, " + {}", number(intercept)),
+        (false, true) => write!(f, " - {}", number(-intercept)),
+    }
 }
 
-impl std::fmt::Display for LinearRegressionError {
+// The fitted equation on the features the model sees, named as in
+// `coefficients`; `{:.2}` rounds every number
+impl<F: Float> std::fmt::Display for LinearRegression<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let terms: Vec<(String, f64)> =
+            self.coefficients().into_iter().map(|(name, w)| (name, w.to_f64().unwrap())).collect();
+        write_equation(f, "y", &terms, self.bias.to_f64().unwrap())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrainingWarning {
+    IllConditioned { condition_number: f64, threshold: f64 },
+}
+
+impl std::fmt::Display for TrainingWarning {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            Self::DimensionMismatch { expected, found, context } => {
-                write!(f, "Dimension mismatch in {}: expected {}, found {}", 
-                       context, expected, found)
+            Self::IllConditioned { condition_number, threshold } => {
+                write!(f, "Design matrix condition number {:.3e} exceeds {:.3e}; \
+                           consider standardizing features or adding regularization",
+                       condition_number, threshold)
             }
-            Self::EmptyData => write!(f, "Empty data provided"),
-            Self::NumericalError(msg) => write!(f, "Numerical error: {}", msg),
         }
     }
 }
 
-impl Error for LinearRegressionError {}
+#[derive(Debug, Clone)]
+pub struct TrainOutcome<F = f64> {
+    pub history: TrainingHistory<F>,
+    pub warnings: Vec<TrainingWarning>,
+}
 
-impl LinearRegression {
-    pub fn new(n_features: usize, learning_rate: f64) -> Self {
+impl<F: Float> LinearRegression<F> {
+    pub fn new(n_features: usize, learning_rate: F) -> Self {
         Self {
             weights: Array1::zeros(n_features),
-            bias: 0.0,
+            bias: F::zero(),
             learning_rate,
+            epochs: default_epochs(),
+            feature_names: None,
+            fitted: false,
+            weight_init: WeightInit::Zeros,
+        }
+    }
+
+    // A ready-to-use model from known coefficients, e.g. a closed-form fit;
+    // counts as fitted
+    pub fn from_parameters(weights: Array1<F>, bias: F) -> Self {
+        let mut model = Self::new(weights.len(), F::from_f64(0.01).unwrap());
+        model.weights = weights;
+        model.bias = bias;
+        model.fitted = true;
+        model
+    }
+
+    // Validated configuration with defaults for everything but the number of
+    // features; see `LinearRegressionBuilder`
+    pub fn builder() -> LinearRegressionBuilder<F> {
+        LinearRegressionBuilder::new()
+    }
+
+    pub fn with_epochs(mut self, epochs: usize) -> Self {
+        self.epochs = epochs;
+        self
+    }
+
+    // Name the weights, e.g. with `Dataset::feature_names`, for `coefficients`
+    // and `coef_for`
+    pub fn with_feature_names(mut self, names: Vec<String>) -> Self {
+        self.feature_names = Some(names);
+        self
+    }
+
+    pub fn feature_names(&self) -> Option<&[String]> {
+        self.feature_names.as_deref()
+    }
+
+    // (name, weight) pairs in column order. Without names, or when they no
+    // longer match the number of weights, columns are called x0, x1, ...
+    pub fn coefficients(&self) -> Vec<(String, F)> {
+        let names = column_names(self.feature_names.as_deref(), self.weights.len());
+        names.into_iter().zip(self.weights.iter().copied()).collect()
+    }
+
+    pub fn coef_for(&self, name: &str) -> Option<F> {
+        self.coefficients().into_iter().find(|(n, _)| n == name).map(|(_, w)| w)
+    }
+
+    pub fn is_fitted(&self) -> bool {
+        self.fitted
+    }
+
+    fn check_fitted(&self) -> Result<(), LinearRegressionError> {
+        if self.fitted {
+            Ok(())
+        } else {
+            Err(LinearRegressionError::NotFitted)
+        }
+    }
+
+    pub fn learning_rate(&self) -> F {
+        self.learning_rate
+    }
+
+    pub fn epochs(&self) -> usize {
+        self.epochs
+    }
+
+    // For adjusting a model between calls to `train` or `partial_fit`; the
+    // weights are kept, so training picks up where it stopped
+    pub fn set_learning_rate(&mut self, learning_rate: F) -> Result<(), LinearRegressionError> {
+        if !(learning_rate.is_finite() && learning_rate > F::zero()) {
+            return Err(LinearRegressionError::InvalidParameter("learning rate must be positive and finite"));
         }
+        self.learning_rate = learning_rate;
+        Ok(())
+    }
+
+    // Plain gradient descent keeps no state between steps besides the
+    // weights, and schedules are driven by the epoch of each call, so there
+    // is nothing to clear. Kept so code that resets models between
+    // experiments does not depend on the solver.
+    pub fn reset_optimizer_state(&mut self) {}
+
+    // Back to freshly initialized weights and a zero bias, unfitted, keeping
+    // the learning rate, epochs and feature names. A scale the builder would
+    // have rejected can only come from an edited file; it falls back to
+    // zeros here and is reported by `fit`.
+    pub fn reset_weights(&mut self) {
+        let n_features = self.weights.len();
+        self.weights = self.weight_init.sample(n_features).unwrap_or_else(|_| Array1::zeros(n_features));
+        self.bias = F::zero();
+        self.fitted = false;
+    }
+
+    // Unfitted weights sized for `n_features`, drawn as `weight_init` says
+    pub(crate) fn initialize(&mut self, n_features: usize) -> Result<(), LinearRegressionError> {
+        self.weights = self.weight_init.sample(n_features)?;
+        self.bias = F::zero();
+        self.fitted = false;
+        Ok(())
     }
 
-    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+    // Inputs may be owned arrays or views (row slices of a larger matrix, for
+    // instance); nothing is copied
+    pub fn predict<S: Data<Elem = F>>(&self, x: &ArrayBase<S, Ix2>) -> Result<Array1<F>, LinearRegressionError> {
+        let mut out = Array1::zeros(x.nrows());
+        self.predict_into(x, &mut out)?;
+        Ok(out)
+    }
+
+    // Predict from plain rows of feature values, e.g. `&[&[1.0, 2.0]]`
+    pub fn predict_rows<R: AsRef<[F]>>(&self, rows: &[R]) -> Result<Array1<F>, LinearRegressionError> {
+        self.predict(&data::rows_to_array(rows)?)
+    }
+
+    // Prediction for a single sample, without building a one-row matrix
+    pub fn predict_one(&self, features: &[F]) -> Result<F, LinearRegressionError> {
+        self.predict_one_array(&ArrayView1::from(features))
+    }
+
+    pub fn predict_one_array<S: Data<Elem = F>>(
+        &self,
+        features: &ArrayBase<S, Ix1>,
+    ) -> Result<F, LinearRegressionError> {
+        self.check_fitted()?;
+        if features.len() != self.weights.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.weights.len(),
+                found: features.len(),
+                context: "number of features in prediction",
+            });
+        }
+        Ok(features.dot(&self.weights) + self.bias)
+    }
+
+    // Write predictions into a caller-owned buffer, so hot paths can reuse it
+    // instead of allocating per call
+    pub fn predict_into<S: Data<Elem = F>>(
+        &self,
+        x: &ArrayBase<S, Ix2>,
+        out: &mut Array1<F>,
+    ) -> Result<(), LinearRegressionError> {
+        self.predict_into_view(x, out.view_mut())
+    }
+
+    pub fn predict_into_slice<S: Data<Elem = F>>(
+        &self,
+        x: &ArrayBase<S, Ix2>,
+        out: &mut [F],
+    ) -> Result<(), LinearRegressionError> {
+        self.predict_into_view(x, ArrayViewMut1::from(out))
+    }
+
+    fn predict_into_view<S: Data<Elem = F>>(
+        &self,
+        x: &ArrayBase<S, Ix2>,
+        mut out: ArrayViewMut1<F>,
+    ) -> Result<(), LinearRegressionError> {
+        self.check_fitted()?;
         if x.ncols() != self.weights.len() {
             return Err(LinearRegressionError::DimensionMismatch {
                 expected: self.weights.len(),
@@ -51,34 +364,322 @@ impl LinearRegression {
                 context: "number of features in prediction",
             });
         }
-        
-        Ok(x.dot(&self.weights) + self.bias)
+        if out.len() != x.nrows() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: out.len(),
+                context: "length of prediction output",
+            });
+        }
+
+        // Contiguous row-major input takes the explicitly vectorized path; BLAS
+        // builds and other layouts go through ndarray's matrix-vector product
+        #[cfg(not(feature = "blas"))]
+        if let (Some(rows), Some(weights)) = (x.as_slice(), self.weights.as_slice()) {
+            if let Some(out) = out.as_slice_mut() {
+                simd::predict_rows(rows, weights, self.bias, out);
+                return Ok(());
+            }
+        }
+
+        out.fill(self.bias);
+        general_mat_vec_mul(F::one(), x, &self.weights, F::one(), &mut out);
+        Ok(())
     }
 
-    pub fn mse_loss(&self, predictions: &Array1<f64>, y: &Array1<f64>) -> f64 {
+    pub fn mse_loss<S: Data<Elem = F>, T: Data<Elem = F>>(
+        &self,
+        predictions: &ArrayBase<S, Ix1>,
+        y: &ArrayBase<T, Ix1>,
+    ) -> F {
         let errors = predictions - y;
-        errors.mapv(|e| e * e).mean().unwrap_or(f64::INFINITY)
+        errors.mapv(|e| e * e).mean().unwrap_or(F::infinity())
     }
 
-    pub fn r_squared(&self, predictions: &Array1<f64>, y: &Array1<f64>) -> f64 {
-        let y_mean = y.mean().unwrap_or(0.0);
+    pub fn r_squared<S: Data<Elem = F>, T: Data<Elem = F>>(
+        &self,
+        predictions: &ArrayBase<S, Ix1>,
+        y: &ArrayBase<T, Ix1>,
+    ) -> F {
+        let y_mean = y.mean().unwrap_or(F::zero());
         let ss_tot = y.iter()
             .map(|&y_i| (y_i - y_mean).powi(2))
-            .sum::<f64>();
+            .sum::<F>();
         let ss_res = predictions.iter()
             .zip(y.iter())
             .map(|(&pred, &actual)| (actual - pred).powi(2))
-            .sum::<f64>();
+            .sum::<F>();
         
-        1.0 - (ss_res / ss_tot)
+        F::one() - (ss_res / ss_tot)
+    }
+
+    // R² of the model's predictions on (x, y)
+    pub fn score<S: Data<Elem = F>, T: Data<Elem = F>>(
+        &self,
+        x: &ArrayBase<S, Ix2>,
+        y: &ArrayBase<T, Ix1>,
+    ) -> Result<F, LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        Ok(self.r_squared(&self.predict(x)?, y))
     }
 
-    pub fn train(
+    pub fn train<S: Data<Elem = F>, T: Data<Elem = F>>(
         &mut self,
-        x: &Array2<f64>,
-        y: &Array1<f64>,
+        x: &ArrayBase<S, Ix2>,
+        y: &ArrayBase<T, Ix1>,
         epochs: usize
-    ) -> Result<Vec<f64>, LinearRegressionError> {
+    ) -> Result<TrainingHistory<F>, LinearRegressionError> {
+        self.train_epochs(x, y, None, epochs)
+    }
+
+    // Same as `train`, also recording the MSE on (x_val, y_val) each epoch in
+    // `validation_loss`. Like the training loss it is measured before that
+    // epoch's update, so both describe the same weights.
+    pub fn train_with_validation<S, T, SV, TV>(
+        &mut self,
+        x: &ArrayBase<S, Ix2>,
+        y: &ArrayBase<T, Ix1>,
+        x_val: &ArrayBase<SV, Ix2>,
+        y_val: &ArrayBase<TV, Ix1>,
+        epochs: usize,
+    ) -> Result<TrainingHistory<F>, LinearRegressionError>
+    where
+        S: Data<Elem = F>,
+        T: Data<Elem = F>,
+        SV: Data<Elem = F>,
+        TV: Data<Elem = F>,
+    {
+        if x_val.nrows() != y_val.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x_val.nrows(),
+                found: y_val.len(),
+                context: "number of samples in validation X and y",
+            });
+        }
+        if x_val.ncols() != self.weights.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.weights.len(),
+                found: x_val.ncols(),
+                context: "number of features in validation set",
+            });
+        }
+        if x_val.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        self.train_epochs(x, y, Some((x_val.view(), y_val.view())), epochs)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(
+        n_samples = x.nrows(),
+        n_features = x.ncols(),
+        validation = validation.is_some(),
+        epochs = epochs,
+    )))]
+    fn train_epochs<S: Data<Elem = F>, T: Data<Elem = F>>(
+        &mut self,
+        x: &ArrayBase<S, Ix2>,
+        y: &ArrayBase<T, Ix1>,
+        validation: Option<(ArrayView2<F>, ArrayView1<F>)>,
+        epochs: usize,
+    ) -> Result<TrainingHistory<F>, LinearRegressionError> {
+        self.check_training_data(x, y)?;
+        // No epochs would leave the initial weights standing in for a fit
+        if epochs == 0 {
+            return Err(LinearRegressionError::InvalidParameter("epochs must be at least 1"));
+        }
+
+        let mut history = TrainingHistory::with_capacity(epochs);
+        let start = Instant::now();
+
+        // Buffers reused across epochs so the loop itself does not allocate
+        let mut errors = Array1::zeros(x.nrows());
+        let mut weight_gradients = Array1::zeros(self.weights.len());
+        let mut validation_errors = Array1::zeros(validation.as_ref().map_or(0, |(x_val, _)| x_val.nrows()));
+        let mut validation_loss = validation.as_ref().map(|_| Vec::with_capacity(epochs));
+
+        for epoch in 0..epochs {
+            if let (Some((x_val, y_val)), Some(losses)) = (&validation, &mut validation_loss) {
+                losses.push(self.loss_into(x_val, y_val, &mut validation_errors));
+            }
+            let (mse, gradient_norm) = self.gradient_step(x, y, &mut errors, &mut weight_gradients, epoch)?;
+            history.record(mse, self.learning_rate, gradient_norm, start.elapsed());
+        }
+
+        history.validation_loss = validation_loss;
+        self.fitted = true;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(final_loss = history.final_loss().and_then(|l| l.to_f64()), "training finished");
+        Ok(history)
+    }
+
+    // Training driven by a `TrainConfig`, which is checked against the data
+    // before the first update. Each epoch records the loss averaged over its
+    // batches, the scheduled rate and the gradient norm of its last batch.
+    // The model's own learning rate is left as it was.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(
+        n_samples = x.nrows(),
+        n_features = x.ncols(),
+        epochs = config.epochs,
+        batch_size = config.batch_size,
+    )))]
+    pub fn train_with_config<S: Data<Elem = F>, T: Data<Elem = F>>(
+        &mut self,
+        x: &ArrayBase<S, Ix2>,
+        y: &ArrayBase<T, Ix1>,
+        config: &TrainConfig,
+    ) -> Result<TrainingHistory<F>, LinearRegressionError> {
+        self.check_training_data(x, y)?;
+        config.validate(x.nrows())?;
+
+        let initial_rate = self.learning_rate;
+        let mut history = TrainingHistory::with_capacity(config.epochs);
+        let outcome = self.configured_epochs(x, y, config, &mut history);
+        self.learning_rate = initial_rate;
+        outcome?;
+        self.fitted = true;
+        Ok(history)
+    }
+
+    fn configured_epochs<S: Data<Elem = F>, T: Data<Elem = F>>(
+        &mut self,
+        x: &ArrayBase<S, Ix2>,
+        y: &ArrayBase<T, Ix1>,
+        config: &TrainConfig,
+        history: &mut TrainingHistory<F>,
+    ) -> Result<(), LinearRegressionError> {
+        let n = x.nrows();
+        let batch_size = config.batch_size.unwrap_or(n);
+        let start = Instant::now();
+        let mut errors = Array1::zeros(batch_size);
+        let mut weight_gradients = Array1::zeros(self.weights.len());
+        // Best loss so far and the epoch it was reached
+        let mut best = (f64::INFINITY, 0);
+
+        for epoch in 0..config.epochs {
+            self.learning_rate = F::from_f64(config.schedule.learning_rate(config.learning_rate, epoch)).unwrap();
+            let mut loss_sum = F::zero();
+            let mut gradient_norm = F::zero();
+            for batch_start in (0..n).step_by(batch_size) {
+                let rows = batch_start..(batch_start + batch_size).min(n);
+                if errors.len() != rows.len() {
+                    errors = Array1::zeros(rows.len());
+                }
+                let x_batch = x.slice(ndarray::s![rows.clone(), ..]);
+                let y_batch = y.slice(ndarray::s![rows.clone()]);
+                let (mse, norm) = self.gradient_step(&x_batch, &y_batch, &mut errors, &mut weight_gradients, epoch)?;
+                loss_sum += mse * F::from_usize(rows.len()).unwrap();
+                gradient_norm = norm;
+            }
+            let loss = loss_sum / F::from_usize(n).unwrap();
+            history.record(loss, self.learning_rate, gradient_norm, start.elapsed());
+            #[cfg(feature = "tracing")]
+            tracing::trace!(epoch, loss = loss.to_f64(), gradient_norm = gradient_norm.to_f64(), "epoch");
+            #[cfg(feature = "tracing")]
+            if let config::Verbosity::Every(every) = config.verbosity {
+                if epoch % every == 0 {
+                    tracing::info!(epoch, loss = loss.to_f64(), "epoch");
+                }
+            }
+
+            if config.tol.is_some_and(|tol| gradient_norm.to_f64().unwrap() < tol) {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(epoch, "gradient norm below tolerance, stopping");
+                break;
+            }
+            if let Some(stopping) = config.early_stopping {
+                let loss = loss.to_f64().unwrap();
+                if loss < best.0 - stopping.min_delta {
+                    best = (loss, epoch);
+                } else if epoch - best.1 >= stopping.patience {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(epoch, best_epoch = best.1, "no improvement, stopping early");
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Same as `train`, but first checks the condition number of X and reports
+    // a warning in the outcome when it exceeds `max_condition`
+    pub fn train_checked<S: Data<Elem = F>, T: Data<Elem = F>>(
+        &mut self,
+        x: &ArrayBase<S, Ix2>,
+        y: &ArrayBase<T, Ix1>,
+        epochs: usize,
+        max_condition: f64,
+    ) -> Result<TrainOutcome<F>, LinearRegressionError> {
+        self.check_training_data(x, y)?;
+
+        let mut warnings = Vec::new();
+        let condition_number = diagnostics::condition_number(&x.mapv(|v| v.to_f64().unwrap()))?;
+        if condition_number > max_condition {
+            warnings.push(TrainingWarning::IllConditioned {
+                condition_number,
+                threshold: max_condition,
+            });
+        }
+
+        let history = self.train(x, y, epochs)?;
+        Ok(TrainOutcome { history, warnings })
+    }
+
+    // Mini-batch training over a stream of (X, y) chunks, one gradient step per
+    // chunk, so the full dataset never has to be held in memory. The history
+    // has one entry per chunk, with its loss before the update.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub fn train_from_iter<I>(&mut self, chunks: I) -> Result<TrainingHistory<F>, LinearRegressionError>
+    where
+        I: IntoIterator<Item = (Array2<F>, Array1<F>)>,
+    {
+        let mut history = TrainingHistory::new();
+        let start = Instant::now();
+        let mut errors = Array1::zeros(0);
+        let mut weight_gradients = Array1::zeros(self.weights.len());
+
+        for (step, (x, y)) in chunks.into_iter().enumerate() {
+            self.check_training_data(&x, &y)?;
+            if errors.len() != x.nrows() {
+                errors = Array1::zeros(x.nrows());
+            }
+            let (mse, gradient_norm) = self.gradient_step(&x, &y, &mut errors, &mut weight_gradients, step)?;
+            history.record(mse, self.learning_rate, gradient_norm, start.elapsed());
+        }
+
+        if history.is_empty() {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        self.fitted = true;
+        Ok(history)
+    }
+
+    // One gradient step on a batch, continuing from the current weights, for
+    // learning from a stream one batch at a time. Returns the batch loss
+    // before the update.
+    pub fn partial_fit<S: Data<Elem = F>, T: Data<Elem = F>>(
+        &mut self,
+        x: &ArrayBase<S, Ix2>,
+        y: &ArrayBase<T, Ix1>,
+    ) -> Result<F, LinearRegressionError> {
+        self.check_training_data(x, y)?;
+        let mut errors = Array1::zeros(x.nrows());
+        let mut weight_gradients = Array1::zeros(self.weights.len());
+        let (mse, _) = self.gradient_step(x, y, &mut errors, &mut weight_gradients, 0)?;
+        self.fitted = true;
+        Ok(mse)
+    }
+
+    fn check_training_data<S: Data<Elem = F>, T: Data<Elem = F>>(
+        &self,
+        x: &ArrayBase<S, Ix2>,
+        y: &ArrayBase<T, Ix1>,
+    ) -> Result<(), LinearRegressionError> {
         // Validate input dimensions
         if x.nrows() != y.len() {
             return Err(LinearRegressionError::DimensionMismatch {
@@ -97,39 +698,243 @@ impl LinearRegression {
         if x.nrows() == 0 {
             return Err(LinearRegressionError::EmptyData);
         }
+        Ok(())
+    }
 
-        let n_samples = x.nrows();
-        let mut history = Vec::with_capacity(epochs);
-        
-        for _ in 0..epochs {
-            let predictions = self.predict(x)?;
-            let errors = &predictions - y;
-            
-            // Check for numerical stability
-            if errors.iter().any(|&e| e.is_infinite() || e.is_nan()) {
-                return Err(LinearRegressionError::NumericalError(
-                    "Infinite or NaN values encountered during training"
-                ));
+    // MSE of the current weights on (x, y), using `errors` as scratch space
+    fn loss_into<S: Data<Elem = F>, T: Data<Elem = F>>(
+        &self,
+        x: &ArrayBase<S, Ix2>,
+        y: &ArrayBase<T, Ix1>,
+        errors: &mut Array1<F>,
+    ) -> F {
+        Zip::from(&mut *errors).and(y).for_each(|e, &t| *e = self.bias - t);
+        general_mat_vec_mul(F::one(), x, &self.weights, F::one(), errors);
+        errors.dot(errors) / F::from_usize(x.nrows()).unwrap()
+    }
+
+    // One gradient descent step on (x, y) using caller-provided buffers sized
+    // to the batch and the weights. Returns the MSE before the update and the
+    // norm of the gradient; `epoch` only labels the error if the step diverges.
+    fn gradient_step<S: Data<Elem = F>, T: Data<Elem = F>>(
+        &mut self,
+        x: &ArrayBase<S, Ix2>,
+        y: &ArrayBase<T, Ix1>,
+        errors: &mut Array1<F>,
+        weight_gradients: &mut Array1<F>,
+        epoch: usize,
+    ) -> Result<(F, F), LinearRegressionError> {
+        let inv_n = F::one() / F::from_usize(x.nrows()).unwrap();
+
+        // errors = X·w + b - y
+        Zip::from(&mut *errors).and(y).for_each(|e, &t| *e = self.bias - t);
+        general_mat_vec_mul(F::one(), x, &self.weights, F::one(), errors);
+
+        let error_sum = errors.sum();
+        let squared_sum = errors.dot(errors);
+
+        // Check for numerical stability (any NaN/inf error poisons the sums)
+        if !error_sum.is_finite() || !squared_sum.is_finite() {
+            return Err(LinearRegressionError::Diverged {
+                epoch,
+                message: "infinite or NaN values encountered; try a lower learning rate".to_string(),
+            });
+        }
+
+        general_mat_vec_mul(inv_n, &x.t(), errors, F::zero(), weight_gradients);
+
+        let bias_gradient = error_sum * inv_n;
+        let gradient_norm = (weight_gradients.dot(weight_gradients) + bias_gradient * bias_gradient).sqrt();
+
+        self.weights.scaled_add(-self.learning_rate, weight_gradients);
+        self.bias -= bias_gradient * self.learning_rate;
+
+        Ok((squared_sum * inv_n, gradient_norm))
+    }
+}
+
+// How the starting weights are set, by the builder and again by every
+// `fit`. Random draws are seeded, so the same seed gives the same model; the
+// bias starts at `initial_bias` in the builder and at zero in `fit`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum WeightInit {
+    #[default]
+    Zeros,
+    // Independent N(0, std²) draws
+    Normal { std: f64, seed: u64 },
+    // Independent draws from [-limit, limit)
+    Uniform { limit: f64, seed: u64 },
+}
+
+// Replaces the seed of the random schemes; zeros stay zeros
+impl random::Seeded for WeightInit {
+    fn set_random_state(&mut self, state: random::RandomState) {
+        if let Self::Normal { seed, .. } | Self::Uniform { seed, .. } = self {
+            *seed = state.seed();
+        }
+    }
+}
+
+impl WeightInit {
+    fn sample<F: Float>(&self, n_features: usize) -> Result<Array1<F>, LinearRegressionError> {
+        let (scale, seed) = match *self {
+            Self::Zeros => return Ok(Array1::zeros(n_features)),
+            Self::Normal { std, seed } => (std, seed),
+            Self::Uniform { limit, seed } => (limit, seed),
+        };
+        if !(scale.is_finite() && scale > 0.0) {
+            return Err(LinearRegressionError::InvalidParameter("initialization scale must be positive and finite"));
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let weights = Array1::from_shape_simple_fn(n_features, || match self {
+            // Box-Muller; 1 - u keeps the logarithm finite
+            Self::Normal { .. } => {
+                let (u, v): (f64, f64) = (rng.random(), rng.random());
+                scale * (-2.0 * (1.0 - u).ln()).sqrt() * (std::f64::consts::TAU * v).cos()
             }
+            _ => rng.random_range(-scale..scale),
+        });
+        Ok(weights.mapv(|w| F::from_f64(w).unwrap()))
+    }
+}
 
-            let weight_gradients = x.t().dot(&errors) * (1.0 / n_samples as f64);
-            let bias_gradient = errors.sum() * (1.0 / n_samples as f64);
-            
-            self.weights = &self.weights - &(weight_gradients * self.learning_rate);
-            self.bias -= bias_gradient * self.learning_rate;
-            
-            let mse = self.mse_loss(&predictions, y);
-            history.push(mse);
+// Collects `LinearRegression` settings and checks them together in `build`,
+// so invalid combinations fail before any training starts. The number of
+// features can be given directly or implied by initial weights or feature
+// names.
+#[derive(Debug, Clone)]
+pub struct LinearRegressionBuilder<F = f64> {
+    n_features: Option<usize>,
+    learning_rate: F,
+    epochs: usize,
+    weights: Option<Array1<F>>,
+    init: WeightInit,
+    bias: F,
+    feature_names: Option<Vec<String>>,
+}
+
+impl<F: Float> LinearRegressionBuilder<F> {
+    pub fn new() -> Self {
+        Self {
+            n_features: None,
+            learning_rate: F::from_f64(0.01).unwrap(),
+            epochs: default_epochs(),
+            weights: None,
+            init: WeightInit::Zeros,
+            bias: F::zero(),
+            feature_names: None,
         }
-        
-        Ok(history)
+    }
+
+    pub fn n_features(mut self, n_features: usize) -> Self {
+        self.n_features = Some(n_features);
+        self
+    }
+
+    // Gradient descent step size, 0.01 by default
+    pub fn learning_rate(mut self, learning_rate: F) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    // Epochs run by `Fit::fit`, 1000 by default
+    pub fn epochs(mut self, epochs: usize) -> Self {
+        self.epochs = epochs;
+        self
+    }
+
+    // Start training from these weights instead of zeros
+    pub fn initial_weights(mut self, weights: Array1<F>) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+
+    // Draw the starting weights instead; cannot be combined with
+    // `initial_weights`
+    pub fn weight_init(mut self, init: WeightInit) -> Self {
+        self.init = init;
+        self
+    }
+
+    pub fn initial_bias(mut self, bias: F) -> Self {
+        self.bias = bias;
+        self
+    }
+
+    // One name per feature; also sets the number of features if not given
+    pub fn feature_names(mut self, names: Vec<String>) -> Self {
+        self.feature_names = Some(names);
+        self
+    }
+
+    pub fn build(self) -> Result<LinearRegression<F>, LinearRegressionError> {
+        let named = self.feature_names.as_ref().map(Vec::len);
+        let n_features = match (self.n_features.or(named), &self.weights) {
+            (Some(n), Some(weights)) if weights.len() != n => {
+                return Err(LinearRegressionError::DimensionMismatch {
+                    expected: n,
+                    found: weights.len(),
+                    context: "length of initial weights",
+                });
+            }
+            (Some(n), _) => n,
+            (None, Some(weights)) => weights.len(),
+            (None, None) => return Err(LinearRegressionError::InvalidParameter("number of features is not set")),
+        };
+        if n_features == 0 {
+            return Err(LinearRegressionError::InvalidParameter("need at least one feature"));
+        }
+        if !(self.learning_rate.is_finite() && self.learning_rate > F::zero()) {
+            return Err(LinearRegressionError::InvalidParameter("learning rate must be positive and finite"));
+        }
+        if self.epochs == 0 {
+            return Err(LinearRegressionError::InvalidParameter("epochs must be at least 1"));
+        }
+        if !self.bias.is_finite() || self.weights.iter().flatten().any(|w| !w.is_finite()) {
+            return Err(LinearRegressionError::InvalidParameter("initial parameters must be finite"));
+        }
+
+        if self.weights.is_some() && self.init != WeightInit::Zeros {
+            return Err(LinearRegressionError::InvalidParameter(
+                "initial weights and a random initialization are both set"
+            ));
+        }
+
+        let mut model = LinearRegression::new(n_features, self.learning_rate).with_epochs(self.epochs);
+        // Weights given outright describe a ready model; drawn or zero ones
+        // still need training
+        model.fitted = self.weights.is_some();
+        model.weights = match self.weights {
+            Some(weights) => weights,
+            None => self.init.sample(n_features)?,
+        };
+        model.weight_init = self.init;
+        if let Some(names) = &self.feature_names {
+            if names.len() != n_features {
+                return Err(LinearRegressionError::DimensionMismatch {
+                    expected: n_features,
+                    found: names.len(),
+                    context: "number of feature names",
+                });
+            }
+        }
+        model.bias = self.bias;
+        model.feature_names = self.feature_names;
+        Ok(model)
+    }
+}
+
+impl<F: Float> Default for LinearRegressionBuilder<F> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ndarray::arr2;  // Added this import
+    use ndarray::{arr2, ShapeBuilder};  // Added this import
+    use std::error::Error;
 
     #[test]
     fn test_linear_regression() -> Result<(), Box<dyn Error>> {
@@ -151,9 +956,15 @@ mod tests {
         // Calculate R-squared
         let r2 = model.r_squared(&predictions, &y);
         assert!(r2 > 0.9); // Check for good fit
+        assert_eq!(model.score(&x, &y)?, r2);
 
         // Check if error decreases
-        assert!(history[0] > history[history.len() - 1]);
+        assert!(history.initial_loss() > history.final_loss());
+        assert_eq!(history.len(), 100);
+        assert_eq!(history.best_epoch(), Some(99));
+        assert!(history.gradient_norm[0] > history.gradient_norm[99]);
+        assert!(history.learning_rate.iter().all(|&lr| lr == 0.01));
+        assert!(history.elapsed.windows(2).all(|w| w[0] <= w[1]));
         
         Ok(())
     }
@@ -170,4 +981,313 @@ mod tests {
             _ => panic!("Expected dimension mismatch error"),
         }
     }
+
+    #[test]
+    fn test_predict_layouts_agree() -> Result<(), LinearRegressionError> {
+        let model = LinearRegression::from_parameters(Array1::from_shape_fn(11, |j| j as f64 - 4.5), 0.25);
+
+        let x = Array2::from_shape_fn((5, 11), |(i, j)| ((i * 11 + j) as f64).cos());
+        let mut x_fortran = Array2::zeros(x.raw_dim().f());
+        x_fortran.assign(&x);
+        assert!(x_fortran.as_slice().is_none());
+
+        let expected = x.dot(&model.weights) + model.bias;
+        for predictions in [model.predict(&x)?, model.predict(&x_fortran)?] {
+            assert!(predictions.iter().zip(expected.iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_feature_names() -> Result<(), LinearRegressionError> {
+        let names = vec!["sq_footage".to_string(), "bedrooms".to_string()];
+        let model = LinearRegression::builder()
+            .feature_names(names.clone())
+            .initial_weights(Array1::from(vec![0.3, 12.0]))
+            .build()?;
+        assert_eq!(model.feature_names(), Some(&names[..]));
+        assert_eq!(model.coefficients(), vec![("sq_footage".to_string(), 0.3), ("bedrooms".to_string(), 12.0)]);
+        assert_eq!((model.coef_for("bedrooms"), model.coef_for("age")), (Some(12.0), None));
+        assert!(LinearRegression::<f64>::builder().feature_names(names).n_features(3).build().is_err());
+
+        let unnamed = LinearRegression::<f64>::new(2, 0.1);
+        assert_eq!(unnamed.coef_for("x1"), Some(0.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_equation() -> Result<(), LinearRegressionError> {
+        let model = LinearRegression::builder()
+            .feature_names(vec!["sqft".to_string(), "bedrooms".to_string()])
+            .initial_weights(Array1::from(vec![0.5234, -10.3]))
+            .initial_bias(12.06)
+            .build()?;
+        assert_eq!(format!("{:.2}", model), "y = 0.52*sqft - 10.30*bedrooms + 12.06");
+        assert_eq!(model.to_string(), "y = 0.5234*sqft - 10.3*bedrooms + 12.06");
+        assert_eq!(format!("{:.1}", LinearRegression::<f32>::new(1, 0.1)), "y = 0.0*x0 + 0.0");
+        Ok(())
+    }
+
+    #[test]
+    fn test_train_with_config() -> Result<(), LinearRegressionError> {
+        use crate::config::{EarlyStopping, Schedule};
+        let x = Array2::from_shape_fn((12, 1), |(i, _)| i as f64 / 6.0);
+        let y = x.column(0).mapv(|v| 3.0 * v - 1.0);
+
+        // Full batches at a constant rate take the same steps as `train`
+        let config = TrainConfig { epochs: 50, learning_rate: 0.1, ..TrainConfig::default() };
+        let mut configured = LinearRegression::new(1, 0.5);
+        configured.train_with_config(&x, &y, &config)?;
+        let mut plain = LinearRegression::new(1, 0.1);
+        plain.train(&x, &y, 50)?;
+        assert!((configured.weights[0] - plain.weights[0]).abs() < 1e-12);
+        assert!((configured.bias - plain.bias).abs() < 1e-12);
+        assert_eq!(configured.learning_rate(), 0.5);
+
+        let config = TrainConfig {
+            epochs: 5000,
+            learning_rate: 0.2,
+            batch_size: Some(4),
+            schedule: Schedule::Step { every: 100, factor: 0.9 },
+            early_stopping: Some(EarlyStopping { patience: 5, min_delta: 1e-12 }),
+            ..TrainConfig::default()
+        };
+        let mut model = LinearRegression::new(1, 0.1);
+        let history = model.train_with_config(&x, &y, &config)?;
+        assert!(history.len() < 5000);
+        assert!((history.learning_rate[100] - 0.18).abs() < 1e-12);
+        assert!((model.weights[0] - 3.0).abs() < 1e-2 && (model.bias + 1.0).abs() < 1e-2);
+
+        // Rejected before the first update
+        let too_large = TrainConfig { batch_size: Some(13), ..config };
+        assert!(matches!(model.train_with_config(&x, &y, &too_large), Err(LinearRegressionError::InvalidParameter(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_train_with_validation() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((20, 1), |(i, _)| i as f64 / 10.0);
+        let y = x.column(0).mapv(|v| 3.0 * v - 1.0);
+        let (x_train, x_val) = (x.slice(ndarray::s![..15, ..]), x.slice(ndarray::s![15.., ..]));
+        let (y_train, y_val) = (y.slice(ndarray::s![..15]), y.slice(ndarray::s![15..]));
+
+        let mut model = LinearRegression::new(1, 0.1);
+        let history = model.train_with_validation(&x_train, &y_train, &x_val, &y_val, 2000)?;
+        let validation = history.validation_loss.as_ref().unwrap();
+        assert_eq!(validation.len(), 2000);
+        assert!(validation[1999] < 1e-4 * validation[0]);
+        // The first entry is the loss of the zero-initialized model
+        assert!((validation[0] - y_val.dot(&y_val) / 5.0).abs() < 1e-12);
+
+        // Validation does not change the updates themselves
+        let mut plain = LinearRegression::new(1, 0.1);
+        assert_eq!(plain.train(&x_train, &y_train, 2000)?.train_loss, history.train_loss);
+        assert!(model.train_with_validation(&x_train, &y_train, &x_val, &y_val.slice(ndarray::s![..2]), 5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_predict_one() -> Result<(), LinearRegressionError> {
+        let weights = Array1::from(vec![2.0, -1.0]);
+        let model = LinearRegression::builder().initial_weights(weights).initial_bias(0.5).build()?;
+        let x = arr2(&[[1.0, 3.0], [4.0, 2.0]]);
+        let batch = model.predict(&x)?;
+        assert_eq!(model.predict_one(&[1.0, 3.0])?, batch[0]);
+        assert_eq!(model.predict_one_array(&x.row(1))?, batch[1]);
+        assert!(model.predict_one(&[1.0]).is_err());
+
+        // Never trained: no silent all-bias predictions
+        let mut untrained = LinearRegression::new(2, 0.1);
+        assert!(!untrained.is_fitted());
+        assert!(matches!(untrained.predict(&x), Err(LinearRegressionError::NotFitted)));
+        assert!(matches!(untrained.predict_one(&[1.0, 3.0]), Err(LinearRegressionError::NotFitted)));
+        assert!(matches!(untrained.train(&x, &batch, 0), Err(LinearRegressionError::InvalidParameter(_))));
+        assert!(!untrained.is_fitted());
+        untrained.train(&x, &batch, 1)?;
+        assert!(untrained.is_fitted() && untrained.predict(&x).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_views_match_owned() -> Result<(), LinearRegressionError> {
+        let data = Array2::from_shape_fn((10, 3), |(i, j)| (i * 3 + j) as f64 / 10.0);
+        let y = data.column(2).mapv(|v| 3.0 * v - 1.0);
+        let x = data.slice(ndarray::s![.., ..2]);
+
+        let mut from_view = LinearRegression::new(2, 0.1);
+        let history = from_view.train(&x, &y.view(), 50)?;
+        let mut from_owned = LinearRegression::new(2, 0.1);
+        assert_eq!(from_owned.train(&x.to_owned(), &y, 50)?.train_loss, history.train_loss);
+
+        let rows = x.slice(ndarray::s![2..5, ..]);
+        assert_eq!(from_view.predict(&rows)?, from_owned.predict(&rows.to_owned())?);
+        let plain: Vec<Vec<f64>> = rows.rows().into_iter().map(|row| row.to_vec()).collect();
+        assert_eq!(from_view.predict_rows(&plain)?, from_view.predict(&rows)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_predict_into_reuses_buffer() -> Result<(), LinearRegressionError> {
+        let model = LinearRegression::from_parameters(Array1::from(vec![1.0, -2.0]), 0.5);
+        let x = arr2(&[[1.0, 1.0], [3.0, 0.5]]);
+
+        let mut out = Array1::zeros(2);
+        model.predict_into(&x, &mut out)?;
+        assert_eq!(out, model.predict(&x)?);
+
+        let mut buffer = [0.0; 2];
+        model.predict_into_slice(&x, &mut buffer)?;
+        assert_eq!(buffer.to_vec(), out.to_vec());
+
+        match model.predict_into(&x, &mut Array1::zeros(3)) {
+            Err(LinearRegressionError::DimensionMismatch { .. }) => (),
+            _ => panic!("Expected dimension mismatch error"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_train_from_iter() -> Result<(), Box<dyn Error>> {
+        let x = arr2(&[[0.0], [1.0], [2.0], [3.0], [4.0], [5.0]]);
+        let y = x.column(0).mapv(|v| 2.0 * v + 1.0);
+
+        // Several passes over the data in chunks of two rows
+        let chunks = (0..300).flat_map(|_| {
+            (0..3).map(|c| {
+                let rows = ndarray::s![2 * c..2 * c + 2, ..];
+                (x.slice(rows).to_owned(), y.slice(ndarray::s![2 * c..2 * c + 2]).to_owned())
+            })
+        });
+
+        let mut model: LinearRegression = LinearRegression::new(1, 0.05);
+        let history = model.train_from_iter(chunks)?;
+
+        assert_eq!(history.len(), 900);
+        assert!((model.weights[0] - 2.0).abs() < 0.05);
+        assert!((model.bias - 1.0).abs() < 0.1);
+
+        match model.train_from_iter(std::iter::empty()) {
+            Err(LinearRegressionError::EmptyData) => (),
+            _ => panic!("Expected empty data error"),
+        }
+
+        // Feeding the same chunks one call at a time takes the same steps
+        let mut streamed: LinearRegression = LinearRegression::new(1, 0.05);
+        let mut batched = streamed.clone();
+        let first = batched.train_from_iter([(x.clone(), y.clone())])?;
+        assert_eq!(streamed.partial_fit(&x, &y)?, first.train_loss[0]);
+        for c in 0..3 {
+            let rows = 2 * c..2 * c + 2;
+            streamed.partial_fit(&x.slice(ndarray::s![rows.clone(), ..]), &y.slice(ndarray::s![rows]))?;
+        }
+        batched.train_from_iter((0..3).map(|c| {
+            (x.slice(ndarray::s![2 * c..2 * c + 2, ..]).to_owned(), y.slice(ndarray::s![2 * c..2 * c + 2]).to_owned())
+        }))?;
+        assert_eq!((streamed.weights, streamed.bias), (batched.weights, batched.bias));
+        Ok(())
+    }
+
+    #[test]
+    fn test_train_checked_warns_on_unscaled_features() -> Result<(), LinearRegressionError> {
+        let x = arr2(&[[1200.0, 2.0], [1500.0, 3.0], [2000.0, 3.0], [1100.0, 2.0]]);
+        let y = Array1::from(vec![200.0, 250.0, 320.0, 190.0]);
+
+        let mut model = LinearRegression::new(2, 1e-8);
+        let outcome = model.train_checked(&x, &y, 10, 1e3)?;
+        assert_eq!(outcome.history.len(), 10);
+        match outcome.warnings.as_slice() {
+            [TrainingWarning::IllConditioned { condition_number, .. }] => assert!(*condition_number > 1e3),
+            _ => panic!("Expected an ill-conditioning warning"),
+        }
+
+        let (_, x_scaled) = preprocessing::StandardScaler::fit_transform(&x)?;
+        let outcome = LinearRegression::new(2, 0.1).train_checked(&x_scaled, &y, 10, 1e3)?;
+        assert!(outcome.warnings.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_linear_regression_f32() -> Result<(), Box<dyn Error>> {
+        let x = arr2(&[[1.0f32], [2.0], [3.0], [4.0]]);
+        let y = Array1::from(vec![3.0f32, 5.0, 7.0, 9.0]);
+
+        let mut model = LinearRegression::<f32>::new(1, 0.05);
+        let history = model.train(&x, &y, 2000)?;
+
+        assert!(history.final_loss().unwrap() < 1e-3);
+        assert!((model.weights[0] - 2.0).abs() < 0.05);
+        assert!((model.bias - 1.0).abs() < 0.1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_divergence_reports_epoch() {
+        let x = arr2(&[[1e3], [2e3], [3e3]]);
+        let y = Array1::from(vec![1.0, 2.0, 3.0]);
+        let mut model = LinearRegression::new(1, 1.0);
+        match model.train(&x, &y, 100) {
+            Err(LinearRegressionError::Diverged { epoch, .. }) => assert!(epoch > 0 && epoch < 100),
+            _ => panic!("Expected divergence error"),
+        }
+    }
+
+    #[test]
+    fn test_builder() -> Result<(), LinearRegressionError> {
+        let model: LinearRegression = LinearRegression::builder().n_features(3).build()?;
+        assert_eq!(model.weights.len(), 3);
+        assert_eq!((model.learning_rate(), model.epochs()), (0.01, 1000));
+
+        let model = LinearRegression::builder()
+            .initial_weights(Array1::from(vec![1.0, 2.0]))
+            .initial_bias(0.5)
+            .learning_rate(0.1)
+            .epochs(50)
+            .build()?;
+        assert_eq!(model.weights.to_vec(), vec![1.0, 2.0]);
+        assert_eq!((model.bias, model.learning_rate(), model.epochs()), (0.5, 0.1, 50));
+
+        let builder = LinearRegression::<f64>::builder();
+        assert!(builder.clone().build().is_err());
+        assert!(builder.clone().n_features(2).learning_rate(-0.1).build().is_err());
+        assert!(builder.clone().n_features(2).epochs(0).build().is_err());
+        match builder.n_features(3).initial_weights(Array1::zeros(2)).build() {
+            Err(LinearRegressionError::DimensionMismatch { expected: 3, found: 2, .. }) => (),
+            _ => panic!("Expected dimension mismatch error"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_weight_init() -> Result<(), LinearRegressionError> {
+        let normal = WeightInit::Normal { std: 0.1, seed: 3 };
+        let build = |init| LinearRegression::<f64>::builder().n_features(2000).weight_init(init).build();
+        let weights = build(normal)?.weights;
+        assert_eq!(build(normal)?.weights, weights);
+        assert_ne!(build(WeightInit::Normal { std: 0.1, seed: 4 })?.weights, weights);
+        assert!(weights.mean().unwrap().abs() < 0.01 && (weights.std(0.0) - 0.1).abs() < 0.01);
+
+        let uniform = build(WeightInit::Uniform { limit: 0.5, seed: 3 })?.weights;
+        assert!(uniform.iter().all(|w| w.abs() <= 0.5) && uniform.iter().any(|w| w.abs() > 0.4));
+        assert!(build(WeightInit::Zeros)?.weights.iter().all(|&w| w == 0.0));
+
+        assert!(build(WeightInit::Uniform { limit: 0.0, seed: 3 }).is_err());
+        let both = LinearRegression::builder().initial_weights(Array1::from(vec![1.0])).weight_init(normal);
+        assert!(both.build().is_err());
+
+        // `fit` and resets draw again instead of starting from zeros, so
+        // resampling and search use the configured initialization too
+        let x = Array2::from_shape_fn((10, 2000), |(i, j)| ((i + j) % 7) as f64);
+        let y = Array1::from_shape_fn(10, |i| i as f64);
+        let mut model = LinearRegression::builder().n_features(2000).weight_init(normal).epochs(1).build()?;
+        model.reset_weights();
+        assert_eq!(model.weights, weights);
+        let mut seeded = random::Seeded::with_random_state(model.clone(), random::RandomState::new(4));
+        seeded.reset_weights();
+        assert_eq!(seeded.weights, build(WeightInit::Normal { std: 0.1, seed: 4 })?.weights);
+        let mut expected = model.clone();
+        expected.train(&x, &y, 1)?;
+        crate::traits::Fit::fit(&mut model, &x, &y)?;
+        assert_eq!(model.weights, expected.weights);
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,282 @@
+use crate::LinearRegressionError;
+use ndarray::{concatenate, Array1, Array2, Axis};
+
+// Prepend a column of ones so the intercept is estimated as coefficient 0
+pub(crate) fn with_intercept(x: &Array2<f64>) -> Array2<f64> {
+    let ones = Array2::ones((x.nrows(), 1));
+    concatenate![Axis(1), ones, *x]
+}
+
+// Gauss-Jordan inversion with partial pivoting
+pub(crate) fn inverse(a: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+    let n = a.nrows();
+    if a.ncols() != n {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: n,
+            found: a.ncols(),
+            context: "columns of square matrix",
+        });
+    }
+
+    let mut m = a.clone();
+    let mut inv = Array2::eye(n);
+    let scale = a.iter().fold(0.0f64, |acc, v| acc.max(v.abs())).max(1.0);
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| m[[i, col]].abs().total_cmp(&m[[j, col]].abs()))
+            .unwrap();
+        if m[[pivot, col]].abs() <= 1e-12 * scale {
+            return Err(LinearRegressionError::NumericalError("matrix is singular"));
+        }
+        if pivot != col {
+            for j in 0..n {
+                m.swap([pivot, j], [col, j]);
+                inv.swap([pivot, j], [col, j]);
+            }
+        }
+
+        let p = m[[col, col]];
+        m.row_mut(col).mapv_inplace(|v| v / p);
+        inv.row_mut(col).mapv_inplace(|v| v / p);
+
+        for row in 0..n {
+            if row != col {
+                let factor = m[[row, col]];
+                if factor != 0.0 {
+                    let m_col = m.row(col).to_owned();
+                    let inv_col = inv.row(col).to_owned();
+                    m.row_mut(row).scaled_add(-factor, &m_col);
+                    inv.row_mut(row).scaled_add(-factor, &inv_col);
+                }
+            }
+        }
+    }
+
+    Ok(inv)
+}
+
+// Least-squares coefficients for a · beta ≈ b via the normal equations
+pub(crate) fn least_squares(a: &Array2<f64>, b: &Array1<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+    Ok(inverse(&a.t().dot(a))?.dot(&a.t().dot(b)))
+}
+
+// Eigen-decomposition of a symmetric matrix by cyclic Jacobi rotations.
+// Returns eigenvalues in descending order and the matching eigenvectors as columns.
+pub(crate) fn symmetric_eigen(a: &Array2<f64>) -> (Array1<f64>, Array2<f64>) {
+    let n = a.nrows();
+    let mut a = a.clone();
+    let mut v = Array2::<f64>::eye(n);
+    let norm = a.iter().map(|x| x * x).sum::<f64>().max(f64::MIN_POSITIVE);
+
+    for _ in 0..100 {
+        let mut off = 0.0;
+        for p in 0..n {
+            for q in (p + 1)..n {
+                off += a[[p, q]] * a[[p, q]];
+            }
+        }
+        if off <= 1e-30 * norm {
+            break;
+        }
+
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let apq = a[[p, q]];
+                if apq.abs() <= f64::MIN_POSITIVE {
+                    continue;
+                }
+                let theta = (a[[q, q]] - a[[p, p]]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for k in 0..n {
+                    let (akp, akq) = (a[[k, p]], a[[k, q]]);
+                    a[[k, p]] = c * akp - s * akq;
+                    a[[k, q]] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[[p, k]], a[[q, k]]);
+                    a[[p, k]] = c * apk - s * aqk;
+                    a[[q, k]] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[[k, p]], v[[k, q]]);
+                    v[[k, p]] = c * vkp - s * vkq;
+                    v[[k, q]] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| a[[j, j]].total_cmp(&a[[i, i]]));
+    let values = order.iter().map(|&i| a[[i, i]]).collect();
+    (values, v.select(Axis(1), &order))
+}
+
+// Thin singular value decomposition by one-sided Jacobi rotations. Returns
+// (u, s, v) with a = u · diag(s) · v', singular values in descending order,
+// and min(n, p) columns in u and v.
+pub(crate) fn svd(a: &Array2<f64>) -> (Array2<f64>, Array1<f64>, Array2<f64>) {
+    if a.nrows() < a.ncols() {
+        let (u, s, v) = svd(&a.t().to_owned());
+        return (v, s, u);
+    }
+
+    // Orthogonalize the columns of w = a · v pairwise; at convergence they are
+    // u scaled by the singular values
+    let p = a.ncols();
+    let mut w = a.clone();
+    let mut v = Array2::<f64>::eye(p);
+    for _ in 0..100 {
+        let mut rotated = false;
+        for i in 0..p {
+            for j in (i + 1)..p {
+                let (wi, wj) = (w.column(i), w.column(j));
+                let (alpha, beta, gamma) = (wi.dot(&wi), wj.dot(&wj), wi.dot(&wj));
+                if gamma.abs() <= 1e-15 * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (2.0 * gamma);
+                let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                let c = 1.0 / (1.0 + t * t).sqrt();
+                let s = c * t;
+
+                for m in [&mut w, &mut v] {
+                    for k in 0..m.nrows() {
+                        let (mi, mj) = (m[[k, i]], m[[k, j]]);
+                        m[[k, i]] = c * mi - s * mj;
+                        m[[k, j]] = s * mi + c * mj;
+                    }
+                }
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+
+    let norms: Array1<f64> = w.columns().into_iter().map(|col| col.dot(&col).sqrt()).collect();
+    let mut order: Vec<usize> = (0..p).collect();
+    order.sort_by(|&i, &j| norms[j].total_cmp(&norms[i]));
+
+    let s = order.iter().map(|&i| norms[i]).collect();
+    let mut u = w.select(Axis(1), &order);
+    for (mut col, &i) in u.columns_mut().into_iter().zip(order.iter()) {
+        if norms[i] > 0.0 {
+            col /= norms[i];
+        }
+    }
+    (u, s, v.select(Axis(1), &order))
+}
+
+// Lower-triangular L with L Lᵀ = a for a symmetric positive definite matrix
+pub(crate) fn cholesky(a: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+    let n = a.nrows();
+    let mut l = Array2::<f64>::zeros((n, n));
+    for j in 0..n {
+        let mut diag = a[[j, j]];
+        for k in 0..j {
+            diag -= l[[j, k]] * l[[j, k]];
+        }
+        if diag.is_nan() || diag <= 0.0 {
+            return Err(LinearRegressionError::NumericalError("matrix is not positive definite"));
+        }
+        let d = diag.sqrt();
+        l[[j, j]] = d;
+        for i in j + 1..n {
+            let mut v = a[[i, j]];
+            for k in 0..j {
+                v -= l[[i, k]] * l[[j, k]];
+            }
+            l[[i, j]] = v / d;
+        }
+    }
+    Ok(l)
+}
+
+// Solve (L Lᵀ) x = b for every column of b, given the Cholesky factor L
+pub(crate) fn cholesky_solve(l: &Array2<f64>, b: &Array2<f64>) -> Array2<f64> {
+    let n = l.nrows();
+    let mut x = b.clone();
+    for mut col in x.columns_mut() {
+        // Forward substitution with L, then back substitution with Lᵀ
+        for i in 0..n {
+            let mut v = col[i];
+            for k in 0..i {
+                v -= l[[i, k]] * col[k];
+            }
+            col[i] = v / l[[i, i]];
+        }
+        for i in (0..n).rev() {
+            let mut v = col[i];
+            for k in i + 1..n {
+                v -= l[[k, i]] * col[k];
+            }
+            col[i] = v / l[[i, i]];
+        }
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_inverse() -> Result<(), LinearRegressionError> {
+        let a = arr2(&[[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 4.0]]);
+        let product = a.dot(&inverse(&a)?);
+        let eye = Array2::<f64>::eye(3);
+        assert!(product.iter().zip(eye.iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+
+        let singular = arr2(&[[1.0, 2.0], [2.0, 4.0]]);
+        assert!(inverse(&singular).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let a = arr2(&[[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]]);
+        let (values, vectors) = symmetric_eigen(&a);
+
+        assert!(values[0] >= values[1] && values[1] >= values[2]);
+        for (i, &lambda) in values.iter().enumerate() {
+            let v = vectors.column(i);
+            let residual = a.dot(&v) - &v * lambda;
+            assert!(residual.iter().all(|r| r.abs() < 1e-10));
+        }
+        // Trace is preserved
+        assert!((values.sum() - 9.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_cholesky() -> Result<(), LinearRegressionError> {
+        let a = arr2(&[[4.0, 2.0, 0.4], [2.0, 5.0, 1.0], [0.4, 1.0, 3.0]]);
+        let l = cholesky(&a)?;
+        let rebuilt = l.dot(&l.t());
+        assert!(rebuilt.iter().zip(a.iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+
+        let b = arr2(&[[1.0], [2.0], [3.0]]);
+        let x = cholesky_solve(&l, &b);
+        assert!(a.dot(&x).iter().zip(b.iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+        assert!(cholesky(&arr2(&[[1.0, 2.0], [2.0, 1.0]])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_svd() {
+        let a = arr2(&[[3.0, 2.0, 2.0], [2.0, 3.0, -2.0]]);
+        for m in [a.clone(), a.t().to_owned()] {
+            let (u, s, v) = svd(&m);
+            // Known singular values of this matrix: 5 and 3
+            assert!((s[0] - 5.0).abs() < 1e-12 && (s[1] - 3.0).abs() < 1e-12);
+            let rebuilt = (&u * &s).dot(&v.t());
+            assert!(rebuilt.iter().zip(m.iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{Float, LinearRegression, LinearRegressionError};
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+
+// The learning rate range test (Smith 2017): one gradient step per rate, with
+// the rate growing geometrically from `min_lr` to `max_lr`, while the loss is
+// recorded after each step. The loss falls while the rate is usable and blows
+// up once it is too large.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LrRange {
+    pub min_lr: f64,
+    pub max_lr: f64,
+    pub steps: usize,
+}
+
+impl Default for LrRange {
+    fn default() -> Self {
+        Self { min_lr: 1e-6, max_lr: 1.0, steps: 100 }
+    }
+}
+
+// Loss after each step of the sweep, cut short once the loss diverges
+#[derive(Debug, Clone, PartialEq)]
+pub struct LrFinderResult<F = f64> {
+    pub learning_rates: Vec<F>,
+    pub losses: Vec<F>,
+}
+
+impl<F: Float> LrFinderResult<F> {
+    // A tenth of the rate that reached the lowest loss, a safe distance from
+    // where training starts to diverge. None when the loss never went below
+    // its first value.
+    pub fn suggestion(&self) -> Option<F> {
+        let (best, _) = self
+            .losses
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| l.is_finite())
+            .fold(None, |best: Option<(usize, F)>, (i, &l)| match best {
+                Some((_, b)) if b <= l => best,
+                _ => Some((i, l)),
+            })?;
+        (best > 0).then(|| self.learning_rates[best] / F::from_f64(10.0).unwrap())
+    }
+}
+
+// Runs the range test on a copy of `model`, starting from its current
+// weights, so the model itself is left untouched. The sweep stops early once
+// the loss is non-finite or four times the best so far.
+pub fn lr_finder<F: Float>(
+    model: &LinearRegression<F>,
+    x: &Array2<F>,
+    y: &Array1<F>,
+    range: LrRange,
+) -> Result<LrFinderResult<F>, LinearRegressionError> {
+    if !(range.min_lr > 0.0 && range.min_lr < range.max_lr && range.max_lr.is_finite()) {
+        return Err(LinearRegressionError::InvalidParameter("lr range needs 0 < min_lr < max_lr"));
+    }
+    if range.steps < 2 {
+        return Err(LinearRegressionError::InvalidParameter("lr range needs at least two steps"));
+    }
+
+    let mut probe = model.clone();
+    let mut errors = Array1::zeros(x.nrows());
+    let ratio = range.max_lr / range.min_lr;
+    let mut result = LrFinderResult { learning_rates: Vec::new(), losses: Vec::new() };
+    let mut best = F::infinity();
+    for step in 0..range.steps {
+        let rate = F::from_f64(range.min_lr * ratio.powf(step as f64 / (range.steps - 1) as f64)).unwrap();
+        probe.set_learning_rate(rate)?;
+        match probe.partial_fit(x, y) {
+            Ok(_) => (),
+            Err(LinearRegressionError::Diverged { .. }) => break,
+            Err(e) => return Err(e),
+        }
+        let loss = probe.loss_into(x, y, &mut errors);
+        result.learning_rates.push(rate);
+        result.losses.push(loss);
+        if !loss.is_finite() || loss > best * F::from_f64(4.0).unwrap() {
+            break;
+        }
+        best = best.min(loss);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lr_finder() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((50, 2), |(i, j)| ((i * (j + 2)) % 7) as f64 / 3.0 - 1.0);
+        let y = x.column(0).mapv(|v| 2.0 * v) + x.column(1).mapv(|v| 0.5 * v) + 1.0;
+        let model = LinearRegression::new(2, 0.01);
+        let result = lr_finder(&model, &x, &y, LrRange { min_lr: 1e-5, max_lr: 10.0, steps: 60 })?;
+
+        // The sweep stops once large rates make the loss blow up
+        assert!(result.losses.len() < 60);
+        assert!(result.learning_rates.windows(2).all(|w| w[0] < w[1]));
+        let suggested = result.suggestion().unwrap();
+        let mut trained = model.clone();
+        trained.set_learning_rate(suggested)?;
+        trained.train(&x, &y, 500)?;
+        assert!(trained.score(&x, &y)? > 0.99);
+        assert!(!model.is_fitted());
+
+        assert!(lr_finder(&model, &x, &y, LrRange { min_lr: 1.0, max_lr: 0.1, steps: 10 }).is_err());
+        Ok(())
+    }
+}
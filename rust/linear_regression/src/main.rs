@@ -1,110 +1,324 @@
-use linear_regression::LinearRegression;
-use ndarray::{arr2, Array1, Array2};
-use std::error::Error;
-
-// Function to normalize features
-fn normalize_features(x: &Array2<f64>) -> (Array2<f64>, Array1<f64>, Array1<f64>) {
-    let mut means = Array1::zeros(x.ncols());
-    let mut stds = Array1::zeros(x.ncols());
-    
-    // Calculate mean and std for each feature
-    for j in 0..x.ncols() {
-        let column = x.column(j);
-        means[j] = column.mean().unwrap();
-        stds[j] = column.iter()
-            .map(|&x| (x - means[j]).powi(2))
-            .sum::<f64>()
-            .sqrt() / (column.len() as f64).sqrt();
+use clap::builder::RangedU64ValueParser;
+use clap::{Parser, Subcommand};
+use linear_regression::automl::Candidate;
+use linear_regression::data::{self, Task};
+use linear_regression::diagnostics;
+use linear_regression::experiment::Experiment;
+use linear_regression::loader::DataLoader;
+use linear_regression::metrics;
+use linear_regression::prelude::*;
+use ndarray::arr2;
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+#[command(name = "linear_regression", about = "Train and apply linear regression models on CSV data")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Seed for every random choice: fold shuffles, bootstraps, forests
+    #[arg(long, global = true, default_value_t = 0)]
+    seed: u64,
+    /// Ignore time budgets and run on one thread, so runs reproduce bit for bit on any machine
+    #[arg(long, global = true)]
+    deterministic: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Fit a model on a CSV file and save it as JSON
+    Train {
+        #[arg(long)]
+        data: PathBuf,
+        #[arg(long)]
+        target: String,
+        #[arg(long)]
+        out: PathBuf,
+        #[arg(long, default_value_t = 0.01)]
+        learning_rate: f64,
+        #[arg(long, default_value_t = 1000, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        epochs: usize,
+        /// Write loss, residual and predicted-vs-actual charts into this directory
+        #[arg(long)]
+        plots: Option<PathBuf>,
+        /// png or svg
+        #[arg(long, default_value = "png")]
+        plot_format: String,
+    },
+    /// Run the experiment described by a TOML file: fit, then save the outputs it lists
+    Run {
+        #[arg(long)]
+        config: PathBuf,
+        /// Write loss, residual and predicted-vs-actual charts into this directory
+        #[arg(long)]
+        plots: Option<PathBuf>,
+        /// png or svg
+        #[arg(long, default_value = "png")]
+        plot_format: String,
+    },
+    /// Write predictions for a CSV file using a saved model
+    Predict {
+        #[arg(long)]
+        model: PathBuf,
+        #[arg(long)]
+        data: PathBuf,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Print metrics for a saved model on a labelled CSV file
+    Evaluate {
+        #[arg(long)]
+        model: PathBuf,
+        #[arg(long)]
+        data: PathBuf,
+        /// Target column, defaults to the one the model was trained on
+        #[arg(long)]
+        target: Option<String>,
+        /// Write loss, residual and predicted-vs-actual charts into this directory
+        #[arg(long)]
+        plots: Option<PathBuf>,
+        /// png or svg
+        #[arg(long, default_value = "png")]
+        plot_format: String,
+        /// Write per-row residuals, leverage and QQ-plot data to this .csv or .json file
+        #[arg(long)]
+        diagnostics: Option<PathBuf>,
+    },
+    /// Cross-validate a default model for the target: a random forest for
+    /// classes, linear regression for continuous values
+    Auto {
+        /// CSV, JSON Lines or Parquet file; text and date columns are encoded
+        #[arg(long)]
+        data: PathBuf,
+        #[arg(long)]
+        target: String,
+        /// classification or regression, detected from the target by default
+        #[arg(long)]
+        task: Option<Task>,
+        #[arg(long, default_value_t = 5)]
+        folds: usize,
+    },
+    /// Run the built-in housing price example
+    Demo,
+}
+
+fn main() -> Result<(), MlError> {
+    let cli = Cli::parse();
+    // Spans are printed as they close, with their duration, e.g. with
+    // RUST_LOG=linear_regression=debug,ml_core=debug
+    #[cfg(feature = "tracing")]
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .init();
+    let state = RandomState::new(cli.seed).with_deterministic(cli.deterministic);
+    #[cfg(feature = "parallel")]
+    if cli.deterministic {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build_global()
+            .expect("the thread pool is configured once, before any parallel work");
     }
-    
-    // Create normalized features array
-    let mut x_normalized = Array2::zeros(x.dim());
-    for i in 0..x.nrows() {
-        for j in 0..x.ncols() {
-            x_normalized[[i, j]] = (x[[i, j]] - means[j]) / stds[j];
+    match cli.command {
+        Command::Train { data, target, out, learning_rate, epochs, plots, plot_format } => {
+            let dataset = Dataset::from_csv(&data, &target)?;
+            println!("Training on {} rows, {} features...", dataset.n_samples(), dataset.n_features());
+
+            let (pipeline, history) = Pipeline::fit(&dataset, learning_rate, epochs)?;
+            print_losses(&history);
+            println!("Fitted: {:.4}", pipeline);
+
+            pipeline.save(&out)?;
+            println!("Model saved to {}", out.display());
+            if let Some(dir) = plots {
+                write_plots(&dir, &plot_format, Some(&history), &pipeline.predict(&dataset.x)?, &dataset.y)?;
+            }
+        }
+        Command::Run { config, plots, plot_format } => {
+            let experiment = Experiment::from_file(&config)?;
+            let (pipeline, history) = experiment.run()?;
+            print_losses(&history);
+            println!("Fitted: {:.4}", pipeline);
+            println!("Model saved to {}", experiment.output.model.display());
+            if let Some(dir) = plots {
+                let dataset = Dataset::from_csv(&experiment.data.path, &experiment.data.target)?;
+                write_plots(&dir, &plot_format, Some(&history), &pipeline.predict(&dataset.x)?, &dataset.y)?;
+            }
+        }
+        Command::Predict { model, data, out } => {
+            let pipeline = Pipeline::load(&model)?;
+            let (headers, table) = data::read_csv(&data)?;
+            let x = data::select_columns(&headers, &table, &pipeline.feature_names)?;
+
+            let predictions = pipeline.predict(&x)?;
+            data::write_predictions(&out, &predictions)?;
+            println!("Wrote {} predictions to {}", predictions.len(), out.display());
+        }
+        Command::Evaluate { model, data, target, plots, plot_format, diagnostics } => {
+            let pipeline = Pipeline::load(&model)?;
+            let target = target.unwrap_or_else(|| pipeline.target_name.clone());
+            let (headers, table) = data::read_csv(&data)?;
+            let x = data::select_columns(&headers, &table, &pipeline.feature_names)?;
+            let y = data::select_columns(&headers, &table, &[target])?.column(0).to_owned();
+
+            let predictions = pipeline.predict(&x)?;
+            println!("Samples: {}", y.len());
+            println!("MSE:  {:.4}", metrics::mean_squared_error(&predictions, &y));
+            println!("RMSE: {:.4}", metrics::root_mean_squared_error(&predictions, &y));
+            println!("MAE:  {:.4}", metrics::mean_absolute_error(&predictions, &y));
+            println!("R²:   {:.4}", metrics::r2_score(&predictions, &y));
+            if let Some(dir) = plots {
+                write_plots(&dir, &plot_format, None, &predictions, &y)?;
+            }
+            if let Some(path) = diagnostics {
+                pipeline.residual_data(&x, &y)?.save(&path)?;
+                println!("Diagnostics written to {}", path.display());
+            }
         }
+        Command::Auto { data, target, task, folds } => {
+            let loaded = DataLoader::new(&data).with_target(&target).load()?;
+            let (dataset, labels) = (loaded.dataset, loaded.labels);
+            let task = task.unwrap_or_else(|| Task::infer(&dataset.y, labels.as_ref()));
+            println!("Task: {} ({} rows, {} features)", task, dataset.n_samples(), dataset.n_features());
+            if let Some(labels) = &labels {
+                println!("Classes: {}", labels.classes().join(", "));
+            }
+            let folds = folds.min(dataset.n_samples());
+            match task {
+                Task::Classification => {
+                    let forest = RandomForest::new(Criterion::Gini, 100).with_random_state(state);
+                    cross_validate(&forest, &dataset, task, folds, state)?
+                }
+                Task::Regression => cross_validate(&AutoModel::new(Candidate::Linear), &dataset, task, folds, state)?,
+            }
+        }
+        Command::Demo => demo()?,
     }
-    
-    (x_normalized, means, stds)
+
+    Ok(())
 }
 
-// Function to normalize new data using existing means and stds
-fn normalize_new_data(x: &Array2<f64>, means: &Array1<f64>, stds: &Array1<f64>) -> Array2<f64> {
-    let mut x_normalized = Array2::zeros(x.dim());
-    for i in 0..x.nrows() {
-        for j in 0..x.ncols() {
-            x_normalized[[i, j]] = (x[[i, j]] - means[j]) / stds[j];
-        }
+fn print_losses(history: &TrainingHistory) {
+    if let (Some(initial), Some(last)) = (history.initial_loss(), history.final_loss()) {
+        println!("Initial loss: {:.4}", initial);
+        println!("Final loss: {:.4}", last);
     }
-    x_normalized
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    // Sample housing data: [square_footage, bedrooms]
-    let x_train = arr2(&[
-        [1200.0, 2.0],
-        [1500.0, 3.0],
-        [2000.0, 3.0],
-        [1700.0, 3.0],
-        [1100.0, 2.0],
-        [1600.0, 3.0],
-        [2300.0, 4.0],
-        [1900.0, 3.0],
-        [2100.0, 4.0],
-        [1250.0, 2.0],
-    ]);
+#[cfg(feature = "plotting")]
+fn write_plots(
+    dir: &Path,
+    format: &str,
+    history: Option<&TrainingHistory>,
+    predictions: &ndarray::Array1<f64>,
+    y: &ndarray::Array1<f64>,
+) -> Result<(), MlError> {
+    use linear_regression::plotting::{save_plots, PlotFormat};
+    let format = PlotFormat::from_path(Path::new(&format!("plot.{}", format)))
+        .ok_or_else(|| MlError::Parse(format!("unknown plot format '{}', expected png or svg", format)))?;
+    for path in save_plots(dir, history, predictions, y, format)? {
+        println!("Plot written to {}", path.display());
+    }
+    Ok(())
+}
 
-    // House prices in thousands of dollars
-    let y_train = Array1::from(vec![
-        200.0, 250.0, 320.0, 280.0, 190.0,
-        260.0, 355.0, 310.0, 330.0, 205.0,
-    ]);
+#[cfg(not(feature = "plotting"))]
+fn write_plots(
+    _dir: &Path,
+    _format: &str,
+    _history: Option<&TrainingHistory>,
+    _predictions: &ndarray::Array1<f64>,
+    _y: &ndarray::Array1<f64>,
+) -> Result<(), MlError> {
+    Err(MlError::InvalidParameter("--plots needs the `plotting` feature"))
+}
+
+fn cross_validate<E: Fit + Score + Clone + Send + Sync>(
+    model: &E,
+    dataset: &Dataset,
+    task: Task,
+    folds: usize,
+    state: RandomState,
+) -> Result<(), MlError> {
+    println!("{}-fold cross-validation:", folds);
+    for &metric in task.default_metrics() {
+        let scores = cross_val_score(model, &dataset.x, &dataset.y, folds, state.seed(), Some(metric))?;
+        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        let std = (scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / scores.len() as f64).sqrt();
+        println!("{:<9} {:.4} ± {:.4}", format!("{}:", metric.name()), mean, std);
+    }
+    Ok(())
+}
+
+fn demo() -> Result<(), MlError> {
+    // Sample housing data: square footage, bedrooms and the price in
+    // thousands of dollars
+    let housing = DataLoader::new(concat!(env!("CARGO_MANIFEST_DIR"), "/data/housing.csv"))
+        .with_target("price")
+        .load()?;
+    let names = housing.dataset.feature_names.clone();
+    let n_features = housing.dataset.n_features();
+    let (x_train, y_train) = (housing.dataset.x, housing.dataset.y);
+
+    // Square footage and bedrooms move together, which makes their
+    // individual coefficients hard to interpret
+    let vif_report = diagnostics::vif(&x_train)?;
+    let vifs: Vec<String> = names.iter().zip(vif_report.vifs.iter()).map(|(n, v)| format!("{} {:.2}", n, v)).collect();
+    println!("VIF: {}", vifs.join(", "));
+    for warning in vif_report.warnings(5.0, Some(&names)) {
+        println!("Warning: {}", warning);
+    }
 
     // Normalize features
     println!("Normalizing features...");
-    let (x_train_norm, means, stds) = normalize_features(&x_train);
-    
+    let (scaler, x_train_norm) = StandardScaler::fit_transform(&x_train)?;
+
     // Create and train the model
-    let mut model = LinearRegression::new(2, 0.01);
-    
+    let mut model = LinearRegression::new(n_features, 0.01).with_feature_names(names.clone());
+
     println!("Training model...");
     let history = model.train(&x_train_norm, &y_train, 1000)?;
-    
+
     // Print training results
     println!("Training completed!");
-    println!("Initial loss: {:.2}", history[0]);
-    println!("Final loss: {:.2}", history[history.len() - 1]);
+    if let (Some(initial), Some(last)) = (history.initial_loss(), history.final_loss()) {
+        println!("Initial loss: {:.2}", initial);
+        println!("Final loss: {:.2}", last);
+    }
 
-    // Make predictions on some test cases
-    let x_test = arr2(&[
+    // Make predictions on some test cases, given by column name and put in
+    // the order the model was trained on
+    let test_headers = ["sqft".to_string(), "bedrooms".to_string()];
+    let houses = arr2(&[
         [1800.0, 3.0], // Medium house
         [2500.0, 4.0], // Large house
         [1000.0, 2.0], // Small house
     ]);
+    let x_test = data::select_columns(&test_headers, &houses, &names)?;
 
     // Normalize test data using training means and stds
-    let x_test_norm = normalize_new_data(&x_test, &means, &stds);
+    let x_test_norm = scaler.transform(&x_test)?;
 
     println!("\nMaking predictions...");
     let predictions = model.predict(&x_test_norm)?;
 
     println!("\nPredicted prices:");
-    for (_i, (&pred, house)) in predictions.iter().zip(x_test.rows()).enumerate() {
-        println!("{:.0} sqft, {} bed house: ${:.2}k", 
-                house[0], 
-                house[1], 
+    for (&pred, house) in predictions.iter().zip(houses.rows()) {
+        println!("{:.0} sqft, {} bed house: ${:.2}k",
+                house[0],
+                house[1],
                 pred);
     }
 
     // Calculate and print R-squared for training data
-    let train_predictions = model.predict(&x_train_norm)?;
-    let r_squared = model.r_squared(&train_predictions, &y_train);
+    let r_squared = model.score(&x_train_norm, &y_train)?;
     println!("\nModel R-squared: {:.4}", r_squared);
 
     // Print feature importance (normalized coefficients)
     println!("\nFeature importance (normalized coefficients):");
-    println!("Square footage: {:.4}", model.weights[0]);
-    println!("Bedrooms: {:.4}", model.weights[1]);
+    for (name, weight) in model.coefficients() {
+        println!("{}: {:.4}", name, weight);
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,401 @@
+use crate::optim::{Adam, Optimizer, Sgd};
+use crate::traits::{Fit, Predictor, Score};
+use crate::random::{RandomState, Seeded};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, Axis};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activation {
+    Relu,
+    Tanh,
+}
+
+impl Activation {
+    fn apply(&self, z: &mut Array2<f64>) {
+        match self {
+            Self::Relu => z.mapv_inplace(|v| v.max(0.0)),
+            Self::Tanh => z.mapv_inplace(f64::tanh),
+        }
+    }
+
+    // Derivative expressed through the activation's output
+    fn derivative(&self, a: f64) -> f64 {
+        match self {
+            Self::Relu => if a > 0.0 { 1.0 } else { 0.0 },
+            Self::Tanh => 1.0 - a * a,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Solver {
+    Sgd { learning_rate: f64, momentum: f64 },
+    Adam { learning_rate: f64 },
+}
+
+impl Solver {
+    fn optimizer(&self) -> Box<dyn Optimizer> {
+        match *self {
+            Self::Sgd { learning_rate, momentum } => Box::new(Sgd::with_momentum(learning_rate, momentum)),
+            Self::Adam { learning_rate } => Box::new(Adam::new(learning_rate)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Layer {
+    // Inputs × outputs
+    weights: Array2<f64>,
+    bias: Array1<f64>,
+}
+
+// Fully connected network and its training settings, shared by the regressor
+// (identity output, squared error) and the classifier (softmax output, cross
+// entropy). The output gradient is the same `output - target` in both cases.
+#[derive(Debug, Clone)]
+struct Network {
+    hidden_layers: Vec<usize>,
+    activation: Activation,
+    solver: Solver,
+    batch_size: usize,
+    epochs: usize,
+    seed: u64,
+    layers: Vec<Layer>,
+    loss_curve: Vec<f64>,
+}
+
+impl Network {
+    fn new(hidden_layers: Vec<usize>) -> Self {
+        Self {
+            hidden_layers,
+            activation: Activation::Relu,
+            solver: Solver::Adam { learning_rate: 0.001 },
+            batch_size: 32,
+            epochs: 200,
+            seed: 0,
+            layers: Vec::new(),
+            loss_curve: Vec::new(),
+        }
+    }
+
+    fn check(&self, x: &Array2<f64>, n_targets: usize) -> Result<(), LinearRegressionError> {
+        if x.nrows() != n_targets {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: n_targets,
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.batch_size == 0 {
+            return Err(LinearRegressionError::InvalidParameter("batch_size must be at least 1"));
+        }
+        if self.hidden_layers.contains(&0) {
+            return Err(LinearRegressionError::InvalidParameter("hidden layers must have at least one unit"));
+        }
+        Ok(())
+    }
+
+    // Minibatch training on `targets` (one column per output). `softmax`
+    // selects the classifier's output layer and loss.
+    fn train(&mut self, x: &Array2<f64>, targets: &Array2<f64>, softmax: bool) -> Result<(), LinearRegressionError> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        // Glorot-uniform initialization
+        let mut sizes = vec![x.ncols()];
+        sizes.extend(&self.hidden_layers);
+        sizes.push(targets.ncols());
+        self.layers = sizes
+            .windows(2)
+            .map(|pair| {
+                let limit = (6.0 / (pair[0] + pair[1]) as f64).sqrt();
+                Layer {
+                    weights: Array2::from_shape_simple_fn((pair[0], pair[1]), || rng.random_range(-limit..limit)),
+                    bias: Array1::zeros(pair[1]),
+                }
+            })
+            .collect();
+
+        let mut optimizer = self.solver.optimizer();
+        let mut order: Vec<usize> = (0..x.nrows()).collect();
+        self.loss_curve.clear();
+
+        for _ in 0..self.epochs {
+            order.shuffle(&mut rng);
+            let mut epoch_loss = 0.0;
+            for batch in order.chunks(self.batch_size) {
+                let x_batch = x.select(Axis(0), batch);
+                let t_batch = targets.select(Axis(0), batch);
+                let activations = self.forward(&x_batch, softmax);
+                let output = activations.last().unwrap();
+
+                epoch_loss += if softmax {
+                    -(&t_batch * &output.mapv(|p| p.max(1e-15).ln())).sum()
+                } else {
+                    0.5 * (output - &t_batch).mapv(|e| e * e).sum()
+                };
+
+                let mut delta = (output - &t_batch) / batch.len() as f64;
+                for l in (0..self.layers.len()).rev() {
+                    // X'δ comes out column-major; the optimizer wants it in the weights' layout
+                    let weight_grad = activations[l].t().dot(&delta).as_standard_layout().into_owned();
+                    let bias_grad = delta.sum_axis(Axis(0));
+                    if l > 0 {
+                        let mut previous = delta.dot(&self.layers[l].weights.t());
+                        previous.zip_mut_with(&activations[l], |d, &a| *d *= self.activation.derivative(a));
+                        delta = previous;
+                    }
+
+                    let layer = &mut self.layers[l];
+                    optimizer.step(2 * l, layer.weights.as_slice_mut().unwrap(), weight_grad.as_slice().unwrap());
+                    optimizer.step(2 * l + 1, layer.bias.as_slice_mut().unwrap(), bias_grad.as_slice().unwrap());
+                }
+            }
+
+            let loss = epoch_loss / x.nrows() as f64;
+            if !loss.is_finite() {
+                return Err(LinearRegressionError::NumericalError("Loss became non-finite during training"));
+            }
+            self.loss_curve.push(loss);
+        }
+        Ok(())
+    }
+
+    // Outputs of every layer, the input first and the network output last
+    fn forward(&self, x: &Array2<f64>, softmax: bool) -> Vec<Array2<f64>> {
+        let mut activations = vec![x.clone()];
+        for (l, layer) in self.layers.iter().enumerate() {
+            let mut z = activations[l].dot(&layer.weights) + &layer.bias;
+            if l + 1 < self.layers.len() {
+                self.activation.apply(&mut z);
+            } else if softmax {
+                for mut row in z.rows_mut() {
+                    let max = row.fold(f64::NEG_INFINITY, |m, &v| m.max(v));
+                    row.mapv_inplace(|v| (v - max).exp());
+                    let total = row.sum();
+                    row /= total;
+                }
+            }
+            activations.push(z);
+        }
+        activations
+    }
+
+    fn output(&self, x: &Array2<f64>, softmax: bool) -> Result<Array2<f64>, LinearRegressionError> {
+        let first = self.layers.first().ok_or(LinearRegressionError::NotFitted)?;
+        if x.ncols() != first.weights.nrows() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: first.weights.nrows(),
+                found: x.ncols(),
+                context: "number of features in prediction",
+            });
+        }
+        Ok(self.forward(x, softmax).pop().unwrap())
+    }
+}
+
+macro_rules! network_settings {
+    () => {
+        pub fn with_activation(mut self, activation: Activation) -> Self {
+            self.inner.activation = activation;
+            self
+        }
+
+        // Adam with learning rate 0.001 by default
+        pub fn with_solver(mut self, solver: Solver) -> Self {
+            self.inner.solver = solver;
+            self
+        }
+
+        pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+            self.inner.batch_size = batch_size;
+            self
+        }
+
+        pub fn with_epochs(mut self, epochs: usize) -> Self {
+            self.inner.epochs = epochs;
+            self
+        }
+
+        pub fn with_seed(mut self, seed: u64) -> Self {
+            self.inner.seed = seed;
+            self
+        }
+
+        // Mean training loss per epoch
+        pub fn loss_curve(&self) -> &[f64] {
+            &self.inner.loss_curve
+        }
+    };
+}
+
+#[derive(Debug, Clone)]
+pub struct MlpRegressor {
+    inner: Network,
+}
+
+impl MlpRegressor {
+    // `hidden_layers` lists the number of units in each hidden layer
+    pub fn new(hidden_layers: Vec<usize>) -> Self {
+        Self { inner: Network::new(hidden_layers) }
+    }
+
+    network_settings!();
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        self.inner.check(x, y.len())?;
+        let targets = y.clone().insert_axis(Axis(1));
+        self.inner.train(x, &targets, false)
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        Ok(self.inner.output(x, false)?.column(0).to_owned())
+    }
+}
+
+impl Seeded for MlpRegressor {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.inner.seed = state.seed();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MlpClassifier {
+    inner: Network,
+    classes: Vec<f64>,
+}
+
+impl MlpClassifier {
+    pub fn new(hidden_layers: Vec<usize>) -> Self {
+        Self {
+            inner: Network::new(hidden_layers),
+            classes: Vec::new(),
+        }
+    }
+
+    network_settings!();
+
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        self.inner.check(x, y.len())?;
+        let mut classes = y.to_vec();
+        classes.sort_by(f64::total_cmp);
+        classes.dedup();
+
+        let mut one_hot = Array2::zeros((y.len(), classes.len()));
+        for (i, label) in y.iter().enumerate() {
+            one_hot[[i, classes.binary_search_by(|c| c.total_cmp(label)).unwrap()]] = 1.0;
+        }
+        self.classes = classes;
+        self.inner.train(x, &one_hot, true)
+    }
+
+    // Class probabilities, one column per class in `classes()` order
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        self.inner.output(x, true)
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let proba = self.predict_proba(x)?;
+        Ok(proba
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let best = (1..row.len()).fold(0, |best, c| if row[c] > row[best] { c } else { best });
+                self.classes[best]
+            })
+            .collect())
+    }
+}
+
+impl Seeded for MlpClassifier {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.inner.seed = state.seed();
+    }
+}
+
+impl Fit for MlpRegressor {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        MlpRegressor::fit(self, x, y)
+    }
+}
+
+impl Predictor for MlpRegressor {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        MlpRegressor::predict(self, x)
+    }
+}
+
+impl Score for MlpRegressor {}
+
+impl Fit for MlpClassifier {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        MlpClassifier::fit(self, x, y)
+    }
+}
+
+impl Predictor for MlpClassifier {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        MlpClassifier::predict(self, x)
+    }
+}
+
+impl Score for MlpClassifier {
+    fn is_classifier(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::r2_score;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_mlp_regressor() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((64, 1), |(i, _)| i as f64 / 32.0 - 1.0);
+        let y = x.column(0).mapv(|v| (3.0 * v).sin());
+
+        let mut model = MlpRegressor::new(vec![16, 16])
+            .with_activation(Activation::Tanh)
+            .with_solver(Solver::Adam { learning_rate: 0.01 })
+            .with_batch_size(16)
+            .with_epochs(500)
+            .with_seed(2);
+        model.fit(&x, &y)?;
+        assert!(r2_score(&model.predict(&x)?, &y) > 0.95);
+        let curve = model.loss_curve();
+        assert!(curve[curve.len() - 1] < curve[0] * 0.1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mlp_classifier() -> Result<(), LinearRegressionError> {
+        // XOR is not linearly separable
+        let x = arr2(&[[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]]);
+        let y = Array1::from(vec![0.0, 1.0, 1.0, 0.0]);
+
+        let mut model = MlpClassifier::new(vec![8])
+            .with_solver(Solver::Sgd { learning_rate: 0.5, momentum: 0.9 })
+            .with_activation(Activation::Tanh)
+            .with_batch_size(4)
+            .with_epochs(500)
+            .with_seed(1);
+        model.fit(&x, &y)?;
+        assert_eq!(model.predict(&x)?, y);
+
+        let proba = model.predict_proba(&x)?;
+        assert!(proba.rows().into_iter().all(|row| (row.sum() - 1.0).abs() < 1e-12));
+        assert!(MlpClassifier::new(vec![0]).fit(&x, &y).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,1060 @@
+use crate::traits::{Fit, ParamMap, Params, Predictor, Score, WarmStart};
+use crate::random::{RandomState, Seeded};
+use crate::{try_map, LinearRegressionError};
+use ndarray::{Array1, Array2, Axis};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+pub use ml_core::metrics::Metric;
+pub use ml_core::split::{k_fold_indices, Fold, KFold};
+
+#[derive(Debug, Clone)]
+pub struct ComparisonRow {
+    pub name: String,
+    // One score per metric, in the order the metrics were given
+    pub scores: Vec<f64>,
+}
+
+// Models evaluated on the same data, best first by the first metric
+#[derive(Debug, Clone)]
+pub struct ModelComparison {
+    pub metrics: Vec<Metric>,
+    pub rows: Vec<ComparisonRow>,
+}
+
+impl ModelComparison {
+    pub fn best(&self) -> &ComparisonRow {
+        &self.rows[0]
+    }
+}
+
+// Score each fitted model on (x, y) with every metric, then rank by the
+// first metric; ties keep the order the models were given in
+pub fn compare_models(
+    models: &[(&str, &dyn Predictor)],
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+    metrics: &[Metric],
+) -> Result<ModelComparison, LinearRegressionError> {
+    if x.nrows() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.nrows(),
+            found: y.len(),
+            context: "number of samples in X and y",
+        });
+    }
+    if models.is_empty() || y.is_empty() {
+        return Err(LinearRegressionError::EmptyData);
+    }
+    if metrics.is_empty() {
+        return Err(LinearRegressionError::InvalidParameter("no metrics to compare models by"));
+    }
+
+    let mut rows = Vec::with_capacity(models.len());
+    for (name, model) in models {
+        let predictions = model.predict(x)?;
+        rows.push(ComparisonRow {
+            name: name.to_string(),
+            scores: metrics.iter().map(|m| m.evaluate(&predictions, y)).collect(),
+        });
+    }
+    // NaN scores rank last whichever way the metric points
+    let primary = metrics[0];
+    rows.sort_by(|a, b| {
+        let (a, b) = (a.scores[0], b.scores[0]);
+        match (a.is_nan(), b.is_nan()) {
+            (false, false) if primary.higher_is_better() => b.total_cmp(&a),
+            (false, false) => a.total_cmp(&b),
+            (a_nan, b_nan) => a_nan.cmp(&b_nan),
+        }
+    });
+    Ok(ModelComparison { metrics: metrics.to_vec(), rows })
+}
+
+impl fmt::Display for ModelComparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name_width = self.rows.iter().map(|row| row.name.chars().count()).max().unwrap_or(0).max(5);
+        write!(f, "{:<4} {:<name_width$}", "Rank", "Model")?;
+        for metric in &self.metrics {
+            write!(f, " {:>12}", metric.name())?;
+        }
+        writeln!(f)?;
+        for (rank, row) in self.rows.iter().enumerate() {
+            write!(f, "{:<4} {:<name_width$}", rank + 1, row.name)?;
+            for score in &row.scores {
+                write!(f, " {:>12.4}", score)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// `metric` on the model's predictions, or `Score::score` without one
+fn evaluate<E: Score>(
+    model: &E,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+    metric: Option<Metric>,
+) -> Result<f64, LinearRegressionError> {
+    match metric {
+        Some(metric) => Ok(metric.evaluate(&model.predict(x)?, y)),
+        None => model.score(x, y),
+    }
+}
+
+// Held-out score of a fresh copy of `estimator` on each fold
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(n_samples = x.nrows(), n_folds = n_folds)
+))]
+pub fn cross_val_score<E: Fit + Score + Clone + Send + Sync>(
+    estimator: &E,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+    n_folds: usize,
+    seed: u64,
+    metric: Option<Metric>,
+) -> Result<Vec<f64>, LinearRegressionError> {
+    if x.nrows() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.nrows(),
+            found: y.len(),
+            context: "number of samples in X and y",
+        });
+    }
+    try_map(k_fold_indices(x.nrows(), n_folds, seed)?, |(train, held_out)| {
+        let mut model = estimator.clone();
+        model.fit(&x.select(Axis(0), &train), &y.select(Axis(0), &train))?;
+        let score = evaluate(&model, &x.select(Axis(0), &held_out), &y.select(Axis(0), &held_out), metric)?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(n_train = train.len(), n_held_out = held_out.len(), score, "fold scored");
+        Ok(score)
+    })
+}
+
+// Candidate values per hyperparameter, by the names `Params` uses
+pub type ParamGrid = BTreeMap<String, Vec<Value>>;
+
+// Every combination of the grid's values, the last name varying fastest. An
+// empty grid has a single, empty combination.
+pub fn grid_combinations(grid: &ParamGrid) -> Result<Vec<ParamMap>, LinearRegressionError> {
+    let mut combinations = vec![ParamMap::new()];
+    for (name, values) in grid {
+        if values.is_empty() {
+            return Err(LinearRegressionError::Parameter {
+                name: name.clone(),
+                message: "no candidate values in the grid".to_string(),
+            });
+        }
+        combinations = combinations
+            .into_iter()
+            .flat_map(|params| {
+                values.iter().map(move |value| {
+                    let mut params = params.clone();
+                    params.insert(name.clone(), value.clone());
+                    params
+                })
+            })
+            .collect();
+    }
+    Ok(combinations)
+}
+
+// Cross-validated scores for one candidate
+#[derive(Debug, Clone)]
+pub struct CvResult {
+    pub params: ParamMap,
+    pub fold_scores: Vec<f64>,
+    pub mean_score: f64,
+    pub std_score: f64,
+}
+
+impl CvResult {
+    pub(crate) fn new(params: ParamMap, fold_scores: Vec<f64>) -> Self {
+        let (mean_score, std_score) = mean_std(&fold_scores);
+        Self { params, fold_scores, mean_score, std_score }
+    }
+}
+
+// Mean and population standard deviation of per-fold scores
+fn mean_std(scores: &[f64]) -> (f64, f64) {
+    let n = scores.len() as f64;
+    let mean = scores.iter().sum::<f64>() / n;
+    (mean, (scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n).sqrt())
+}
+
+// Index of the best mean score; NaN never wins and the earliest wins ties
+pub(crate) fn best_result(results: &[CvResult], higher_is_better: bool) -> Option<usize> {
+    let better = |a: f64, b: f64| if higher_is_better { a > b } else { a < b };
+    (0..results.len()).filter(|&i| !results[i].mean_score.is_nan()).fold(None, |best, i| match best {
+        Some(b) if !better(results[i].mean_score, results[b].mean_score) => Some(b),
+        _ => Some(i),
+    })
+}
+
+// Cross-validate each candidate on the same folds, then refit the best one
+// on all the data. Returns every result along with the best index and model.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(n_candidates = candidates.len(), n_folds = n_folds)
+))]
+fn search<E: Fit + Score + Params + Clone + Send + Sync>(
+    estimator: &E,
+    candidates: Vec<ParamMap>,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+    n_folds: usize,
+    seed: u64,
+    metric: Option<Metric>,
+) -> Result<(Vec<CvResult>, (usize, E)), LinearRegressionError> {
+    let results = try_map(candidates, |params| {
+        let mut candidate = estimator.clone();
+        candidate.set_params(&params)?;
+        let fold_scores = cross_val_score(&candidate, x, y, n_folds, seed, metric)?;
+        let result = CvResult::new(params, fold_scores);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(params = ?result.params, mean_score = result.mean_score, "candidate scored");
+        Ok(result)
+    })?;
+    let index = best_result(&results, metric.is_none_or(|m| m.higher_is_better()))
+        .ok_or(LinearRegressionError::NumericalError("every candidate scored NaN"))?;
+
+    let mut best = estimator.clone();
+    best.set_params(&results[index].params)?;
+    best.fit(x, y)?;
+    Ok((results, (index, best)))
+}
+
+// Exhaustive search over a parameter grid: every combination is scored by
+// K-fold cross-validation on the same folds, and the best one is refitted on
+// all the data. Scores are `Score::score` (R² or accuracy) unless a metric is
+// set; error metrics such as MSE pick the lowest mean.
+#[derive(Debug, Clone)]
+pub struct GridSearchCV<E> {
+    estimator: E,
+    grid: ParamGrid,
+    n_folds: usize,
+    seed: u64,
+    metric: Option<Metric>,
+    results: Vec<CvResult>,
+    best: Option<(usize, E)>,
+}
+
+impl<E: Fit + Score + Params + Clone + Send + Sync> GridSearchCV<E> {
+    // `estimator` supplies every hyperparameter the grid does not mention
+    pub fn new(estimator: E, grid: ParamGrid) -> Self {
+        Self {
+            estimator,
+            grid,
+            n_folds: 5,
+            seed: 0,
+            metric: None,
+            results: Vec::new(),
+            best: None,
+        }
+    }
+
+    pub fn with_n_folds(mut self, n_folds: usize) -> Self {
+        self.n_folds = n_folds;
+        self
+    }
+
+    // Seed for shuffling rows into folds
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_metric(mut self, metric: Metric) -> Self {
+        self.metric = Some(metric);
+        self
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        let candidates = grid_combinations(&self.grid)?;
+        let (results, best) = search(&self.estimator, candidates, x, y, self.n_folds, self.seed, self.metric)?;
+        self.results = results;
+        self.best = Some(best);
+        Ok(())
+    }
+
+    // One entry per combination, in grid order
+    pub fn results(&self) -> &[CvResult] {
+        &self.results
+    }
+
+    pub fn best_params(&self) -> Option<&ParamMap> {
+        self.best.as_ref().map(|(index, _)| &self.results[*index].params)
+    }
+
+    pub fn best_score(&self) -> Option<f64> {
+        self.best.as_ref().map(|(index, _)| self.results[*index].mean_score)
+    }
+
+    // The best combination refitted on all the training data
+    pub fn best_estimator(&self) -> Option<&E> {
+        self.best.as_ref().map(|(_, model)| model)
+    }
+}
+
+impl<E> Seeded for GridSearchCV<E> {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+impl<E: Fit + Score + Params + Clone + Send + Sync> Fit for GridSearchCV<E> {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        GridSearchCV::fit(self, x, y)
+    }
+}
+
+impl<E: Predictor> Predictor for GridSearchCV<E> {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        match &self.best {
+            Some((_, model)) => model.predict(x),
+            None => Err(LinearRegressionError::NotFitted),
+        }
+    }
+}
+
+impl<E: Score> Score for GridSearchCV<E> {
+    fn is_classifier(&self) -> bool {
+        self.estimator.is_classifier()
+    }
+}
+
+// Where `RandomSearch` draws one hyperparameter from
+#[derive(Debug, Clone, PartialEq)]
+pub enum Distribution {
+    // Uniform on [low, high)
+    Uniform { low: f64, high: f64 },
+    // Uniform in log space, for scale parameters such as learning rates
+    LogUniform { low: f64, high: f64 },
+    // Integers in [low, high], both included
+    IntRange { low: i64, high: i64 },
+    // One of the listed values, with equal chance
+    Choice(Vec<Value>),
+}
+
+impl Distribution {
+    fn validate(&self, name: &str) -> Result<(), LinearRegressionError> {
+        let invalid = |message: &str| {
+            Err(LinearRegressionError::Parameter { name: name.to_string(), message: message.to_string() })
+        };
+        match self {
+            Self::Uniform { low, high } if !(low.is_finite() && high.is_finite() && low < high) => {
+                invalid("uniform bounds must be finite with low < high")
+            }
+            Self::LogUniform { low, high } if !(*low > 0.0 && high.is_finite() && low < high) => {
+                invalid("log-uniform bounds must be positive and finite with low < high")
+            }
+            Self::IntRange { low, high } if low > high => invalid("integer range needs low <= high"),
+            Self::Choice(values) if values.is_empty() => invalid("no values to choose from"),
+            _ => Ok(()),
+        }
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> Value {
+        match self {
+            Self::Uniform { low, high } => Value::from(rng.random_range(*low..*high)),
+            Self::LogUniform { low, high } => Value::from(rng.random_range(low.ln()..high.ln()).exp()),
+            Self::IntRange { low, high } => Value::from(rng.random_range(*low..=*high)),
+            Self::Choice(values) => values[rng.random_range(0..values.len())].clone(),
+        }
+    }
+}
+
+pub type ParamDistributions = BTreeMap<String, Distribution>;
+
+// `n` independent draws of every parameter; the same seed gives the same draws
+pub fn sample_candidates(
+    distributions: &ParamDistributions,
+    n: usize,
+    seed: u64,
+) -> Result<Vec<ParamMap>, LinearRegressionError> {
+    if n == 0 {
+        return Err(LinearRegressionError::InvalidParameter("need at least one candidate"));
+    }
+    for (name, distribution) in distributions {
+        distribution.validate(name)?;
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let candidates = (0..n)
+        .map(|_| distributions.iter().map(|(name, d)| (name.clone(), d.sample(&mut rng))).collect())
+        .collect();
+    Ok(candidates)
+}
+
+// Like `GridSearchCV`, but scores `n_trials` random draws from the given
+// distributions instead of every combination, which keeps wide search spaces
+// affordable. The same seed draws the same candidates and folds.
+#[derive(Debug, Clone)]
+pub struct RandomSearch<E> {
+    estimator: E,
+    distributions: ParamDistributions,
+    n_trials: usize,
+    n_folds: usize,
+    seed: u64,
+    metric: Option<Metric>,
+    results: Vec<CvResult>,
+    best: Option<(usize, E)>,
+}
+
+impl<E: Fit + Score + Params + Clone + Send + Sync> RandomSearch<E> {
+    pub fn new(estimator: E, distributions: ParamDistributions) -> Self {
+        Self {
+            estimator,
+            distributions,
+            n_trials: 10,
+            n_folds: 5,
+            seed: 0,
+            metric: None,
+            results: Vec::new(),
+            best: None,
+        }
+    }
+
+    pub fn with_n_trials(mut self, n_trials: usize) -> Self {
+        self.n_trials = n_trials;
+        self
+    }
+
+    pub fn with_n_folds(mut self, n_folds: usize) -> Self {
+        self.n_folds = n_folds;
+        self
+    }
+
+    // Seed for drawing candidates and shuffling rows into folds
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_metric(mut self, metric: Metric) -> Self {
+        self.metric = Some(metric);
+        self
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        let candidates = sample_candidates(&self.distributions, self.n_trials, self.seed)?;
+        let (results, best) = search(&self.estimator, candidates, x, y, self.n_folds, self.seed, self.metric)?;
+        self.results = results;
+        self.best = Some(best);
+        Ok(())
+    }
+
+    // One entry per trial, in the order they were drawn
+    pub fn results(&self) -> &[CvResult] {
+        &self.results
+    }
+
+    pub fn best_params(&self) -> Option<&ParamMap> {
+        self.best.as_ref().map(|(index, _)| &self.results[*index].params)
+    }
+
+    pub fn best_score(&self) -> Option<f64> {
+        self.best.as_ref().map(|(index, _)| self.results[*index].mean_score)
+    }
+
+    // The best trial refitted on all the training data
+    pub fn best_estimator(&self) -> Option<&E> {
+        self.best.as_ref().map(|(_, model)| model)
+    }
+}
+
+impl<E> Seeded for RandomSearch<E> {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+impl<E: Fit + Score + Params + Clone + Send + Sync> Fit for RandomSearch<E> {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        RandomSearch::fit(self, x, y)
+    }
+}
+
+impl<E: Predictor> Predictor for RandomSearch<E> {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        match &self.best {
+            Some((_, model)) => model.predict(x),
+            None => Err(LinearRegressionError::NotFitted),
+        }
+    }
+}
+
+impl<E: Score> Score for RandomSearch<E> {
+    fn is_classifier(&self) -> bool {
+        self.estimator.is_classifier()
+    }
+}
+
+// Validation scores of the candidates still in the race after one round
+#[derive(Debug, Clone)]
+pub struct HalvingRound {
+    // Total epochs each candidate has trained for by the end of the round
+    pub epochs: usize,
+    // (candidate index, validation score), best first
+    pub scores: Vec<(usize, f64)>,
+}
+
+// Successive halving (Jamieson & Talwalkar 2016): every candidate trains for
+// a few epochs, only the best `1 / factor` of them keep going, and their
+// budget grows by `factor` each round until one is left. Training continues
+// from where the previous round stopped, so the survivors never start over.
+// Candidates are scored on a held-out split of the training data and the
+// winner is refitted on all of it for the epochs it reached.
+#[derive(Debug, Clone)]
+pub struct SuccessiveHalving<E> {
+    estimator: E,
+    candidates: Vec<ParamMap>,
+    min_epochs: usize,
+    factor: usize,
+    validation_fraction: f64,
+    seed: u64,
+    metric: Option<Metric>,
+    rounds: Vec<HalvingRound>,
+    best: Option<(usize, E)>,
+}
+
+impl<E: WarmStart + Score + Params + Clone + Send + Sync> SuccessiveHalving<E> {
+    // Candidates can come from `grid_combinations` or `sample_candidates`
+    pub fn new(estimator: E, candidates: Vec<ParamMap>) -> Self {
+        Self {
+            estimator,
+            candidates,
+            min_epochs: 10,
+            factor: 3,
+            validation_fraction: 0.25,
+            seed: 0,
+            metric: None,
+            rounds: Vec::new(),
+            best: None,
+        }
+    }
+
+    // Epochs every candidate gets in the first round
+    pub fn with_min_epochs(mut self, min_epochs: usize) -> Self {
+        self.min_epochs = min_epochs;
+        self
+    }
+
+    // Share of candidates dropped and budget growth per round
+    pub fn with_factor(mut self, factor: usize) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    pub fn with_validation_fraction(mut self, validation_fraction: f64) -> Self {
+        self.validation_fraction = validation_fraction;
+        self
+    }
+
+    // Seed for the validation split
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_metric(mut self, metric: Metric) -> Self {
+        self.metric = Some(metric);
+        self
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(n_candidates = self.candidates.len())
+    ))]
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if self.candidates.is_empty() {
+            return Err(LinearRegressionError::InvalidParameter("need at least one candidate"));
+        }
+        if self.min_epochs == 0 || self.factor < 2 {
+            return Err(LinearRegressionError::InvalidParameter("need min_epochs >= 1 and factor >= 2"));
+        }
+        let n_validation = (x.nrows() as f64 * self.validation_fraction).round() as usize;
+        let in_unit = self.validation_fraction > 0.0 && self.validation_fraction < 1.0;
+        if !in_unit || n_validation == 0 || n_validation == x.nrows() {
+            return Err(LinearRegressionError::InvalidParameter("validation split leaves no rows on one side"));
+        }
+
+        let mut order: Vec<usize> = (0..x.nrows()).collect();
+        order.shuffle(&mut StdRng::seed_from_u64(self.seed));
+        let (validation, train) = order.split_at(n_validation);
+        let (x_train, y_train) = (x.select(Axis(0), train), y.select(Axis(0), train));
+        let (x_val, y_val) = (x.select(Axis(0), validation), y.select(Axis(0), validation));
+
+        let mut alive = Vec::with_capacity(self.candidates.len());
+        for (i, params) in self.candidates.iter().enumerate() {
+            let mut model = self.estimator.clone();
+            model.set_params(params)?;
+            alive.push((i, model));
+        }
+        let higher_is_better = self.metric.is_none_or(|m| m.higher_is_better());
+        let (mut trained, mut budget) = (0, self.min_epochs);
+        let mut rounds = Vec::new();
+        loop {
+            let mut scored = try_map(alive, |(i, mut model)| {
+                model.fit_more(&x_train, &y_train, budget - trained)?;
+                Ok((evaluate(&model, &x_val, &y_val, self.metric)?, i, model))
+            })?;
+            // NaN scores rank last whichever way the metric points
+            scored.sort_by(|a, b| match (a.0.is_nan(), b.0.is_nan()) {
+                (false, false) if higher_is_better => b.0.total_cmp(&a.0),
+                (false, false) => a.0.total_cmp(&b.0),
+                (a_nan, b_nan) => a_nan.cmp(&b_nan),
+            });
+            #[cfg(feature = "tracing")]
+            tracing::debug!(epochs = budget, n_alive = scored.len(), best_score = scored[0].0, "halving round");
+            rounds.push(HalvingRound { epochs: budget, scores: scored.iter().map(|(s, i, _)| (*i, *s)).collect() });
+            if scored.len() == 1 {
+                break;
+            }
+            scored.truncate(scored.len().div_ceil(self.factor));
+            alive = scored.into_iter().map(|(_, i, model)| (i, model)).collect();
+            trained = budget;
+            budget *= self.factor;
+        }
+
+        let winner = rounds.last().unwrap().scores[0].0;
+        let mut best = self.estimator.clone();
+        best.set_params(&self.candidates[winner])?;
+        best.fit_more(x, y, budget)?;
+        self.rounds = rounds;
+        self.best = Some((winner, best));
+        Ok(())
+    }
+
+    pub fn rounds(&self) -> &[HalvingRound] {
+        &self.rounds
+    }
+
+    pub fn best_params(&self) -> Option<&ParamMap> {
+        self.best.as_ref().map(|(index, _)| &self.candidates[*index])
+    }
+
+    // Validation score of the winner in the last round
+    pub fn best_score(&self) -> Option<f64> {
+        self.rounds.last().map(|round| round.scores[0].1)
+    }
+
+    // The winner refitted on all the training data
+    pub fn best_estimator(&self) -> Option<&E> {
+        self.best.as_ref().map(|(_, model)| model)
+    }
+}
+
+impl<E> Seeded for SuccessiveHalving<E> {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+impl<E: WarmStart + Score + Params + Clone + Send + Sync> Fit for SuccessiveHalving<E> {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        SuccessiveHalving::fit(self, x, y)
+    }
+}
+
+impl<E: Predictor> Predictor for SuccessiveHalving<E> {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        match &self.best {
+            Some((_, model)) => model.predict(x),
+            None => Err(LinearRegressionError::NotFitted),
+        }
+    }
+}
+
+impl<E: Score> Score for SuccessiveHalving<E> {
+    fn is_classifier(&self) -> bool {
+        self.estimator.is_classifier()
+    }
+}
+
+// Searches that pick hyperparameters during `fit`, so they can be evaluated
+// like any other model by an outer resampling loop
+pub trait Tuner: Fit + Score + Clone + Send + Sync {
+    fn best_params(&self) -> Option<&ParamMap>;
+}
+
+impl<E: Fit + Score + Params + Clone + Send + Sync> Tuner for GridSearchCV<E> {
+    fn best_params(&self) -> Option<&ParamMap> {
+        GridSearchCV::best_params(self)
+    }
+}
+
+impl<E: Fit + Score + Params + Clone + Send + Sync> Tuner for RandomSearch<E> {
+    fn best_params(&self) -> Option<&ParamMap> {
+        RandomSearch::best_params(self)
+    }
+}
+
+impl<E: WarmStart + Score + Params + Clone + Send + Sync> Tuner for SuccessiveHalving<E> {
+    fn best_params(&self) -> Option<&ParamMap> {
+        SuccessiveHalving::best_params(self)
+    }
+}
+
+// Outer-fold estimate of a tuned model, with what the tuner chose each time
+#[derive(Debug, Clone)]
+pub struct NestedCvResult {
+    pub fold_scores: Vec<f64>,
+    pub mean_score: f64,
+    pub std_score: f64,
+    // The tuner's pick on each outer training split
+    pub fold_params: Vec<ParamMap>,
+}
+
+// Nested cross-validation: the tuner runs its own search on each outer
+// training split and its refitted pick is scored on the held-out fold, which
+// it never saw. The best score of a single search is optimistically biased
+// by the selection itself; these scores are not.
+pub fn nested_cv<T: Tuner>(
+    tuner: &T,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+    n_folds: usize,
+    seed: u64,
+    metric: Option<Metric>,
+) -> Result<NestedCvResult, LinearRegressionError> {
+    if x.nrows() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.nrows(),
+            found: y.len(),
+            context: "number of samples in X and y",
+        });
+    }
+    let folds = try_map(k_fold_indices(x.nrows(), n_folds, seed)?, |(train, held_out)| {
+        let mut model = tuner.clone();
+        model.fit(&x.select(Axis(0), &train), &y.select(Axis(0), &train))?;
+        let score = evaluate(&model, &x.select(Axis(0), &held_out), &y.select(Axis(0), &held_out), metric)?;
+        Ok((score, model.best_params().cloned().unwrap_or_default()))
+    })?;
+    let (fold_scores, fold_params): (Vec<f64>, Vec<ParamMap>) = folds.into_iter().unzip();
+    let (mean_score, std_score) = mean_std(&fold_scores);
+    Ok(NestedCvResult { fold_scores, mean_score, std_score, fold_params })
+}
+
+// Scores per training size, one column per fold
+#[derive(Debug, Clone)]
+pub struct LearningCurve {
+    // Rows trained on, as in the first fold; other folds differ by at most one
+    pub train_sizes: Vec<usize>,
+    pub train_scores: Array2<f64>,
+    pub validation_scores: Array2<f64>,
+}
+
+impl LearningCurve {
+    pub fn mean_train_scores(&self) -> Array1<f64> {
+        self.train_scores.mean_axis(Axis(1)).unwrap()
+    }
+
+    pub fn mean_validation_scores(&self) -> Array1<f64> {
+        self.validation_scores.mean_axis(Axis(1)).unwrap()
+    }
+}
+
+// Train and held-out scores of models fitted on growing subsets of each
+// fold's training rows, with `train_sizes` as fractions in (0, 1]. Scores
+// that stay apart as the size grows point to variance (more data helps);
+// low scores that meet early point to bias (more capacity helps).
+pub fn learning_curve<E, M>(
+    model_factory: M,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+    train_sizes: &[f64],
+    cv: KFold,
+    metric: Option<Metric>,
+) -> Result<LearningCurve, LinearRegressionError>
+where
+    E: Fit + Score,
+    M: Fn() -> E + Sync + Send,
+{
+    if x.nrows() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.nrows(),
+            found: y.len(),
+            context: "number of samples in X and y",
+        });
+    }
+    if train_sizes.is_empty() || train_sizes.iter().any(|&f| !(f > 0.0 && f <= 1.0)) {
+        return Err(LinearRegressionError::InvalidParameter("train sizes must be fractions in (0, 1]"));
+    }
+    let folds = cv.split(x.nrows())?;
+    let subset = |fold: &Fold, fraction: f64| ((fold.0.len() as f64 * fraction).round() as usize).max(1);
+
+    // One job per (size, fold), size-major, which is the layout of the score tables
+    let n_folds = folds.len();
+    let jobs: Vec<(usize, usize)> = (0..train_sizes.len()).flat_map(|s| (0..n_folds).map(move |f| (s, f))).collect();
+    let scores = try_map(jobs, |(s, f)| {
+        let (train, held_out) = &folds[f];
+        let rows = &train[..subset(&folds[f], train_sizes[s])];
+        let (x_train, y_train) = (x.select(Axis(0), rows), y.select(Axis(0), rows));
+        let mut model = model_factory();
+        model.fit(&x_train, &y_train)?;
+        let train_score = evaluate(&model, &x_train, &y_train, metric)?;
+        Ok((train_score, evaluate(&model, &x.select(Axis(0), held_out), &y.select(Axis(0), held_out), metric)?))
+    })?;
+
+    let shape = (train_sizes.len(), n_folds);
+    Ok(LearningCurve {
+        train_sizes: train_sizes.iter().map(|&fraction| subset(&folds[0], fraction)).collect(),
+        train_scores: Array2::from_shape_fn(shape, |(s, f)| scores[s * n_folds + f].0),
+        validation_scores: Array2::from_shape_fn(shape, |(s, f)| scores[s * n_folds + f].1),
+    })
+}
+
+// Scores per value of one hyperparameter, one column per fold
+#[derive(Debug, Clone)]
+pub struct ValidationCurve {
+    pub param: String,
+    pub values: Vec<Value>,
+    pub train_scores: Array2<f64>,
+    pub validation_scores: Array2<f64>,
+}
+
+impl ValidationCurve {
+    pub fn mean_train_scores(&self) -> Array1<f64> {
+        self.train_scores.mean_axis(Axis(1)).unwrap()
+    }
+
+    pub fn mean_validation_scores(&self) -> Array1<f64> {
+        self.validation_scores.mean_axis(Axis(1)).unwrap()
+    }
+}
+
+// Train and held-out scores as `param` takes each of `values`, every other
+// hyperparameter coming from `estimator`. Where the two curves part ways the
+// model starts to overfit; where both are poor it underfits.
+pub fn validation_curve<E: Fit + Score + Params + Clone + Send + Sync>(
+    estimator: &E,
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+    param: &str,
+    values: &[Value],
+    cv: KFold,
+    metric: Option<Metric>,
+) -> Result<ValidationCurve, LinearRegressionError> {
+    if x.nrows() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.nrows(),
+            found: y.len(),
+            context: "number of samples in X and y",
+        });
+    }
+    if values.is_empty() {
+        return Err(LinearRegressionError::Parameter {
+            name: param.to_string(),
+            message: "no values to sweep".to_string(),
+        });
+    }
+    let folds = cv.split(x.nrows())?;
+    // One model per value, checked before any fitting starts
+    let mut models = Vec::with_capacity(values.len());
+    for value in values {
+        let mut model = estimator.clone();
+        model.set_param(param, value)?;
+        models.push(model);
+    }
+
+    let n_folds = folds.len();
+    let jobs: Vec<(usize, usize)> = (0..values.len()).flat_map(|v| (0..n_folds).map(move |f| (v, f))).collect();
+    let scores = try_map(jobs, |(v, f)| {
+        let (train, held_out) = &folds[f];
+        let (x_train, y_train) = (x.select(Axis(0), train), y.select(Axis(0), train));
+        let mut model = models[v].clone();
+        model.fit(&x_train, &y_train)?;
+        let train_score = evaluate(&model, &x_train, &y_train, metric)?;
+        Ok((train_score, evaluate(&model, &x.select(Axis(0), held_out), &y.select(Axis(0), held_out), metric)?))
+    })?;
+
+    let shape = (values.len(), n_folds);
+    Ok(ValidationCurve {
+        param: param.to_string(),
+        values: values.to_vec(),
+        train_scores: Array2::from_shape_fn(shape, |(v, f)| scores[v * n_folds + f].0),
+        validation_scores: Array2::from_shape_fn(shape, |(v, f)| scores[v * n_folds + f].1),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::{Criterion, DecisionTree};
+    use crate::LinearRegression;
+
+    #[test]
+    fn test_compare_models() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((30, 1), |(i, _)| i as f64 / 10.0);
+        let y = x.column(0).mapv(|v| 2.0 * v + 1.0);
+        let exact = LinearRegression::from_parameters(Array1::from(vec![2.0]), 1.0);
+        let biased = LinearRegression::from_parameters(Array1::from(vec![2.0]), 1.5);
+        let mut stump = DecisionTree::new(Criterion::Mse).with_max_depth(1);
+        stump.fit(&x, &y)?;
+
+        let models: [(&str, &dyn Predictor); 3] = [("biased", &biased), ("stump", &stump), ("exact", &exact)];
+        let comparison = compare_models(&models, &x, &y, &[Metric::Mse, Metric::R2])?;
+        assert_eq!(comparison.best().name, "exact");
+        assert_eq!(comparison.best().scores, vec![0.0, 1.0]);
+        assert_eq!(comparison.rows[1].name, "biased");
+        assert!((comparison.rows[1].scores[0] - 0.25).abs() < 1e-12);
+
+        let table = comparison.to_string();
+        assert_eq!(table.lines().count(), 4);
+        assert!(table.lines().next().unwrap().contains("MSE") && table.contains("1    exact"));
+        assert!(compare_models(&models, &x, &y, &[]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_grid_search() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((40, 2), |(i, j)| ((i * (j + 3)) % 11) as f64 / 5.0);
+        let y = x.column(0).mapv(|v| 3.0 * v) + x.column(1).mapv(|v| -v) + 0.5;
+
+        let grid = ParamGrid::from([
+            ("learning_rate".to_string(), vec![Value::from(0.001), Value::from(0.1)]),
+            ("epochs".to_string(), vec![Value::from(50), Value::from(500)]),
+        ]);
+        let mut search = GridSearchCV::new(LinearRegression::new(2, 0.01), grid.clone())
+            .with_n_folds(4)
+            .with_metric(Metric::Mse);
+        assert!(search.predict(&x).is_err());
+        search.fit(&x, &y)?;
+
+        assert_eq!(search.results().len(), 4);
+        assert_eq!(search.results()[0].params["epochs"], Value::from(50));
+        assert_eq!(search.results()[0].fold_scores.len(), 4);
+        let best = search.best_params().unwrap();
+        assert_eq!((best["learning_rate"].as_f64(), best["epochs"].as_u64()), (Some(0.1), Some(500)));
+        assert!(search.results().iter().all(|r| r.mean_score >= search.best_score().unwrap()));
+        assert_eq!(search.best_estimator().unwrap().epochs(), 500);
+        assert!(search.score(&x, &y)? > 0.99);
+
+        let unknown = ParamGrid::from([("alpha".to_string(), vec![Value::from(1.0)])]);
+        assert!(GridSearchCV::new(LinearRegression::new(2, 0.01), unknown).fit(&x, &y).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_random_search() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((40, 1), |(i, _)| i as f64 / 20.0);
+        let y = x.column(0).mapv(|v| 2.0 * v - 1.0);
+        let distributions = ParamDistributions::from([
+            ("learning_rate".to_string(), Distribution::LogUniform { low: 1e-4, high: 0.5 }),
+            ("epochs".to_string(), Distribution::IntRange { low: 10, high: 400 }),
+        ]);
+        let search = RandomSearch::new(LinearRegression::new(1, 0.01), distributions).with_n_trials(8).with_n_folds(4);
+        let (mut first, mut second) = (search.clone(), search.clone().with_seed(1));
+        first.fit(&x, &y)?;
+        second.fit(&x, &y)?;
+
+        assert_eq!(first.results().len(), 8);
+        assert!(first.results().iter().all(|r| {
+            let (rate, epochs) = (r.params["learning_rate"].as_f64().unwrap(), r.params["epochs"].as_u64().unwrap());
+            (1e-4..0.5).contains(&rate) && (10..=400).contains(&epochs)
+        }));
+        assert!(first.results().iter().all(|r| r.mean_score <= first.best_score().unwrap()));
+        assert_ne!(first.results()[0].params, second.results()[0].params);
+        let mut repeated = search.clone();
+        repeated.fit(&x, &y)?;
+        assert_eq!(repeated.best_params(), first.best_params());
+
+        let zero = Distribution::LogUniform { low: 0.0, high: 1.0 };
+        let bad = ParamDistributions::from([("learning_rate".to_string(), zero)]);
+        assert!(RandomSearch::new(LinearRegression::new(1, 0.01), bad).fit(&x, &y).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_successive_halving() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((60, 1), |(i, _)| i as f64 / 30.0);
+        let y = x.column(0).mapv(|v| 1.5 * v + 0.5);
+        let grid = ParamGrid::from([(
+            "learning_rate".to_string(),
+            [1e-4, 1e-3, 3e-3, 0.01, 0.03, 0.1, 0.2, 0.3, 0.5].map(Value::from).to_vec(),
+        )]);
+        let mut tuner = SuccessiveHalving::new(LinearRegression::new(1, 0.01), grid_combinations(&grid)?)
+            .with_min_epochs(20)
+            .with_factor(3)
+            .with_metric(Metric::Mse);
+        tuner.fit(&x, &y)?;
+
+        // 9 candidates, then 3, then 1, each round with three times the epochs
+        let rounds = tuner.rounds();
+        let schedule: Vec<(usize, usize)> = rounds.iter().map(|r| (r.scores.len(), r.epochs)).collect();
+        assert_eq!(schedule, vec![(9, 20), (3, 60), (1, 180)]);
+        assert!(rounds[0].scores.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(tuner.best_params().unwrap()["learning_rate"].as_f64().unwrap() >= 0.1);
+        assert!(tuner.best_score().unwrap() < 1e-3);
+        assert!(tuner.score(&x, &y)? > 0.99);
+        assert!(tuner.clone().with_factor(1).fit(&x, &y).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_cv() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((48, 1), |(i, _)| ((i * 7) % 48) as f64 / 24.0);
+        let noise = Array1::from_shape_fn(48, |i| if i % 3 == 0 { 0.2 } else { -0.1 });
+        let y = x.column(0).mapv(|v| 3.0 * v - 2.0) + noise;
+        let grid = ParamGrid::from([("learning_rate".to_string(), vec![Value::from(1e-4), Value::from(0.1)])]);
+        let search = GridSearchCV::new(LinearRegression::new(1, 0.01).with_epochs(300), grid).with_n_folds(3);
+
+        let nested = nested_cv(&search, &x, &y, 4, 1, None)?;
+        assert_eq!((nested.fold_scores.len(), nested.fold_params.len()), (4, 4));
+        assert!(nested.fold_params.iter().all(|p| p["learning_rate"] == 0.1));
+        assert!(nested.mean_score > 0.9 && nested.std_score >= 0.0);
+        assert!(nested_cv(&search, &x, &y, 1, 1, None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_learning_curve() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((60, 1), |(i, _)| ((i * 13) % 60) as f64 / 10.0);
+        let y = x.column(0).mapv(|v| (v * 2.0).sin());
+        let factory = || DecisionTree::new(Criterion::Mse);
+        let curve = learning_curve(factory, &x, &y, &[0.1, 0.5, 1.0], KFold::new(5, 0), Some(Metric::Mse))?;
+
+        assert_eq!(curve.train_sizes, vec![5, 24, 48]);
+        assert_eq!(curve.train_scores.dim(), (3, 5));
+        // A full-depth tree memorizes its training rows, and held-out error
+        // falls as it sees more of them
+        assert!(curve.train_scores.iter().all(|&s| s < 1e-12));
+        let validation = curve.mean_validation_scores();
+        assert!(validation[0] > validation[2]);
+        assert!(learning_curve(factory, &x, &y, &[0.0], KFold::new(5, 0), None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation_curve() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((40, 2), |(i, j)| ((i * (j + 5)) % 13) as f64 / 6.0);
+        let y = x.column(0).mapv(|v| 1.5 * v) - x.column(1) + 2.0;
+        let values: Vec<Value> = [1, 10, 100, 1000].map(Value::from).to_vec();
+        let model = LinearRegression::new(2, 0.05);
+        let curve = validation_curve(&model, &x, &y, "epochs", &values, KFold::new(4, 3), None)?;
+
+        assert_eq!((curve.param.as_str(), curve.train_scores.dim()), ("epochs", (4, 4)));
+        // Too few epochs underfit on both sides; enough of them fit both
+        let (train, validation) = (curve.mean_train_scores(), curve.mean_validation_scores());
+        assert!(train.windows(2).into_iter().all(|w| w[0] < w[1]));
+        assert!(validation[0] < 0.5 && validation[3] > 0.99);
+        assert!(validation_curve(&model, &x, &y, "alpha", &values, KFold::new(4, 3), None).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,343 @@
+use crate::traits::{positive_column, BinaryScore, Fit, Predictor, Score};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, Axis};
+
+// Per-class joint log likelihoods (rows: samples, columns: classes) to
+// normalized log posteriors, via log-sum-exp so tiny likelihoods do not
+// underflow to zero
+fn normalize_log_proba(mut joint: Array2<f64>) -> Array2<f64> {
+    for mut row in joint.rows_mut() {
+        let max = row.fold(f64::NEG_INFINITY, |m, &v| m.max(v));
+        let log_total = max + row.mapv(|v| (v - max).exp()).sum().ln();
+        row -= log_total;
+    }
+    joint
+}
+
+fn argmax_labels(log_proba: &Array2<f64>, classes: &[f64]) -> Array1<f64> {
+    log_proba
+        .rows()
+        .into_iter()
+        .map(|row| {
+            let best = (1..row.len()).fold(0, |best, c| if row[c] > row[best] { c } else { best });
+            classes[best]
+        })
+        .collect()
+}
+
+// Sorted distinct labels and, for each, the rows that carry it
+fn group_by_class(x: &Array2<f64>, y: &Array1<f64>) -> Result<(Vec<f64>, Vec<Vec<usize>>), LinearRegressionError> {
+    if x.nrows() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: x.nrows(),
+            found: y.len(),
+            context: "number of samples in X and y",
+        });
+    }
+    if x.nrows() == 0 {
+        return Err(LinearRegressionError::EmptyData);
+    }
+
+    let mut classes = y.to_vec();
+    classes.sort_by(f64::total_cmp);
+    classes.dedup();
+    let mut members = vec![Vec::new(); classes.len()];
+    for (i, label) in y.iter().enumerate() {
+        members[classes.binary_search_by(|c| c.total_cmp(label)).unwrap()].push(i);
+    }
+    Ok((classes, members))
+}
+
+fn check_features(expected: usize, x: &Array2<f64>) -> Result<(), LinearRegressionError> {
+    if expected == 0 {
+        return Err(LinearRegressionError::NotFitted);
+    }
+    if x.ncols() != expected {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected,
+            found: x.ncols(),
+            context: "number of features in prediction",
+        });
+    }
+    Ok(())
+}
+
+// Gaussian Naive Bayes: each feature is normal within each class
+#[derive(Debug, Clone)]
+pub struct GaussianNb {
+    var_smoothing: f64,
+    classes: Vec<f64>,
+    class_log_prior: Array1<f64>,
+    // One row per class, one column per feature
+    means: Array2<f64>,
+    variances: Array2<f64>,
+}
+
+impl GaussianNb {
+    pub fn new() -> Self {
+        Self {
+            var_smoothing: 1e-9,
+            classes: Vec::new(),
+            class_log_prior: Array1::zeros(0),
+            means: Array2::zeros((0, 0)),
+            variances: Array2::zeros((0, 0)),
+        }
+    }
+
+    // Fraction of the largest feature variance added to every variance, so
+    // features that are constant within a class do not divide by zero
+    pub fn with_var_smoothing(mut self, var_smoothing: f64) -> Self {
+        self.var_smoothing = var_smoothing;
+        self
+    }
+
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    pub fn means(&self) -> &Array2<f64> {
+        &self.means
+    }
+
+    pub fn variances(&self) -> &Array2<f64> {
+        &self.variances
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        let (classes, members) = group_by_class(x, y)?;
+        if self.var_smoothing.is_nan() || self.var_smoothing < 0.0 {
+            return Err(LinearRegressionError::InvalidParameter("var_smoothing must be non-negative"));
+        }
+
+        let epsilon = self.var_smoothing * x.var_axis(Axis(0), 0.0).fold(0.0f64, |m, &v| m.max(v));
+        let mut means = Array2::zeros((classes.len(), x.ncols()));
+        let mut variances = Array2::zeros((classes.len(), x.ncols()));
+        for (c, rows) in members.iter().enumerate() {
+            let x_c = x.select(Axis(0), rows);
+            means.row_mut(c).assign(&x_c.mean_axis(Axis(0)).unwrap());
+            variances.row_mut(c).assign(&(x_c.var_axis(Axis(0), 0.0) + epsilon));
+        }
+        if variances.iter().any(|&v| v <= 0.0) {
+            return Err(LinearRegressionError::NumericalError("zero variance; increase var_smoothing"));
+        }
+
+        let n = x.nrows() as f64;
+        self.class_log_prior = members.iter().map(|rows| (rows.len() as f64 / n).ln()).collect();
+        self.classes = classes;
+        self.means = means;
+        self.variances = variances;
+        Ok(())
+    }
+
+    pub fn predict_log_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        check_features(self.means.ncols(), x)?;
+        let tau = std::f64::consts::TAU;
+        let mut joint = Array2::zeros((x.nrows(), self.classes.len()));
+        for c in 0..self.classes.len() {
+            let (mean, var) = (self.means.row(c), self.variances.row(c));
+            let norm: f64 = var.iter().map(|&v| (tau * v).ln()).sum::<f64>() * -0.5;
+            for (i, row) in x.rows().into_iter().enumerate() {
+                let quad: f64 = row
+                    .iter()
+                    .zip(mean.iter().zip(var.iter()))
+                    .map(|(&v, (&m, &s2))| (v - m) * (v - m) / s2)
+                    .sum();
+                joint[[i, c]] = self.class_log_prior[c] + norm - 0.5 * quad;
+            }
+        }
+        Ok(normalize_log_proba(joint))
+    }
+
+    // Posterior class probabilities, one column per class in `classes()` order
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        Ok(self.predict_log_proba(x)?.mapv(f64::exp))
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        Ok(argmax_labels(&self.predict_log_proba(x)?, &self.classes))
+    }
+}
+
+impl Default for GaussianNb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Multinomial Naive Bayes for non-negative count features, such as word
+// counts, with additive (Laplace/Lidstone) smoothing
+#[derive(Debug, Clone)]
+pub struct MultinomialNb {
+    alpha: f64,
+    classes: Vec<f64>,
+    class_log_prior: Array1<f64>,
+    // One row per class: log P(feature | class)
+    feature_log_prob: Array2<f64>,
+}
+
+impl MultinomialNb {
+    pub fn new() -> Self {
+        Self {
+            alpha: 1.0,
+            classes: Vec::new(),
+            class_log_prior: Array1::zeros(0),
+            feature_log_prob: Array2::zeros((0, 0)),
+        }
+    }
+
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    pub fn feature_log_prob(&self) -> &Array2<f64> {
+        &self.feature_log_prob
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        let (classes, members) = group_by_class(x, y)?;
+        if self.alpha.is_nan() || self.alpha < 0.0 {
+            return Err(LinearRegressionError::InvalidParameter("alpha must be non-negative"));
+        }
+        if x.iter().any(|&v| v < 0.0) {
+            return Err(LinearRegressionError::InvalidParameter("MultinomialNb needs non-negative features"));
+        }
+
+        let mut feature_log_prob = Array2::zeros((classes.len(), x.ncols()));
+        for (c, rows) in members.iter().enumerate() {
+            let counts = x.select(Axis(0), rows).sum_axis(Axis(0)) + self.alpha;
+            let total = counts.sum();
+            feature_log_prob.row_mut(c).assign(&counts.mapv(|count| (count / total).ln()));
+        }
+
+        let n = x.nrows() as f64;
+        self.class_log_prior = members.iter().map(|rows| (rows.len() as f64 / n).ln()).collect();
+        self.classes = classes;
+        self.feature_log_prob = feature_log_prob;
+        Ok(())
+    }
+
+    pub fn predict_log_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        check_features(self.feature_log_prob.ncols(), x)?;
+        // With alpha = 0 unseen features have log probability -inf; a zero
+        // count must not turn that into NaN
+        let mut joint = Array2::zeros((x.nrows(), self.classes.len()));
+        for (i, row) in x.rows().into_iter().enumerate() {
+            for c in 0..self.classes.len() {
+                let log_likelihood: f64 = row
+                    .iter()
+                    .zip(self.feature_log_prob.row(c).iter())
+                    .filter(|(&count, _)| count != 0.0)
+                    .map(|(&count, &log_p)| count * log_p)
+                    .sum();
+                joint[[i, c]] = self.class_log_prior[c] + log_likelihood;
+            }
+        }
+        Ok(normalize_log_proba(joint))
+    }
+
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        Ok(self.predict_log_proba(x)?.mapv(f64::exp))
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        Ok(argmax_labels(&self.predict_log_proba(x)?, &self.classes))
+    }
+}
+
+impl Default for MultinomialNb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fit for GaussianNb {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        GaussianNb::fit(self, x, y)
+    }
+}
+
+impl Predictor for GaussianNb {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        GaussianNb::predict(self, x)
+    }
+}
+
+impl Score for GaussianNb {
+    fn is_classifier(&self) -> bool {
+        true
+    }
+}
+
+impl BinaryScore for GaussianNb {
+    fn binary_score(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        positive_column(self.predict_proba(x)?)
+    }
+}
+
+impl Fit for MultinomialNb {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        MultinomialNb::fit(self, x, y)
+    }
+}
+
+impl Predictor for MultinomialNb {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        MultinomialNb::predict(self, x)
+    }
+}
+
+impl Score for MultinomialNb {
+    fn is_classifier(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_gaussian_nb() -> Result<(), LinearRegressionError> {
+        let x = arr2(&[[1.0, 2.0], [1.2, 1.8], [0.8, 2.2], [4.0, 0.0], [4.2, 0.4], [3.8, -0.4]]);
+        let y = Array1::from(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+
+        let mut model = GaussianNb::new();
+        model.fit(&x, &y)?;
+        assert_eq!(model.predict(&x)?, y);
+
+        // Class 0, feature 0: mean 1, population variance 0.08/3
+        assert!((model.means()[[0, 0]] - 1.0).abs() < 1e-12);
+        assert!((model.variances()[[0, 0]] - 0.08 / 3.0).abs() < 1e-6);
+
+        let proba = model.predict_proba(&arr2(&[[2.5, 1.0], [100.0, -50.0]]))?;
+        assert!(proba.rows().into_iter().all(|row| (row.sum() - 1.0).abs() < 1e-12));
+        // Far from both classes the log-space computation still gives an answer
+        assert!(proba[[1, 1]] > 0.99);
+        Ok(())
+    }
+
+    #[test]
+    fn test_multinomial_nb() -> Result<(), LinearRegressionError> {
+        // Word counts for two topics
+        let x = arr2(&[[3.0, 0.0, 1.0], [2.0, 1.0, 0.0], [0.0, 4.0, 1.0], [1.0, 3.0, 2.0]]);
+        let y = Array1::from(vec![0.0, 0.0, 1.0, 1.0]);
+
+        let mut model = MultinomialNb::new();
+        model.fit(&x, &y)?;
+        assert_eq!(model.predict(&x)?, y);
+
+        // Class 0 counts [5, 1, 1] + alpha 1 over a total of 10
+        assert!((model.feature_log_prob()[[0, 0]] - 0.6f64.ln()).abs() < 1e-12);
+
+        let proba = model.predict_proba(&arr2(&[[10.0, 0.0, 0.0]]))?;
+        assert!(proba[[0, 0]] > 0.99);
+        assert!(MultinomialNb::new().fit(&arr2(&[[-1.0]]), &Array1::from(vec![0.0])).is_err());
+        Ok(())
+    }
+}
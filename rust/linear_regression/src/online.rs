@@ -0,0 +1,326 @@
+use crate::traits::{Fit, Predictor, Score};
+use crate::random::{RandomState, Seeded};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Rule {
+    Perceptron,
+    // PA-I with aggressiveness C
+    PassiveAggressive { c: f64 },
+}
+
+// Linear classifier updated one sample at a time. Two classes share a single
+// weight vector (positive score means the larger label); more classes get one
+// one-vs-rest vector each and the highest score wins.
+#[derive(Debug, Clone)]
+struct OnlineLinear {
+    rule: Rule,
+    epochs: usize,
+    seed: u64,
+    classes: Vec<f64>,
+    // One row per one-vs-rest unit
+    weights: Array2<f64>,
+    bias: Array1<f64>,
+}
+
+impl OnlineLinear {
+    fn new(rule: Rule) -> Self {
+        Self {
+            rule,
+            epochs: 10,
+            seed: 0,
+            classes: Vec::new(),
+            weights: Array2::zeros((0, 0)),
+            bias: Array1::zeros(0),
+        }
+    }
+
+    fn check(x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        Ok(())
+    }
+
+    fn initialize(&mut self, classes: &[f64], n_features: usize) -> Result<(), LinearRegressionError> {
+        let mut classes = classes.to_vec();
+        classes.sort_by(f64::total_cmp);
+        classes.dedup();
+        if classes.len() < 2 {
+            return Err(LinearRegressionError::InvalidParameter("need at least two classes"));
+        }
+        let units = if classes.len() == 2 { 1 } else { classes.len() };
+        self.weights = Array2::zeros((units, n_features));
+        self.bias = Array1::zeros(units);
+        self.classes = classes;
+        Ok(())
+    }
+
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        Self::check(x, y)?;
+        self.initialize(&y.to_vec(), x.ncols())?;
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut order: Vec<usize> = (0..x.nrows()).collect();
+        for _ in 0..self.epochs {
+            order.shuffle(&mut rng);
+            for &i in &order {
+                self.update(x.row(i), y[i]);
+            }
+        }
+        Ok(())
+    }
+
+    fn partial_fit(
+        &mut self,
+        x: &Array2<f64>,
+        y: &Array1<f64>,
+        classes: Option<&[f64]>,
+    ) -> Result<(), LinearRegressionError> {
+        Self::check(x, y)?;
+        if self.classes.is_empty() {
+            let classes = classes.ok_or(LinearRegressionError::InvalidParameter(
+                "classes must be given on the first call to partial_fit"
+            ))?;
+            self.initialize(classes, x.ncols())?;
+        }
+        if x.ncols() != self.weights.ncols() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.weights.ncols(),
+                found: x.ncols(),
+                context: "number of features",
+            });
+        }
+        if y.iter().any(|label| !self.classes.contains(label)) {
+            return Err(LinearRegressionError::InvalidParameter("label not in the declared classes"));
+        }
+
+        for (row, &label) in x.rows().into_iter().zip(y.iter()) {
+            self.update(row, label);
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, row: ArrayView1<f64>, label: f64) {
+        let binary = self.weights.nrows() == 1;
+        for unit in 0..self.weights.nrows() {
+            let positive = if binary { label == self.classes[1] } else { label == self.classes[unit] };
+            let sign = if positive { 1.0 } else { -1.0 };
+            let score = self.weights.row(unit).dot(&row) + self.bias[unit];
+
+            let step = match self.rule {
+                Rule::Perceptron => if sign * score <= 0.0 { 1.0 } else { 0.0 },
+                Rule::PassiveAggressive { c } => {
+                    let loss = (1.0 - sign * score).max(0.0);
+                    // The bias acts as a weight on a constant 1 feature
+                    let norm = row.dot(&row) + 1.0;
+                    (loss / norm).min(c)
+                }
+            };
+            if step > 0.0 {
+                self.weights.row_mut(unit).scaled_add(step * sign, &row);
+                self.bias[unit] += step * sign;
+            }
+        }
+    }
+
+    fn decision_function(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        // No classes means neither `fit` nor `partial_fit` has run yet
+        if self.classes.is_empty() {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if x.ncols() != self.weights.ncols() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.weights.ncols(),
+                found: x.ncols(),
+                context: "number of features in prediction",
+            });
+        }
+        Ok(x.dot(&self.weights.t()) + &self.bias)
+    }
+
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let scores = self.decision_function(x)?;
+        Ok(scores
+            .axis_iter(Axis(0))
+            .map(|row| {
+                if row.len() == 1 {
+                    return if row[0] > 0.0 { self.classes[1] } else { self.classes[0] };
+                }
+                let best = (1..row.len()).fold(0, |best, c| if row[c] > row[best] { c } else { best });
+                self.classes[best]
+            })
+            .collect())
+    }
+}
+
+macro_rules! online_classifier {
+    ($name:ident) => {
+        impl $name {
+            // Passes over the data made by `fit`; `partial_fit` always makes one
+            pub fn with_epochs(mut self, epochs: usize) -> Self {
+                self.inner.epochs = epochs;
+                self
+            }
+
+            // Seed for the per-epoch shuffling in `fit`
+            pub fn with_seed(mut self, seed: u64) -> Self {
+                self.inner.seed = seed;
+                self
+            }
+
+            pub fn classes(&self) -> &[f64] {
+                &self.inner.classes
+            }
+
+            // One row per one-vs-rest unit; a single row for two classes
+            pub fn weights(&self) -> &Array2<f64> {
+                &self.inner.weights
+            }
+
+            pub fn bias(&self) -> &Array1<f64> {
+                &self.inner.bias
+            }
+
+            // Train from scratch, discarding anything learned before
+            pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+                self.inner.fit(x, y)
+            }
+
+            // One pass over a chunk of data, continuing from the current
+            // weights. The full set of labels must be given on the first call,
+            // since later chunks may not contain every class.
+            pub fn partial_fit(
+                &mut self,
+                x: &Array2<f64>,
+                y: &Array1<f64>,
+                classes: Option<&[f64]>,
+            ) -> Result<(), LinearRegressionError> {
+                self.inner.partial_fit(x, y, classes)
+            }
+
+            pub fn decision_function(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+                self.inner.decision_function(x)
+            }
+
+            pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+                self.inner.predict(x)
+            }
+        }
+
+        impl Seeded for $name {
+            fn set_random_state(&mut self, state: RandomState) {
+                self.inner.seed = state.seed();
+            }
+        }
+
+        impl Fit for $name {
+            fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+                $name::fit(self, x, y)
+            }
+        }
+
+        impl Predictor for $name {
+            fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+                $name::predict(self, x)
+            }
+        }
+
+        impl Score for $name {
+            fn is_classifier(&self) -> bool {
+                true
+            }
+        }
+    };
+}
+
+// Rosenblatt's perceptron: update only on mistakes, with unit step size
+#[derive(Debug, Clone)]
+pub struct Perceptron {
+    inner: OnlineLinear,
+}
+
+impl Perceptron {
+    pub fn new() -> Self {
+        Self { inner: OnlineLinear::new(Rule::Perceptron) }
+    }
+}
+
+impl Default for Perceptron {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+online_classifier!(Perceptron);
+
+// Passive-Aggressive (PA-I, Crammer et al. 2006): on any hinge loss, move just
+// far enough to classify the sample with margin 1, capped at step size C
+#[derive(Debug, Clone)]
+pub struct PassiveAggressive {
+    inner: OnlineLinear,
+}
+
+impl PassiveAggressive {
+    pub fn new(c: f64) -> Self {
+        Self { inner: OnlineLinear::new(Rule::PassiveAggressive { c }) }
+    }
+}
+
+online_classifier!(PassiveAggressive);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_online_classifiers() -> Result<(), LinearRegressionError> {
+        let x = arr2(&[[2.0, 1.0], [3.0, 2.0], [2.5, 0.5], [-1.0, -2.0], [-2.0, -1.0], [-1.5, -0.5]]);
+        let y = Array1::from(vec![4.0, 4.0, 4.0, 2.0, 2.0, 2.0]);
+
+        let mut perceptron = Perceptron::new();
+        perceptron.fit(&x, &y)?;
+        assert_eq!(perceptron.predict(&x)?, y);
+        assert_eq!(perceptron.weights().nrows(), 1);
+
+        let mut pa = PassiveAggressive::new(1.0);
+        pa.fit(&x, &y)?;
+        assert_eq!(pa.predict(&x)?, y);
+        Ok(())
+    }
+
+    #[test]
+    fn test_partial_fit() -> Result<(), LinearRegressionError> {
+        // Three well separated classes streamed one chunk at a time
+        let x = arr2(&[[5.0, 0.0], [0.0, 5.0], [-5.0, -5.0], [6.0, 1.0], [1.0, 6.0], [-6.0, -4.0]]);
+        let y = Array1::from(vec![0.0, 1.0, 2.0, 0.0, 1.0, 2.0]);
+
+        let mut model = PassiveAggressive::new(1.0);
+        let chunk = arr2(&[[5.0, 0.0]]);
+        assert!(model.partial_fit(&chunk, &Array1::from(vec![0.0]), None).is_err());
+
+        for _ in 0..5 {
+            for (row, &label) in x.rows().into_iter().zip(y.iter()) {
+                let chunk = row.to_owned().insert_axis(Axis(0));
+                model.partial_fit(&chunk, &Array1::from(vec![label]), Some(&[0.0, 1.0, 2.0]))?;
+            }
+        }
+        assert_eq!(model.classes(), &[0.0, 1.0, 2.0]);
+        assert_eq!(model.weights().nrows(), 3);
+        assert_eq!(model.predict(&x)?, y);
+        assert!(model.partial_fit(&chunk, &Array1::from(vec![9.0]), None).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+use crate::linalg::inverse;
+use crate::traits::{param_f64, param_usize, unknown_param, Fit, ParamMap, Params, Predictor, Score};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2};
+use serde_json::Value;
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+// Proportional-odds (cumulative logit) model for ordered class labels:
+// P(y <= c_k | x) = σ(θ_k - x·β) for the sorted labels c_1 < … < c_K, with
+// increasing thresholds θ_1 < … < θ_{K-1}. A single β shifts every cumulative
+// log-odds by the same amount, so a positive coefficient pushes towards the
+// larger labels. Fitted by Newton's method on the log-likelihood.
+#[derive(Debug, Clone)]
+pub struct OrdinalRegression {
+    max_iter: usize,
+    tol: f64,
+    l2: f64,
+    classes: Vec<f64>,
+    coefficients: Array1<f64>,
+    thresholds: Array1<f64>,
+    log_likelihood: f64,
+}
+
+impl OrdinalRegression {
+    pub fn new() -> Self {
+        Self {
+            max_iter: 100,
+            tol: 1e-8,
+            l2: 0.0,
+            classes: Vec::new(),
+            coefficients: Array1::zeros(0),
+            thresholds: Array1::zeros(0),
+            log_likelihood: f64::NAN,
+        }
+    }
+
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    // Stop once the largest gradient component falls below this
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    // Ridge penalty on β (not the thresholds); keeps separable data finite
+    pub fn with_l2(mut self, l2: f64) -> Self {
+        self.l2 = l2;
+        self
+    }
+
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    pub fn coefficients(&self) -> &Array1<f64> {
+        &self.coefficients
+    }
+
+    // Cut points θ_k between consecutive classes, increasing
+    pub fn thresholds(&self) -> &Array1<f64> {
+        &self.thresholds
+    }
+
+    pub fn log_likelihood(&self) -> f64 {
+        self.log_likelihood
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.l2.is_nan() || self.l2 < 0.0 {
+            return Err(LinearRegressionError::InvalidParameter("l2 must be non-negative"));
+        }
+        let mut classes = y.to_vec();
+        classes.sort_by(f64::total_cmp);
+        classes.dedup();
+        if classes.len() < 2 {
+            return Err(LinearRegressionError::InvalidParameter("ordinal regression needs at least two classes"));
+        }
+        let labels: Vec<usize> =
+            y.iter().map(|v| classes.binary_search_by(|c| c.total_cmp(v)).unwrap()).collect();
+
+        // Start at β = 0 with thresholds at the logits of the cumulative
+        // class proportions, which is the maximum without features
+        let (p, k) = (x.ncols(), classes.len() - 1);
+        let n = x.nrows() as f64;
+        let mut params = Array1::zeros(p + k);
+        let mut cumulative = 0.0;
+        for j in 0..k {
+            cumulative += labels.iter().filter(|&&l| l == j).count() as f64 / n;
+            params[p + j] = (cumulative / (1.0 - cumulative)).ln();
+        }
+
+        let mut current = self.objective(x, &labels, &params);
+        for _ in 0..self.max_iter {
+            let (gradient, hessian) = self.derivatives(x, &labels, &params);
+            if gradient.iter().fold(0.0f64, |m, g| m.max(g.abs())) < self.tol {
+                break;
+            }
+            // The log-likelihood is concave, so -H is positive semi-definite
+            let mut information = -hessian;
+            information.diag_mut().mapv_inplace(|v| v + 1e-10);
+            let direction = inverse(&information)?.dot(&gradient);
+
+            // Halve the step until the thresholds stay ordered and the
+            // objective improves
+            let mut step = 1.0;
+            loop {
+                let candidate = &params + &(&direction * step);
+                let value = self.objective(x, &labels, &candidate);
+                if value > current {
+                    params = candidate;
+                    current = value;
+                    break;
+                }
+                step /= 2.0;
+                if step < 1e-10 {
+                    break;
+                }
+            }
+            if step < 1e-10 {
+                break;
+            }
+        }
+        if !current.is_finite() {
+            return Err(LinearRegressionError::NumericalError("ordinal regression did not converge"));
+        }
+
+        self.classes = classes;
+        self.coefficients = params.slice(ndarray::s![..p]).to_owned();
+        self.thresholds = params.slice(ndarray::s![p..]).to_owned();
+        self.log_likelihood = current + 0.5 * self.l2 * self.coefficients.dot(&self.coefficients);
+        Ok(())
+    }
+
+    // Class probabilities, one column per class in `classes()` order
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        if self.classes.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        if x.ncols() != self.coefficients.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.coefficients.len(),
+                found: x.ncols(),
+                context: "number of features",
+            });
+        }
+        let eta = x.dot(&self.coefficients);
+        let k = self.classes.len();
+        Ok(Array2::from_shape_fn((x.nrows(), k), |(i, c)| {
+            let upper = if c + 1 < k { sigmoid(self.thresholds[c] - eta[i]) } else { 1.0 };
+            let lower = if c > 0 { sigmoid(self.thresholds[c - 1] - eta[i]) } else { 0.0 };
+            upper - lower
+        }))
+    }
+
+    // Most probable class, ties going to the smaller label
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let proba = self.predict_proba(x)?;
+        Ok(proba
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let best = (1..row.len()).fold(0, |best, c| if row[c] > row[best] { c } else { best });
+                self.classes[best]
+            })
+            .collect())
+    }
+
+    // Penalized log-likelihood, or -inf when the thresholds are out of order
+    fn objective(&self, x: &Array2<f64>, labels: &[usize], params: &Array1<f64>) -> f64 {
+        let p = x.ncols();
+        let thresholds = params.slice(ndarray::s![p..]);
+        if thresholds.windows(2).into_iter().any(|w| w[0].is_nan() || w[0] >= w[1]) {
+            return f64::NEG_INFINITY;
+        }
+        let beta = params.slice(ndarray::s![..p]);
+        let eta = x.dot(&beta);
+        let k = thresholds.len();
+        let log_likelihood: f64 = labels
+            .iter()
+            .zip(eta.iter())
+            .map(|(&l, &e)| {
+                let upper = if l < k { sigmoid(thresholds[l] - e) } else { 1.0 };
+                let lower = if l > 0 { sigmoid(thresholds[l - 1] - e) } else { 0.0 };
+                (upper - lower).ln()
+            })
+            .sum();
+        log_likelihood - 0.5 * self.l2 * beta.dot(&beta)
+    }
+
+    // Gradient and Hessian of the objective. For a sample in class l, with
+    // a = θ_l - η, b = θ_{l-1} - η, P = σ(a) - σ(b) and f = σ(1 - σ), the
+    // log-likelihood ln P has ∂/∂θ_l = f(a)/P, ∂/∂θ_{l-1} = -f(b)/P and
+    // ∂/∂η = -(f(a) - f(b))/P; missing end thresholds contribute zero.
+    fn derivatives(&self, x: &Array2<f64>, labels: &[usize], params: &Array1<f64>) -> (Array1<f64>, Array2<f64>) {
+        let p = x.ncols();
+        let k = params.len() - p;
+        let beta = params.slice(ndarray::s![..p]);
+        let eta = x.dot(&beta);
+        let mut gradient = Array1::zeros(p + k);
+        let mut hessian = Array2::zeros((p + k, p + k));
+
+        for (i, &l) in labels.iter().enumerate() {
+            // (σ, f, f') at the upper and lower cut, zero when absent
+            let at = |j: usize| {
+                let s = sigmoid(params[p + j] - eta[i]);
+                let f = s * (1.0 - s);
+                (s, f, f * (1.0 - 2.0 * s))
+            };
+            let (sa, fa, da) = if l < k { at(l) } else { (1.0, 0.0, 0.0) };
+            let (sb, fb, db) = if l > 0 { at(l - 1) } else { (0.0, 0.0, 0.0) };
+            let prob = (sa - sb).max(1e-300);
+            let (ga, gb) = (fa / prob, fb / prob);
+
+            // Second derivatives of ln P in (η, θ_l, θ_{l-1})
+            let d_eta = -(ga - gb);
+            let d_eta_eta = (da - db) / prob - (ga - gb) * (ga - gb);
+            let d_a_a = da / prob - ga * ga;
+            let d_b_b = -db / prob - gb * gb;
+            let d_a_b = ga * gb;
+            let d_eta_a = -da / prob + ga * (ga - gb);
+            let d_eta_b = db / prob - gb * (ga - gb);
+
+            let row = x.row(i);
+            for r in 0..p {
+                gradient[r] += d_eta * row[r];
+                for c in 0..p {
+                    hessian[[r, c]] += d_eta_eta * row[r] * row[c];
+                }
+            }
+            let mut cuts = Vec::with_capacity(2);
+            if l < k {
+                cuts.push((p + l, ga, d_a_a, d_eta_a));
+            }
+            if l > 0 {
+                cuts.push((p + l - 1, -gb, d_b_b, d_eta_b));
+            }
+            for &(j, g, d_jj, d_eta_j) in &cuts {
+                gradient[j] += g;
+                hessian[[j, j]] += d_jj;
+                for r in 0..p {
+                    hessian[[r, j]] += d_eta_j * row[r];
+                    hessian[[j, r]] += d_eta_j * row[r];
+                }
+            }
+            if cuts.len() == 2 {
+                hessian[[p + l, p + l - 1]] += d_a_b;
+                hessian[[p + l - 1, p + l]] += d_a_b;
+            }
+        }
+        for r in 0..p {
+            gradient[r] -= self.l2 * beta[r];
+            hessian[[r, r]] -= self.l2;
+        }
+        (gradient, hessian)
+    }
+}
+
+impl Default for OrdinalRegression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fit for OrdinalRegression {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        OrdinalRegression::fit(self, x, y)
+    }
+}
+
+impl Predictor for OrdinalRegression {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        OrdinalRegression::predict(self, x)
+    }
+}
+
+impl Score for OrdinalRegression {
+    fn is_classifier(&self) -> bool {
+        true
+    }
+}
+
+impl Params for OrdinalRegression {
+    fn get_params(&self) -> ParamMap {
+        ParamMap::from([
+            ("max_iter".to_string(), Value::from(self.max_iter)),
+            ("tol".to_string(), Value::from(self.tol)),
+            ("l2".to_string(), Value::from(self.l2)),
+        ])
+    }
+
+    fn set_param(&mut self, name: &str, value: &Value) -> Result<(), LinearRegressionError> {
+        match name {
+            "max_iter" => self.max_iter = param_usize(name, value, 0)?,
+            "tol" => self.tol = param_f64(name, value, 0.0)?,
+            "l2" => self.l2 = param_f64(name, value, 0.0)?,
+            _ => return Err(unknown_param(name)),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_ordinal_regression() -> Result<(), LinearRegressionError> {
+        // Ratings 1-4 cut from a latent 2 x₀ - x₁ + logistic noise
+        let mut rng = StdRng::seed_from_u64(3);
+        let n = 2000;
+        let x = Array2::from_shape_fn((n, 2), |_| rng.random_range(-2.0..2.0));
+        let cuts = [-1.0, 0.5, 2.0];
+        let y: Array1<f64> = (0..n)
+            .map(|i| {
+                let u: f64 = rng.random_range(1e-12..1.0);
+                let latent = 2.0 * x[[i, 0]] - x[[i, 1]] + (u / (1.0 - u)).ln();
+                1.0 + cuts.iter().filter(|&&c| latent > c).count() as f64
+            })
+            .collect();
+
+        let mut model = OrdinalRegression::new();
+        model.fit(&x, &y)?;
+        assert_eq!(model.classes(), [1.0, 2.0, 3.0, 4.0]);
+        assert!((model.coefficients()[0] - 2.0).abs() < 0.2 && (model.coefficients()[1] + 1.0).abs() < 0.15);
+        for (theta, cut) in model.thresholds().iter().zip(cuts) {
+            assert!((theta - cut).abs() < 0.25);
+        }
+
+        let proba = model.predict_proba(&x)?;
+        assert!(proba.rows().into_iter().all(|row| (row.sum() - 1.0).abs() < 1e-12));
+        let high = ndarray::arr2(&[[2.0, -2.0], [-2.0, 2.0]]);
+        assert_eq!(model.predict(&high)?.to_vec(), vec![4.0, 1.0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use crate::linalg::svd;
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+
+// How many principal components to keep
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Components {
+    All,
+    Count(usize),
+    // The fewest components whose explained variance ratios add up to at
+    // least this fraction, in (0, 1]
+    Variance(f64),
+}
+
+// Principal component analysis via the SVD of the centered data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pca {
+    pub means: Array1<f64>,
+    // One row per component, unit length, ordered by explained variance
+    pub components: Array2<f64>,
+    // Variance along each kept component (sample variance, ddof 1)
+    pub explained_variance: Array1<f64>,
+    // Share of the total variance along each kept component
+    pub explained_variance_ratio: Array1<f64>,
+}
+
+impl Pca {
+    pub fn fit(x: &Array2<f64>, n_components: Components) -> Result<Self, LinearRegressionError> {
+        if x.nrows() < 2 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+
+        let means = x.mean_axis(Axis(0)).unwrap();
+        let centered = x - &means;
+        let (_, singular_values, v) = svd(&centered);
+
+        let explained: Array1<f64> = singular_values.mapv(|s| s * s / (x.nrows() - 1) as f64);
+        let total = explained.sum();
+        let ratio = if total > 0.0 { &explained / total } else { Array1::zeros(explained.len()) };
+
+        let k = match n_components {
+            Components::All => explained.len(),
+            Components::Count(k) if k >= 1 && k <= explained.len() => k,
+            Components::Count(_) => {
+                return Err(LinearRegressionError::InvalidParameter(
+                    "n_components must be between 1 and min(n_samples, n_features)"
+                ))
+            }
+            Components::Variance(threshold) if threshold > 0.0 && threshold <= 1.0 => {
+                let mut cumulative = 0.0;
+                // Guard against rounding keeping the sum just under 1.0
+                ratio
+                    .iter()
+                    .position(|&r| {
+                        cumulative += r;
+                        cumulative >= threshold - 1e-12
+                    })
+                    .map_or(explained.len(), |i| i + 1)
+            }
+            Components::Variance(_) => {
+                return Err(LinearRegressionError::InvalidParameter("variance threshold must be in (0, 1]"))
+            }
+        };
+
+        // Fix each component's sign so its largest entry is positive, making
+        // the result independent of the SVD's arbitrary signs
+        let mut components = v.slice(ndarray::s![.., ..k]).t().to_owned();
+        for mut row in components.rows_mut() {
+            let largest = row.iter().fold(0.0f64, |m, &c| if c.abs() > m.abs() { c } else { m });
+            if largest < 0.0 {
+                row.mapv_inplace(|c| -c);
+            }
+        }
+
+        Ok(Self {
+            means,
+            components,
+            explained_variance: explained.slice(ndarray::s![..k]).to_owned(),
+            explained_variance_ratio: ratio.slice(ndarray::s![..k]).to_owned(),
+        })
+    }
+
+    pub fn n_components(&self) -> usize {
+        self.components.nrows()
+    }
+
+    // Coordinates of each row along the kept components
+    pub fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        if x.ncols() != self.means.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.means.len(),
+                found: x.ncols(),
+                context: "number of features in PCA transform",
+            });
+        }
+        Ok((x - &self.means).dot(&self.components.t()))
+    }
+
+    // Map component coordinates back to the original feature space. Exact
+    // only when every component was kept.
+    pub fn inverse_transform(&self, z: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        if z.ncols() != self.n_components() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.n_components(),
+                found: z.ncols(),
+                context: "number of components in PCA inverse transform",
+            });
+        }
+        Ok(z.dot(&self.components) + &self.means)
+    }
+
+    pub fn fit_transform(
+        x: &Array2<f64>,
+        n_components: Components,
+    ) -> Result<(Self, Array2<f64>), LinearRegressionError> {
+        let pca = Self::fit(x, n_components)?;
+        let transformed = pca.transform(x)?;
+        Ok((pca, transformed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pca() -> Result<(), LinearRegressionError> {
+        // Points spread along (1, 1) with a small perpendicular wobble
+        let x = Array2::from_shape_fn((50, 2), |(i, j)| {
+            let t = i as f64 / 5.0 - 5.0;
+            let wobble = ((i * 7) % 5) as f64 / 10.0 - 0.2;
+            if j == 0 { t + wobble } else { t - wobble }
+        });
+
+        let (pca, z) = Pca::fit_transform(&x, Components::All)?;
+        assert_eq!(pca.n_components(), 2);
+        let r = std::f64::consts::FRAC_1_SQRT_2;
+        assert!((pca.components[[0, 0]] - r).abs() < 1e-2 && (pca.components[[0, 1]] - r).abs() < 1e-2);
+        assert!((pca.explained_variance_ratio.sum() - 1.0).abs() < 1e-12);
+        assert!(pca.explained_variance_ratio[0] > 0.99);
+        // The variance of the scores along a component is its explained variance
+        assert!((z.column(0).var(1.0) - pca.explained_variance[0]).abs() < 1e-9);
+        let rebuilt = pca.inverse_transform(&z)?;
+        assert!(rebuilt.iter().zip(x.iter()).all(|(a, b)| (a - b).abs() < 1e-10));
+
+        let reduced = Pca::fit(&x, Components::Variance(0.95))?;
+        assert_eq!(reduced.n_components(), 1);
+        assert!(Pca::fit(&x, Components::Count(3)).is_err());
+        Ok(())
+    }
+}
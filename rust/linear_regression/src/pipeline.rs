@@ -0,0 +1,270 @@
+use crate::data::Dataset;
+use crate::diagnostics::{self, ResidualData};
+use crate::history::TrainingHistory;
+use crate::preprocessing::StandardScaler;
+use crate::spline::{SplineBasis, SplineTransformer};
+use crate::{LinearRegression, LinearRegressionError};
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read};
+use std::path::Path;
+
+// A fitted scaler + model pair, along with the column names it was trained on.
+// An optional spline expansion runs ahead of the scaler.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pipeline {
+    pub feature_names: Vec<String>,
+    pub target_name: String,
+    #[serde(default)]
+    pub splines: Option<SplineTransformer>,
+    pub scaler: StandardScaler,
+    pub model: LinearRegression,
+}
+
+// The fitted equation in the original units, with the scaling folded into
+// the coefficients, e.g. `price = 0.52*sqft + 10.3*bedrooms + 12.1`. Spline
+// pipelines show one term per basis function.
+impl std::fmt::Display for Pipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let names = match &self.splines {
+            Some(splines) => splines.feature_names(&self.feature_names),
+            None => self.feature_names.clone(),
+        };
+        let scaling = self.scaler.means.iter().zip(self.scaler.stds.iter());
+        let mut intercept = self.model.bias;
+        let mut terms = Vec::with_capacity(names.len());
+        for ((name, &w), (&mean, &std)) in names.into_iter().zip(self.model.weights.iter()).zip(scaling) {
+            intercept -= w * mean / std;
+            terms.push((name, w / std));
+        }
+        crate::write_equation(f, &self.target_name, &terms, intercept)
+    }
+}
+
+impl Pipeline {
+    pub fn fit(
+        dataset: &Dataset,
+        learning_rate: f64,
+        epochs: usize,
+    ) -> Result<(Self, TrainingHistory), LinearRegressionError> {
+        Self::fit_expanded(dataset, None, learning_rate, epochs)
+    }
+
+    // Expand every feature in a spline basis with `n_knots` quantile knots
+    // before scaling, so the linear model fits a smooth curve per feature
+    pub fn fit_with_splines(
+        dataset: &Dataset,
+        basis: SplineBasis,
+        n_knots: usize,
+        learning_rate: f64,
+        epochs: usize,
+    ) -> Result<(Self, TrainingHistory), LinearRegressionError> {
+        let splines = SplineTransformer::fit(&dataset.x, basis, n_knots)?;
+        Self::fit_expanded(dataset, Some(splines), learning_rate, epochs)
+    }
+
+    fn fit_expanded(
+        dataset: &Dataset,
+        splines: Option<SplineTransformer>,
+        learning_rate: f64,
+        epochs: usize,
+    ) -> Result<(Self, TrainingHistory), LinearRegressionError> {
+        let x = match &splines {
+            Some(splines) => splines.transform(&dataset.x)?,
+            None => dataset.x.clone(),
+        };
+        let (scaler, x_norm) = StandardScaler::fit_transform(&x)?;
+        let mut model = LinearRegression::new(x.ncols(), learning_rate);
+        // Spline models have one weight per basis column, not per input
+        if splines.is_none() {
+            model = model.with_feature_names(dataset.feature_names.clone());
+        }
+        let history = model.train(&x_norm, &dataset.y, epochs)?;
+
+        let pipeline = Self {
+            feature_names: dataset.feature_names.clone(),
+            target_name: dataset.target_name.clone(),
+            splines,
+            scaler,
+            model,
+        };
+        Ok((pipeline, history))
+    }
+
+    // More epochs on the current weights, e.g. after `model.set_learning_rate`
+    // or `model.reset_weights`. The data goes through the fitted splines and
+    // scaler as they are, so predictions stay in the same units.
+    pub fn continue_training(
+        &mut self,
+        x: &Array2<f64>,
+        y: &Array1<f64>,
+        epochs: usize,
+    ) -> Result<TrainingHistory, LinearRegressionError> {
+        let x_norm = self.model_input(x)?;
+        self.model.train(&x_norm, y, epochs)
+    }
+
+    // Predict from raw (unscaled) features
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        self.model.predict(&self.model_input(x)?)
+    }
+
+    // Per-row residuals, leverage and QQ positions for raw features. Leverage
+    // is measured on the columns the model sees, spline bases included.
+    pub fn residual_data(&self, x: &Array2<f64>, y: &Array1<f64>) -> Result<ResidualData, LinearRegressionError> {
+        diagnostics::residual_data(&self.model, &self.model_input(x)?, y)
+    }
+
+    fn model_input(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        match &self.splines {
+            Some(splines) => self.scaler.transform(&splines.transform(x)?),
+            None => self.scaler.transform(x),
+        }
+    }
+
+    // Prediction for a single sample
+    pub fn predict_one(&self, features: &[f64]) -> Result<f64, LinearRegressionError> {
+        if let Some(splines) = &self.splines {
+            let row = Array2::from_shape_vec((1, features.len()), features.to_vec()).unwrap();
+            return self.model.predict_one_array(&self.scaler.transform(&splines.transform(&row)?)?.row(0));
+        }
+        if features.len() != self.scaler.means.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.scaler.means.len(),
+                found: features.len(),
+                context: "number of features in prediction",
+            });
+        }
+        let scaling = self.scaler.means.iter().zip(self.scaler.stds.iter());
+        let scaled: Array1<f64> = features.iter().zip(scaling).map(|(v, (m, s))| (v - m) / s).collect();
+        self.model.predict_one_array(&scaled)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), LinearRegressionError> {
+        let writer = BufWriter::new(File::create(path)?);
+        Ok(serde_json::to_writer_pretty(writer, self)?)
+    }
+
+    // Rejects files whose parts disagree on the number of features, which
+    // would otherwise only surface as a confusing error at prediction time
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, LinearRegressionError> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    // Same as `load`, for JSON that does not come from a file
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, LinearRegressionError> {
+        let pipeline: Self = serde_json::from_reader(reader)?;
+        pipeline.check_consistent()?;
+        Ok(pipeline)
+    }
+
+    fn check_consistent(&self) -> Result<(), LinearRegressionError> {
+        let mismatch = |what: &str, expected: usize, found: usize| {
+            LinearRegressionError::IncompatibleModel(format!("expected {} {}, found {}", expected, what, found))
+        };
+        let n_inputs = self.feature_names.len();
+        let n_expanded = match &self.splines {
+            Some(splines) if splines.knots.len() != n_inputs => {
+                return Err(mismatch("spline knot sets", n_inputs, splines.knots.len()));
+            }
+            Some(splines) => {
+                splines.validate()?;
+                splines.n_output_features()
+            }
+            None => n_inputs,
+        };
+        if self.scaler.means.len() != n_expanded || self.scaler.stds.len() != n_expanded {
+            return Err(mismatch("scaler columns", n_expanded, self.scaler.means.len().min(self.scaler.stds.len())));
+        }
+        if self.model.weights.len() != n_expanded {
+            return Err(mismatch("model weights", n_expanded, self.model.weights.len()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Score;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_pipeline_save_load_roundtrip() -> Result<(), LinearRegressionError> {
+        let dataset = Dataset {
+            x: arr2(&[[1200.0, 2.0], [1500.0, 3.0], [2000.0, 3.0], [1100.0, 2.0]]),
+            y: Array1::from(vec![200.0, 250.0, 320.0, 190.0]),
+            feature_names: vec!["sqft".to_string(), "bedrooms".to_string()],
+            target_name: "price".to_string(),
+        };
+        let (pipeline, history) = Pipeline::fit(&dataset, 0.1, 200)?;
+        assert!(history.initial_loss() > history.final_loss());
+
+        let path = std::env::temp_dir().join("linear_regression_pipeline_test.json");
+        pipeline.save(&path)?;
+        let loaded = Pipeline::load(&path)?;
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(loaded.feature_names, pipeline.feature_names);
+        assert_eq!(loaded.model.coef_for("bedrooms"), Some(pipeline.model.weights[1]));
+        let original = pipeline.predict(&dataset.x)?;
+        let reloaded = loaded.predict(&dataset.x)?;
+        assert!(original.iter().zip(reloaded.iter()).all(|(a, b)| (a - b).abs() < 1e-9));
+        assert!((loaded.predict_one(&[1500.0, 3.0])? - original[1]).abs() < 1e-9);
+        let equation = format!("{:.6}", loaded);
+        assert!(equation.starts_with("price = ") && equation.contains("*sqft") && equation.contains("*bedrooms"));
+        assert!(loaded.predict_one(&[1500.0]).is_err());
+        let mut reset = loaded.clone();
+        reset.model.reset_weights();
+        assert!(matches!(reset.predict_one(&[1500.0, 3.0]), Err(LinearRegressionError::NotFitted)));
+
+        // A model trimmed to one weight no longer matches the two features
+        let mut broken = pipeline.clone();
+        broken.model.weights = Array1::from(vec![1.0]);
+        broken.save(&path)?;
+        let result = Pipeline::load(&path);
+        std::fs::remove_file(&path)?;
+        match result {
+            Err(LinearRegressionError::IncompatibleModel(message)) => assert!(message.contains("model weights")),
+            _ => panic!("Expected incompatible model error"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_spline_pipeline() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((90, 1), |(i, _)| i as f64 / 10.0);
+        let dataset = Dataset {
+            y: x.column(0).mapv(f64::sin),
+            x,
+            feature_names: vec!["t".to_string()],
+            target_name: "signal".to_string(),
+        };
+        let (linear, _) = Pipeline::fit(&dataset, 0.1, 2000)?;
+        let (spline, _) = Pipeline::fit_with_splines(&dataset, SplineBasis::BSpline { degree: 3 }, 8, 0.1, 5000)?;
+        assert_eq!(spline.model.weights.len(), 10);
+
+        assert!(linear.score(&dataset.x, &dataset.y)? < 0.1);
+        assert!(spline.score(&dataset.x, &dataset.y)? > 0.95);
+        assert!((spline.predict_one(&[2.5])? - spline.predict(&dataset.x)?[25]).abs() < 1e-9);
+
+        // Retraining from scratch at a new rate keeps the fitted basis and scaler
+        let mut retrained = spline.clone();
+        retrained.model.reset_weights();
+        retrained.model.reset_optimizer_state();
+        assert!(retrained.predict(&dataset.x).is_err());
+        retrained.model.set_learning_rate(0.2)?;
+        assert!(retrained.model.set_learning_rate(0.0).is_err());
+        retrained.continue_training(&dataset.x, &dataset.y, 2500)?;
+        assert_eq!(retrained.scaler.means, spline.scaler.means);
+        assert!(retrained.score(&dataset.x, &dataset.y)? > 0.95);
+
+        // Knots edited out of order in a saved file are caught on load
+        let mut edited = spline.clone();
+        edited.splines.as_mut().unwrap().knots[0].reverse();
+        let json = serde_json::to_string(&edited)?;
+        assert!(matches!(Pipeline::from_reader(json.as_bytes()), Err(LinearRegressionError::InvalidParameter(_))));
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+// Charts of a training run and of a fit, written as PNG or SVG so they can
+// be looked at without leaving Rust. The format comes from the extension.
+
+use crate::history::TrainingHistory;
+use crate::LinearRegressionError;
+use ndarray::Array1;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::path::{Path, PathBuf};
+
+const SIZE: (u32, u32) = (800, 600);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlotFormat {
+    Png,
+    Svg,
+}
+
+impl PlotFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "png" => Some(Self::Png),
+            "svg" => Some(Self::Svg),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+struct Line {
+    label: &'static str,
+    points: Vec<(f64, f64)>,
+    color: RGBColor,
+}
+
+// What one chart shows: labelled lines and a cloud of points
+struct Figure {
+    title: &'static str,
+    x_label: &'static str,
+    y_label: &'static str,
+    lines: Vec<Line>,
+    points: Vec<(f64, f64)>,
+}
+
+// Training loss per epoch, with the validation loss when it was recorded
+pub fn plot_history<P: AsRef<Path>>(history: &TrainingHistory, path: P) -> Result<(), LinearRegressionError> {
+    if history.is_empty() {
+        return Err(LinearRegressionError::EmptyData);
+    }
+    let curve = |label, losses: &[f64], color| Line {
+        label,
+        points: losses.iter().enumerate().map(|(epoch, &loss)| (epoch as f64, loss)).collect(),
+        color,
+    };
+    let mut lines = vec![curve("training", &history.train_loss, BLUE)];
+    if let Some(validation) = &history.validation_loss {
+        lines.push(curve("validation", validation, RED));
+    }
+    let figure = Figure { title: "Training loss", x_label: "Epoch", y_label: "MSE", lines, points: Vec::new() };
+    render(&figure, path.as_ref())
+}
+
+// Residuals (actual - predicted) against the predictions, around a zero line.
+// A pattern instead of an even band points at a missing feature or transform.
+pub fn plot_residuals<P: AsRef<Path>>(
+    predictions: &Array1<f64>,
+    y: &Array1<f64>,
+    path: P,
+) -> Result<(), LinearRegressionError> {
+    check_lengths(predictions, y)?;
+    let points: Vec<(f64, f64)> = predictions.iter().zip(y.iter()).map(|(&p, &t)| (p, t - p)).collect();
+    let (lo, hi) = bounds(points.iter().map(|p| p.0));
+    let figure = Figure {
+        title: "Residuals vs fitted",
+        x_label: "Fitted",
+        y_label: "Residual",
+        lines: vec![Line { label: "zero", points: vec![(lo, 0.0), (hi, 0.0)], color: BLACK }],
+        points,
+    };
+    render(&figure, path.as_ref())
+}
+
+// Predictions against the actual values, with the diagonal a perfect model
+// would follow
+pub fn plot_predicted_vs_actual<P: AsRef<Path>>(
+    predictions: &Array1<f64>,
+    y: &Array1<f64>,
+    path: P,
+) -> Result<(), LinearRegressionError> {
+    check_lengths(predictions, y)?;
+    let points: Vec<(f64, f64)> = y.iter().zip(predictions.iter()).map(|(&t, &p)| (t, p)).collect();
+    let (lo, hi) = bounds(points.iter().flat_map(|&(t, p)| [t, p]));
+    let figure = Figure {
+        title: "Predicted vs actual",
+        x_label: "Actual",
+        y_label: "Predicted",
+        lines: vec![Line { label: "y = x", points: vec![(lo, lo), (hi, hi)], color: BLACK }],
+        points,
+    };
+    render(&figure, path.as_ref())
+}
+
+// Every chart that applies into `dir` (created if needed): loss.<ext> when a
+// history is given, residuals.<ext> and predicted_vs_actual.<ext>. Returns
+// the files written.
+pub fn save_plots<P: AsRef<Path>>(
+    dir: P,
+    history: Option<&TrainingHistory>,
+    predictions: &Array1<f64>,
+    y: &Array1<f64>,
+    format: PlotFormat,
+) -> Result<Vec<PathBuf>, LinearRegressionError> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    let file = |name: &str| dir.join(format!("{}.{}", name, format.extension()));
+    let mut written = Vec::new();
+    if let Some(history) = history {
+        plot_history(history, file("loss"))?;
+        written.push(file("loss"));
+    }
+    plot_residuals(predictions, y, file("residuals"))?;
+    written.push(file("residuals"));
+    plot_predicted_vs_actual(predictions, y, file("predicted_vs_actual"))?;
+    written.push(file("predicted_vs_actual"));
+    Ok(written)
+}
+
+fn check_lengths(predictions: &Array1<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+    if predictions.len() != y.len() {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: y.len(),
+            found: predictions.len(),
+            context: "number of predictions and targets",
+        });
+    }
+    if y.is_empty() {
+        return Err(LinearRegressionError::EmptyData);
+    }
+    Ok(())
+}
+
+// Smallest and largest finite value, widened when they coincide so the axis
+// has some extent
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (lo, hi) = values
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    match (lo.is_finite(), lo < hi) {
+        (false, _) => (0.0, 1.0),
+        (true, false) => (lo - 1.0, hi + 1.0),
+        (true, true) => (lo, hi),
+    }
+}
+
+fn render(figure: &Figure, path: &Path) -> Result<(), LinearRegressionError> {
+    let format = PlotFormat::from_path(path)
+        .ok_or_else(|| LinearRegressionError::Parse(format!("unknown plot format for {}", path.display())))?;
+    match format {
+        PlotFormat::Png => draw(figure, BitMapBackend::new(path, SIZE).into_drawing_area()),
+        PlotFormat::Svg => draw(figure, SVGBackend::new(path, SIZE).into_drawing_area()),
+    }
+}
+
+fn draw<DB: DrawingBackend>(figure: &Figure, root: DrawingArea<DB, Shift>) -> Result<(), LinearRegressionError> {
+    let all = || figure.lines.iter().flat_map(|line| line.points.iter()).chain(figure.points.iter());
+    let (x_lo, x_hi) = bounds(all().map(|p| p.0));
+    let (y_lo, y_hi) = bounds(all().map(|p| p.1));
+    let pad = |lo: f64, hi: f64| (lo - 0.05 * (hi - lo), hi + 0.05 * (hi - lo));
+    let ((x_lo, x_hi), (y_lo, y_hi)) = (pad(x_lo, x_hi), pad(y_lo, y_hi));
+
+    root.fill(&WHITE).map_err(plot_error)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(figure.title, ("sans-serif", 28))
+        .margin(15)
+        .x_label_area_size(45)
+        .y_label_area_size(70)
+        .build_cartesian_2d(x_lo..x_hi, y_lo..y_hi)
+        .map_err(plot_error)?;
+    chart.configure_mesh().x_desc(figure.x_label).y_desc(figure.y_label).draw().map_err(plot_error)?;
+
+    chart
+        .draw_series(figure.points.iter().map(|&p| Circle::new(p, 3, BLUE.mix(0.6).filled())))
+        .map_err(plot_error)?;
+    for line in &figure.lines {
+        let color = line.color;
+        chart
+            .draw_series(LineSeries::new(line.points.iter().copied(), color.stroke_width(2)))
+            .map_err(plot_error)?
+            .label(line.label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2)));
+    }
+    if figure.lines.len() > 1 {
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()
+            .map_err(plot_error)?;
+    }
+    root.present().map_err(plot_error)
+}
+
+fn plot_error<E: std::error::Error + Send + Sync>(e: DrawingAreaErrorKind<E>) -> LinearRegressionError {
+    LinearRegressionError::Io(std::io::Error::other(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_plots() -> Result<(), LinearRegressionError> {
+        let dir = std::env::temp_dir().join("linear_regression_plotting_test");
+        let x = ndarray::Array2::from_shape_fn((30, 1), |(i, _)| i as f64 / 10.0);
+        let y = x.column(0).mapv(|v| 2.0 * v + 1.0 + (7.0 * v).sin() * 0.1);
+        let mut model = crate::LinearRegression::new(1, 0.1);
+        let history = model.train(&x, &y, 200)?;
+        let predictions = model.predict(&x)?;
+
+        let written = save_plots(&dir, Some(&history), &predictions, &y, PlotFormat::Svg)?;
+        assert_eq!(written.len(), 3);
+        let svg = std::fs::read_to_string(dir.join("loss.svg"))?;
+        assert!(svg.starts_with("<svg") && svg.contains("Training loss"));
+        plot_residuals(&predictions, &y, dir.join("residuals.png"))?;
+        assert!(std::fs::metadata(dir.join("residuals.png"))?.len() > 0);
+
+        assert!(plot_residuals(&predictions, &y, dir.join("residuals.gif")).is_err());
+        let short = y.slice(ndarray::s![1..]).to_owned();
+        assert!(plot_predicted_vs_actual(&predictions, &short, dir.join("a.svg")).is_err());
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
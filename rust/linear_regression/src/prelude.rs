@@ -0,0 +1,64 @@
+// The types most programs need, for `use linear_regression::prelude::*`.
+// Specialized models (time series, survival, count data, ...) stay in their
+// own modules.
+
+pub use crate::automl::{auto_fit, AutoModel};
+pub use crate::config::TrainConfig;
+pub use crate::data::Dataset;
+pub use crate::history::TrainingHistory;
+pub use crate::lr_finder::{lr_finder, LrRange};
+pub use crate::metrics::{accuracy_score, mean_absolute_error, mean_squared_error, r2_score, root_mean_squared_error};
+pub use crate::model_selection::{
+    compare_models, cross_val_score, grid_combinations, learning_curve, nested_cv, sample_candidates, validation_curve,
+    Distribution, GridSearchCV, KFold, Metric, ParamDistributions, ParamGrid, RandomSearch, SuccessiveHalving, Tuner,
+};
+pub use crate::pipeline::Pipeline;
+pub use crate::preprocessing::StandardScaler;
+pub use crate::random::{RandomState, Seeded};
+pub use crate::traits::{BinaryScore, Estimator, Fit, ParamMap, Params, Predictor, Score, WarmStart};
+pub use crate::{ErrorKind, LinearRegression, LinearRegressionBuilder, LinearRegressionError, MlError, WeightInit};
+
+pub use crate::boosting::{GradientBoosting, Loss};
+pub use crate::forest::RandomForest;
+pub use crate::knn::{KnnClassifier, KnnRegressor};
+pub use crate::mlp::{MlpClassifier, MlpRegressor};
+pub use crate::naive_bayes::GaussianNb;
+pub use crate::svm::{LinearSvc, LinearSvr};
+pub use crate::tree::{Criterion, DecisionTree};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{Array1, Array2};
+
+    #[test]
+    fn test_prelude() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((20, 1), |(i, _)| i as f64);
+        let y = x.column(0).mapv(|v| if v < 10.0 { 0.0 } else { 1.0 });
+        let mut tree = DecisionTree::new(Criterion::Gini);
+        Fit::fit(&mut tree, &x, &y)?;
+        assert_eq!(Score::score(&tree, &x, &y)?, 1.0);
+        let linear = LinearRegression::from_parameters(Array1::from(vec![0.05]), 0.0);
+        let comparison = compare_models(&[("tree", &tree), ("linear", &linear)], &x, &y, &[Metric::Accuracy])?;
+        assert_eq!(comparison.best().name, "tree");
+        Ok(())
+    }
+
+    #[test]
+    fn test_unfitted_models_report_not_fitted() {
+        let x = Array2::zeros((3, 2));
+        let unfitted: Vec<(&str, Box<dyn Estimator>)> = vec![
+            ("linear", Box::new(LinearRegression::new(2, 0.1))),
+            ("tree", Box::new(DecisionTree::new(Criterion::Mse))),
+            ("forest", Box::new(RandomForest::new(Criterion::Mse, 3))),
+            ("boosting", Box::new(GradientBoosting::new(Loss::LeastSquares, 3))),
+            ("knn", Box::new(KnnRegressor::new(1))),
+            ("mlp", Box::new(MlpRegressor::new(vec![2]))),
+            ("svr", Box::new(LinearSvr::new(0.1))),
+            ("naive_bayes", Box::new(GaussianNb::new())),
+        ];
+        for (name, model) in unfitted {
+            assert!(matches!(model.predict(&x), Err(LinearRegressionError::NotFitted)), "{}", name);
+        }
+    }
+}
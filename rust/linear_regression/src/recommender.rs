@@ -0,0 +1,252 @@
+use crate::linalg::inverse;
+use crate::random::{RandomState, Seeded};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+// How the latent factors are trained
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FactorizationSolver {
+    // Alternating least squares: solve exactly for all user factors with the
+    // item factors fixed, then the other way round
+    Als,
+    // Stochastic gradient descent, one observed rating at a time
+    Sgd { learning_rate: f64 },
+}
+
+// Low-rank model of a sparse ratings matrix, r(u, i) ≈ μ + p_u · q_i, where
+// μ is the mean observed rating. Ratings are given as (user, item, rating)
+// triplets with users and items numbered from zero.
+#[derive(Debug, Clone)]
+pub struct MatrixFactorization {
+    n_factors: usize,
+    solver: FactorizationSolver,
+    regularization: f64,
+    epochs: usize,
+    seed: u64,
+    global_mean: f64,
+    // One row per user / item
+    user_factors: Array2<f64>,
+    item_factors: Array2<f64>,
+    history: Vec<f64>,
+}
+
+impl MatrixFactorization {
+    pub fn new(n_factors: usize) -> Self {
+        Self {
+            n_factors,
+            solver: FactorizationSolver::Als,
+            regularization: 0.1,
+            epochs: 20,
+            seed: 0,
+            global_mean: 0.0,
+            user_factors: Array2::zeros((0, 0)),
+            item_factors: Array2::zeros((0, 0)),
+            history: Vec::new(),
+        }
+    }
+
+    pub fn with_solver(mut self, solver: FactorizationSolver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    // L2 penalty on the factors. ALS scales it by each user's or item's
+    // number of ratings (ALS-WR), so heavy raters are not over-shrunk.
+    pub fn with_regularization(mut self, regularization: f64) -> Self {
+        self.regularization = regularization;
+        self
+    }
+
+    // ALS sweeps or SGD passes over the ratings
+    pub fn with_epochs(mut self, epochs: usize) -> Self {
+        self.epochs = epochs;
+        self
+    }
+
+    // Seed for the factor initialization and the SGD shuffling
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn user_factors(&self) -> &Array2<f64> {
+        &self.user_factors
+    }
+
+    pub fn item_factors(&self) -> &Array2<f64> {
+        &self.item_factors
+    }
+
+    // Training RMSE after each epoch
+    pub fn history(&self) -> &[f64] {
+        &self.history
+    }
+
+    pub fn fit(&mut self, ratings: &[(usize, usize, f64)]) -> Result<(), LinearRegressionError> {
+        if ratings.is_empty() {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.n_factors == 0 {
+            return Err(LinearRegressionError::InvalidParameter("n_factors must be at least 1"));
+        }
+        if self.regularization.is_nan() || self.regularization < 0.0 {
+            return Err(LinearRegressionError::InvalidParameter("regularization must be non-negative"));
+        }
+
+        let n_users = ratings.iter().map(|r| r.0).max().unwrap() + 1;
+        let n_items = ratings.iter().map(|r| r.1).max().unwrap() + 1;
+        let global_mean = ratings.iter().map(|r| r.2).sum::<f64>() / ratings.len() as f64;
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let scale = 0.1 / (self.n_factors as f64).sqrt();
+        let mut init = |rows| Array2::from_shape_fn((rows, self.n_factors), |_| rng.random_range(-scale..scale));
+        let mut users = init(n_users);
+        let mut items = init(n_items);
+
+        let mut history = Vec::with_capacity(self.epochs);
+        match self.solver {
+            FactorizationSolver::Als => {
+                let mut by_user = vec![Vec::new(); n_users];
+                let mut by_item = vec![Vec::new(); n_items];
+                for &(u, i, r) in ratings {
+                    by_user[u].push((i, r - global_mean));
+                    by_item[i].push((u, r - global_mean));
+                }
+                for _ in 0..self.epochs {
+                    solve_side(&mut users, &items, &by_user, self.regularization)?;
+                    solve_side(&mut items, &users, &by_item, self.regularization)?;
+                    history.push(rmse(ratings, global_mean, &users, &items));
+                }
+            }
+            FactorizationSolver::Sgd { learning_rate } => {
+                let mut order: Vec<usize> = (0..ratings.len()).collect();
+                for _ in 0..self.epochs {
+                    order.shuffle(&mut rng);
+                    for &k in &order {
+                        let (u, i, r) = ratings[k];
+                        let error = r - global_mean - users.row(u).dot(&items.row(i));
+                        let p = users.row(u).to_owned();
+                        let q = items.row(i).to_owned();
+                        let lambda = self.regularization;
+                        users.row_mut(u).zip_mut_with(&q, |p, &q| *p += learning_rate * (error * q - lambda * *p));
+                        items.row_mut(i).zip_mut_with(&p, |q, &p| *q += learning_rate * (error * p - lambda * *q));
+                    }
+                    let loss = rmse(ratings, global_mean, &users, &items);
+                    if !loss.is_finite() {
+                        return Err(LinearRegressionError::NumericalError("SGD diverged; lower the learning rate"));
+                    }
+                    history.push(loss);
+                }
+            }
+        }
+
+        self.global_mean = global_mean;
+        self.user_factors = users;
+        self.item_factors = items;
+        self.history = history;
+        Ok(())
+    }
+
+    pub fn predict(&self, user: usize, item: usize) -> Result<f64, LinearRegressionError> {
+        if self.user_factors.nrows() == 0 {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        if user >= self.user_factors.nrows() {
+            return Err(LinearRegressionError::InvalidParameter("user not seen in training"));
+        }
+        if item >= self.item_factors.nrows() {
+            return Err(LinearRegressionError::InvalidParameter("item not seen in training"));
+        }
+        Ok(self.global_mean + self.user_factors.row(user).dot(&self.item_factors.row(item)))
+    }
+
+    pub fn predict_many(&self, pairs: &[(usize, usize)]) -> Result<Array1<f64>, LinearRegressionError> {
+        pairs.iter().map(|&(user, item)| self.predict(user, item)).collect()
+    }
+}
+
+impl Seeded for MatrixFactorization {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+// With the `other` factors fixed, each row of `factors` has a closed-form
+// ridge solution over the ratings it appears in. Rows without ratings stay
+// as they are.
+fn solve_side(
+    factors: &mut Array2<f64>,
+    other: &Array2<f64>,
+    observed: &[Vec<(usize, f64)>],
+    regularization: f64,
+) -> Result<(), LinearRegressionError> {
+    let k = factors.ncols();
+    for (row, ratings) in observed.iter().enumerate() {
+        if ratings.is_empty() {
+            continue;
+        }
+        let mut gram = Array2::<f64>::eye(k) * (regularization * ratings.len() as f64).max(1e-10);
+        let mut rhs = Array1::<f64>::zeros(k);
+        for &(j, r) in ratings {
+            let q = other.row(j);
+            for a in 0..k {
+                rhs[a] += q[a] * r;
+                for b in 0..k {
+                    gram[[a, b]] += q[a] * q[b];
+                }
+            }
+        }
+        factors.row_mut(row).assign(&inverse(&gram)?.dot(&rhs));
+    }
+    Ok(())
+}
+
+fn rmse(ratings: &[(usize, usize, f64)], global_mean: f64, users: &Array2<f64>, items: &Array2<f64>) -> f64 {
+    let sse: f64 = ratings
+        .iter()
+        .map(|&(u, i, r)| (r - global_mean - users.row(u).dot(&items.row(i))).powi(2))
+        .sum();
+    (sse / ratings.len() as f64).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_factorization() -> Result<(), LinearRegressionError> {
+        // Ratings from two latent tastes for 20 users and 12 items, with every
+        // fourth entry held out. Centring on the mean adds a constant, so the
+        // model needs three factors.
+        let rating = |u: usize, i: usize| {
+            3.0 + (u % 3) as f64 * 0.5 * (i % 2) as f64 - (u % 5) as f64 * 0.3 * (i % 4) as f64 / 3.0
+        };
+        let mut train = Vec::new();
+        let mut held_out = Vec::new();
+        for u in 0..20 {
+            for i in 0..12 {
+                if (u + i) % 4 == 3 { held_out.push((u, i)) } else { train.push((u, i, rating(u, i))) }
+            }
+        }
+
+        let mut als = MatrixFactorization::new(3).with_regularization(1e-3).with_epochs(200).with_seed(1);
+        als.fit(&train)?;
+        assert!(*als.history().last().unwrap() < 0.02);
+        let predictions = als.predict_many(&held_out)?;
+        let error = held_out.iter().zip(predictions.iter()).map(|(&(u, i), p)| (p - rating(u, i)).abs());
+        assert!(error.fold(0.0, f64::max) < 0.1);
+
+        let mut sgd = MatrixFactorization::new(3)
+            .with_solver(FactorizationSolver::Sgd { learning_rate: 0.05 })
+            .with_regularization(1e-3)
+            .with_epochs(300)
+            .with_seed(1);
+        sgd.fit(&train)?;
+        assert!(sgd.history()[299] < sgd.history()[0] && sgd.history()[299] < 0.05);
+        assert!(sgd.predict(20, 0).is_err());
+        Ok(())
+    }
+}
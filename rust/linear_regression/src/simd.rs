@@ -0,0 +1,53 @@
+use crate::Float;
+
+const LANES: usize = 8;
+
+// Dot product with one accumulator per lane, so the compiler can keep them in
+// vector registers instead of serializing every add on a single running sum
+pub(crate) fn dot<F: Float>(a: &[F], b: &[F]) -> F {
+    debug_assert_eq!(a.len(), b.len());
+
+    let chunks_a = a.chunks_exact(LANES);
+    let chunks_b = b.chunks_exact(LANES);
+    let tail = chunks_a.remainder()
+        .iter()
+        .zip(chunks_b.remainder())
+        .map(|(&x, &y)| x * y)
+        .sum::<F>();
+
+    let mut acc = [F::zero(); LANES];
+    for (ca, cb) in chunks_a.zip(chunks_b) {
+        for ((s, &x), &y) in acc.iter_mut().zip(ca).zip(cb) {
+            *s += x * y;
+        }
+    }
+
+    acc.iter().copied().sum::<F>() + tail
+}
+
+// out[i] = x[i, ..] · weights + bias for a row-major `x` with weights.len() columns
+pub(crate) fn predict_rows<F: Float>(x: &[F], weights: &[F], bias: F, out: &mut [F]) {
+    if weights.is_empty() {
+        out.fill(bias);
+        return;
+    }
+
+    for (row, o) in x.chunks_exact(weights.len()).zip(out.iter_mut()) {
+        *o = dot(row, weights) + bias;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dot_matches_naive() {
+        for len in [0, 1, 7, 8, 9, 31] {
+            let a: Vec<f64> = (0..len).map(|i| i as f64 * 0.5 - 3.0).collect();
+            let b: Vec<f64> = (0..len).map(|i| (i as f64).sin()).collect();
+            let naive: f64 = a.iter().zip(&b).map(|(x, y)| x * y).sum();
+            assert!((dot(&a, &b) - naive).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use crate::optim::nelder_mead;
+use crate::LinearRegressionError;
+use ndarray::Array1;
+
+// How the seasonal component combines with level and trend
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Seasonality {
+    None,
+    // y = level + trend + season; the season swings by a fixed amount
+    Additive { period: usize },
+    // y = (level + trend) × season; the swing grows with the level.
+    // Needs a strictly positive series.
+    Multiplicative { period: usize },
+}
+
+// Exponential smoothing in Holt-Winters form: simple smoothing by default,
+// Holt's linear method with a trend, and Holt-Winters with a season.
+// Smoothing weights not fixed by the builders are chosen to minimize the
+// squared one-step forecast errors.
+#[derive(Debug, Clone)]
+pub struct ExponentialSmoothing {
+    trend: bool,
+    seasonality: Seasonality,
+    fixed: [Option<f64>; 3],
+    max_iter: usize,
+    // Smoothing weights for level, trend and season
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    level: f64,
+    slope: f64,
+    // Latest seasonal factor for each position in the period, indexed by
+    // time modulo the period
+    season: Vec<f64>,
+    fitted: Array1<f64>,
+    sse: f64,
+}
+
+// States after a smoothing pass, with the one-step forecasts along the way
+struct Pass {
+    level: f64,
+    slope: f64,
+    season: Vec<f64>,
+    fitted: Vec<f64>,
+    sse: f64,
+}
+
+impl ExponentialSmoothing {
+    pub fn new() -> Self {
+        Self {
+            trend: false,
+            seasonality: Seasonality::None,
+            fixed: [None; 3],
+            max_iter: 1000,
+            alpha: f64::NAN,
+            beta: 0.0,
+            gamma: 0.0,
+            level: f64::NAN,
+            slope: 0.0,
+            season: Vec::new(),
+            fitted: Array1::zeros(0),
+            sse: f64::NAN,
+        }
+    }
+
+    // Add an additive linear trend (Holt's method)
+    pub fn with_trend(mut self) -> Self {
+        self.trend = true;
+        self
+    }
+
+    pub fn with_seasonality(mut self, seasonality: Seasonality) -> Self {
+        self.seasonality = seasonality;
+        self
+    }
+
+    // Fix the level weight instead of optimizing it
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.fixed[0] = Some(alpha);
+        self
+    }
+
+    // Fix the trend weight instead of optimizing it
+    pub fn with_beta(mut self, beta: f64) -> Self {
+        self.fixed[1] = Some(beta);
+        self
+    }
+
+    // Fix the seasonal weight instead of optimizing it
+    pub fn with_gamma(mut self, gamma: f64) -> Self {
+        self.fixed[2] = Some(gamma);
+        self
+    }
+
+    // Nelder-Mead iterations for the weight search
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    pub fn slope(&self) -> f64 {
+        self.slope
+    }
+
+    // One-step-ahead forecasts over the training series
+    pub fn fitted(&self) -> &Array1<f64> {
+        &self.fitted
+    }
+
+    // Sum of squared one-step errors at the chosen weights
+    pub fn sse(&self) -> f64 {
+        self.sse
+    }
+
+    pub fn fit(&mut self, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        let period = match self.seasonality {
+            Seasonality::None => 0,
+            Seasonality::Additive { period } | Seasonality::Multiplicative { period } => period,
+        };
+        if period == 1 {
+            return Err(LinearRegressionError::InvalidParameter("seasonal period must be at least 2"));
+        }
+        let needed = if period > 0 { 2 * period } else { 1 + usize::from(self.trend) };
+        if y.len() < needed {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if y.iter().any(|v| !v.is_finite()) {
+            return Err(LinearRegressionError::InvalidParameter("series must be finite"));
+        }
+        if matches!(self.seasonality, Seasonality::Multiplicative { .. }) && y.iter().any(|&v| v <= 0.0) {
+            return Err(LinearRegressionError::InvalidParameter("multiplicative seasonality needs a positive series"));
+        }
+        if self.fixed.iter().flatten().any(|w| !(*w >= 0.0 && *w <= 1.0)) {
+            return Err(LinearRegressionError::InvalidParameter("smoothing weights must be in [0, 1]"));
+        }
+
+        // Weights in play; the rest stay at zero
+        let active = [true, self.trend, period > 0];
+        let free: Vec<usize> = (0..3).filter(|&k| active[k] && self.fixed[k].is_none()).collect();
+        let weights = |params: &[f64]| {
+            let mut weights = self.fixed.map(|w| w.unwrap_or(0.0));
+            for (&k, &v) in free.iter().zip(params) {
+                // Logistic map keeps the search unconstrained
+                weights[k] = 1.0 / (1.0 + (-v).exp());
+            }
+            weights
+        };
+        let y = y.to_vec();
+        let objective = |params: &[f64]| {
+            let sse = self.smooth(&y, period, weights(params)).sse;
+            if sse.is_finite() { sse } else { f64::INFINITY }
+        };
+        // Start from weights of about 0.3 for the level and 0.1 otherwise
+        let start: Vec<f64> = free.iter().map(|&k| if k == 0 { -0.85 } else { -2.2 }).collect();
+        let (params, _) = nelder_mead(objective, &start, 0.5, self.max_iter);
+
+        let [alpha, beta, gamma] = weights(&params);
+        let pass = self.smooth(&y, period, [alpha, beta, gamma]);
+        if !pass.sse.is_finite() {
+            return Err(LinearRegressionError::NumericalError("smoothing diverged"));
+        }
+        self.alpha = alpha;
+        self.beta = beta;
+        self.gamma = gamma;
+        self.level = pass.level;
+        self.slope = pass.slope;
+        self.season = pass.season;
+        self.fitted = Array1::from(pass.fitted);
+        self.sse = pass.sse;
+        Ok(())
+    }
+
+    // Forecasts for the next `horizon` steps after the training series
+    pub fn forecast(&self, horizon: usize) -> Result<Array1<f64>, LinearRegressionError> {
+        if self.level.is_nan() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        let n = self.fitted.len();
+        Ok(Array1::from_shape_fn(horizon, |h| {
+            let base = self.level + (h + 1) as f64 * self.slope;
+            match self.seasonality {
+                Seasonality::None => base,
+                Seasonality::Additive { period } => base + self.season[(n + h) % period],
+                Seasonality::Multiplicative { period } => base * self.season[(n + h) % period],
+            }
+        }))
+    }
+
+    // Runs the recursions over the whole series from initial states set by
+    // the first one or two seasons (or observations without a season)
+    fn smooth(&self, y: &[f64], period: usize, [alpha, beta, gamma]: [f64; 3]) -> Pass {
+        let multiplicative = matches!(self.seasonality, Seasonality::Multiplicative { .. });
+        let (mut level, mut slope, mut season) = if period > 0 {
+            let first = y[..period].iter().sum::<f64>() / period as f64;
+            let second = y[period..2 * period].iter().sum::<f64>() / period as f64;
+            let season: Vec<f64> =
+                y[..period].iter().map(|&v| if multiplicative { v / first } else { v - first }).collect();
+            // The first-season mean sits mid-season; step it back to just
+            // before the series starts
+            let slope = if self.trend { (second - first) / period as f64 } else { 0.0 };
+            (first - slope * (period as f64 + 1.0) / 2.0, slope, season)
+        } else {
+            let slope = if self.trend { y[1] - y[0] } else { 0.0 };
+            (y[0] - slope, slope, Vec::new())
+        };
+
+        let mut fitted = Vec::with_capacity(y.len());
+        let mut sse = 0.0;
+        for (t, &v) in y.iter().enumerate() {
+            let base = level + slope;
+            let s = if period > 0 { season[t % period] } else if multiplicative { 1.0 } else { 0.0 };
+            let forecast = if multiplicative { base * s } else { base + s };
+            fitted.push(forecast);
+            sse += (v - forecast) * (v - forecast);
+
+            let deseasonalized = if multiplicative { v / s } else { v - s };
+            let previous = level;
+            level = alpha * deseasonalized + (1.0 - alpha) * base;
+            slope = beta * (level - previous) + (1.0 - beta) * slope;
+            if period > 0 {
+                let observed = if multiplicative { v / level } else { v - level };
+                season[t % period] = gamma * observed + (1.0 - gamma) * s;
+            }
+        }
+        Pass { level, slope, season, fitted, sse }
+    }
+}
+
+impl Default for ExponentialSmoothing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_and_holt() -> Result<(), LinearRegressionError> {
+        // A noiseless line: Holt's method forecasts it exactly, while simple
+        // smoothing can only forecast a flat continuation
+        let y = Array1::from_shape_fn(30, |t| 2.0 + 0.5 * t as f64);
+        let mut holt = ExponentialSmoothing::new().with_trend();
+        holt.fit(&y)?;
+        let forecast = holt.forecast(5)?;
+        for (h, v) in forecast.iter().enumerate() {
+            assert!((v - (2.0 + 0.5 * (30 + h) as f64)).abs() < 1e-6);
+        }
+
+        let mut simple = ExponentialSmoothing::new().with_alpha(0.5);
+        simple.fit(&y)?;
+        assert_eq!(simple.alpha(), 0.5);
+        let forecast = simple.forecast(3)?;
+        assert!(forecast.iter().all(|&v| v == forecast[0]));
+        assert!(ExponentialSmoothing::new().forecast(1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_holt_winters() -> Result<(), LinearRegressionError> {
+        let pattern = [1.0, 3.0, -2.0, -2.0];
+        let trend = |t: usize| 10.0 + 0.2 * t as f64;
+        let wobble = |t: usize| 0.05 * ((t * 7) % 5) as f64;
+
+        let additive: Array1<f64> = (0..48).map(|t| trend(t) + pattern[t % 4] + wobble(t)).collect();
+        let mut model = ExponentialSmoothing::new().with_trend().with_seasonality(Seasonality::Additive { period: 4 });
+        model.fit(&additive)?;
+        let forecast = model.forecast(8)?;
+        for (h, v) in forecast.iter().enumerate() {
+            assert!((v - trend(48 + h) - pattern[(48 + h) % 4]).abs() < 0.3);
+        }
+
+        let factors = [1.2, 1.1, 0.8, 0.9];
+        let multiplicative: Array1<f64> = (0..48).map(|t| trend(t) * factors[t % 4] + wobble(t)).collect();
+        let mut model =
+            ExponentialSmoothing::new().with_trend().with_seasonality(Seasonality::Multiplicative { period: 4 });
+        model.fit(&multiplicative)?;
+        let forecast = model.forecast(4)?;
+        for (h, v) in forecast.iter().enumerate() {
+            assert!((v - trend(48 + h) * factors[(48 + h) % 4]).abs() < 0.4);
+        }
+        assert!(model.fit(&multiplicative.mapv(|v| v - 15.0)).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+use crate::stats::quantile;
+use crate::LinearRegressionError;
+use ndarray::{Array2, Axis};
+use serde::{Deserialize, Serialize};
+
+// Family of basis functions each feature is expanded into
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SplineBasis {
+    // Cubic between the knots and linear beyond the boundary knots; K knots
+    // give K - 1 columns, the first being the feature itself
+    NaturalCubic,
+    // B-splines of the given degree on the knots, with the boundary knots
+    // repeated; K knots give K + degree - 1 columns that sum to one
+    BSpline { degree: usize },
+}
+
+// Per-feature spline basis expansion. Fitting a linear model on the expanded
+// columns gives a smooth additive curve for each feature while keeping the
+// fit linear in its coefficients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplineTransformer {
+    pub basis: SplineBasis,
+    // Ascending knots for each input feature
+    pub knots: Vec<Vec<f64>>,
+}
+
+impl SplineTransformer {
+    // Place `n_knots` knots at evenly spaced quantiles of each feature,
+    // from its minimum to its maximum
+    pub fn fit(x: &Array2<f64>, basis: SplineBasis, n_knots: usize) -> Result<Self, LinearRegressionError> {
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if n_knots < 2 {
+            return Err(LinearRegressionError::InvalidParameter("need at least two knots"));
+        }
+        check_finite(x)?;
+        let knots = x
+            .axis_iter(Axis(1))
+            .map(|column| {
+                let mut sorted = column.to_vec();
+                sorted.sort_by(f64::total_cmp);
+                let mut knots: Vec<f64> =
+                    (0..n_knots).map(|k| quantile(&sorted, k as f64 / (n_knots - 1) as f64)).collect();
+                // Heavily tied features can put several quantiles on one value
+                knots.dedup();
+                knots
+            })
+            .collect();
+        Self::from_knots(basis, knots)
+    }
+
+    // Use the given knots for each feature instead of quantiles
+    pub fn from_knots(basis: SplineBasis, knots: Vec<Vec<f64>>) -> Result<Self, LinearRegressionError> {
+        let transformer = Self { basis, knots };
+        transformer.validate()?;
+        Ok(transformer)
+    }
+
+    // What `from_knots` checks, for transformers read back from a file
+    pub(crate) fn validate(&self) -> Result<(), LinearRegressionError> {
+        if let SplineBasis::BSpline { degree: 0 } = self.basis {
+            return Err(LinearRegressionError::InvalidParameter("B-spline degree must be at least 1"));
+        }
+        for feature in &self.knots {
+            if feature.len() < 2 {
+                return Err(LinearRegressionError::InvalidParameter("each feature needs two distinct knots"));
+            }
+            if feature.iter().any(|k| !k.is_finite()) {
+                return Err(LinearRegressionError::InvalidParameter("knots must be finite"));
+            }
+            if feature.windows(2).any(|w| w[0] >= w[1]) {
+                return Err(LinearRegressionError::InvalidParameter("knots must be strictly increasing"));
+            }
+        }
+        Ok(())
+    }
+
+    // Expanded columns for each input feature, in input order
+    pub fn n_output_features(&self) -> usize {
+        self.knots.iter().fold(0, |total, knots| total.saturating_add(self.columns_per_feature(knots.len())))
+    }
+
+    pub fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        if x.ncols() != self.knots.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.knots.len(),
+                found: x.ncols(),
+                context: "number of features in spline transform",
+            });
+        }
+        check_finite(x)?;
+
+        let mut out = Array2::zeros((x.nrows(), self.n_output_features()));
+        let mut offset = 0;
+        for (j, knots) in self.knots.iter().enumerate() {
+            let width = self.columns_per_feature(knots.len());
+            for (i, &v) in x.column(j).iter().enumerate() {
+                let mut row = out.slice_mut(ndarray::s![i, offset..offset + width]);
+                match self.basis {
+                    SplineBasis::NaturalCubic => natural_cubic(v, knots, row.as_slice_mut().unwrap()),
+                    SplineBasis::BSpline { degree } => b_spline(v, knots, degree, row.as_slice_mut().unwrap()),
+                }
+            }
+            offset += width;
+        }
+        Ok(out)
+    }
+
+    pub fn fit_transform(
+        x: &Array2<f64>,
+        basis: SplineBasis,
+        n_knots: usize,
+    ) -> Result<(Self, Array2<f64>), LinearRegressionError> {
+        let transformer = Self::fit(x, basis, n_knots)?;
+        let transformed = transformer.transform(x)?;
+        Ok((transformer, transformed))
+    }
+
+    // Expanded column names: B0(name), B1(name), ... for each input feature
+    pub fn feature_names(&self, input_names: &[String]) -> Vec<String> {
+        let mut names = Vec::with_capacity(self.n_output_features());
+        for (name, knots) in input_names.iter().zip(self.knots.iter()) {
+            names.extend((0..self.columns_per_feature(knots.len())).map(|k| format!("B{}({})", k, name)));
+        }
+        names
+    }
+
+    fn columns_per_feature(&self, n_knots: usize) -> usize {
+        match self.basis {
+            SplineBasis::NaturalCubic => n_knots - 1,
+            // Saturating, so an absurd degree in a hand-edited file fails the
+            // size checks instead of overflowing
+            SplineBasis::BSpline { degree } => n_knots.saturating_add(degree) - 1,
+        }
+    }
+}
+
+// Truncated power basis of Hastie, Tibshirani & Friedman (ESL 5.2.1):
+// x, then d_k(x) - d_{K-1}(x) with d_k(x) = ((x - ξ_k)³₊ - (x - ξ_K)³₊) / (ξ_K - ξ_k)
+fn natural_cubic(v: f64, knots: &[f64], out: &mut [f64]) {
+    let k = knots.len();
+    let last = knots[k - 1];
+    let cube = |t: f64| (v - t).max(0.0).powi(3);
+    let d = |i: usize| (cube(knots[i]) - cube(last)) / (last - knots[i]);
+    out[0] = v;
+    for i in 0..k - 2 {
+        out[i + 1] = d(i) - d(k - 2);
+    }
+}
+
+// A spline has no value at NaN or infinity, so such inputs are refused
+// rather than passed on as a basis row. Rows are numbered from 1.
+fn check_finite(x: &Array2<f64>) -> Result<(), LinearRegressionError> {
+    match x.indexed_iter().find(|(_, v)| !v.is_finite()) {
+        Some(((i, j), v)) => Err(LinearRegressionError::InvalidValue {
+            row: i + 1,
+            column: format!("feature {}", j),
+            message: format!("spline input must be finite, found {}", v),
+        }),
+        None => Ok(()),
+    }
+}
+
+// Cox-de Boor recursion on the clamped knot vector. Values outside the
+// boundary knots are clamped to them, so the basis extrapolates as a constant.
+fn b_spline(v: f64, knots: &[f64], degree: usize, out: &mut [f64]) {
+    let (first, last) = (knots[0], knots[knots.len() - 1]);
+    let v = v.clamp(first, last);
+    let mut t = vec![first; degree];
+    t.extend_from_slice(knots);
+    t.extend(std::iter::repeat_n(last, degree));
+
+    // Degree 0: indicator of the half-open span holding v, with the right
+    // boundary assigned to the last non-empty span
+    let mut basis = vec![0.0; t.len() - 1];
+    let span = if v >= last {
+        degree + knots.len() - 2
+    } else {
+        (0..t.len() - 1).find(|&i| t[i] <= v && v < t[i + 1]).unwrap()
+    };
+    basis[span] = 1.0;
+
+    for p in 1..=degree {
+        for i in 0..t.len() - 1 - p {
+            let left = if t[i + p] > t[i] { (v - t[i]) / (t[i + p] - t[i]) * basis[i] } else { 0.0 };
+            let right = if t[i + p + 1] > t[i + 1] {
+                (t[i + p + 1] - v) / (t[i + p + 1] - t[i + 1]) * basis[i + 1]
+            } else {
+                0.0
+            };
+            basis[i] = left + right;
+        }
+    }
+    out.copy_from_slice(&basis[..out.len()]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spline_bases() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((41, 1), |(i, _)| i as f64 / 4.0);
+
+        let (cubic, expanded) = SplineTransformer::fit_transform(&x, SplineBasis::NaturalCubic, 5)?;
+        assert_eq!(cubic.knots[0], vec![0.0, 2.5, 5.0, 7.5, 10.0]);
+        assert_eq!(expanded.ncols(), 4);
+        assert_eq!(expanded.column(0), x.column(0));
+        // Linear beyond the boundary knots: constant first differences
+        let outside = Array2::from_shape_vec((3, 1), vec![11.0, 12.0, 13.0]).unwrap();
+        let tail = cubic.transform(&outside)?;
+        for c in 0..4 {
+            let (a, b) = (tail[[1, c]] - tail[[0, c]], tail[[2, c]] - tail[[1, c]]);
+            assert!((a - b).abs() < 1e-9);
+        }
+
+        let bspline = SplineTransformer::fit(&x, SplineBasis::BSpline { degree: 3 }, 5)?;
+        let expanded = bspline.transform(&x)?;
+        assert_eq!(expanded.ncols(), 7);
+        assert!(expanded.iter().all(|&v| v >= 0.0));
+        assert!(expanded.rows().into_iter().all(|row| (row.sum() - 1.0).abs() < 1e-12));
+        // The boundary basis functions peak at the boundary knots
+        assert!((expanded[[0, 0]] - 1.0).abs() < 1e-12 && (expanded[[40, 6]] - 1.0).abs() < 1e-12);
+
+        assert!(SplineTransformer::from_knots(SplineBasis::NaturalCubic, vec![vec![1.0, 1.0]]).is_err());
+        assert!(SplineTransformer::from_knots(SplineBasis::NaturalCubic, vec![vec![1.0, f64::NAN]]).is_err());
+
+        // Non-finite inputs are errors, not panics or NaN features
+        let missing = Array2::from_shape_vec((2, 1), vec![1.0, f64::NAN]).unwrap();
+        for transformer in [&cubic, &bspline] {
+            let result = transformer.transform(&missing);
+            assert!(matches!(result, Err(LinearRegressionError::InvalidValue { row: 2, .. })));
+            assert!(transformer.transform(&Array2::from_elem((1, 1), f64::INFINITY)).is_err());
+        }
+        assert!(SplineTransformer::fit(&missing, SplineBasis::NaturalCubic, 2).is_err());
+        Ok(())
+    }
+}
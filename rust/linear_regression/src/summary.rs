@@ -0,0 +1,151 @@
+use crate::data::Dataset;
+use crate::inference::CoefficientInference;
+use crate::metrics::r2_score;
+use crate::stats::f_sf;
+use crate::{LinearRegression, LinearRegressionError};
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct CoefficientRow {
+    pub name: String,
+    pub coef: f64,
+    pub std_err: f64,
+    pub t_value: f64,
+    pub p_value: f64,
+    pub ci_lower: f64,
+    pub ci_upper: f64,
+}
+
+// Regression report in the spirit of statsmodels' OLS summary. The
+// intercept row comes first, then one row per feature.
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub target_name: String,
+    pub n_observations: usize,
+    pub df_model: usize,
+    pub df_resid: usize,
+    pub r_squared: f64,
+    pub adj_r_squared: f64,
+    pub f_statistic: f64,
+    pub f_p_value: f64,
+    pub alpha: f64,
+    pub coefficients: Vec<CoefficientRow>,
+}
+
+impl LinearRegression {
+    // Summary of the fitted model on `dataset`, with 95% confidence intervals
+    pub fn summary(&self, dataset: &Dataset) -> Result<Summary, LinearRegressionError> {
+        let alpha = 0.05;
+        let inference = CoefficientInference::new(self, &dataset.x, &dataset.y)?;
+        let ci = inference.conf_int(alpha)?;
+
+        let n = dataset.n_samples();
+        let df_model = dataset.n_features();
+        let df_resid = inference.df_resid;
+
+        let r_squared = r2_score(&self.predict(&dataset.x)?, &dataset.y);
+        let adj_r_squared = 1.0 - (1.0 - r_squared) * (n - 1) as f64 / df_resid as f64;
+        let f_statistic = (r_squared / df_model as f64) / ((1.0 - r_squared) / df_resid as f64);
+        let f_p_value = f_sf(f_statistic, df_model as f64, df_resid as f64);
+
+        let names = std::iter::once("intercept".to_string()).chain(dataset.feature_names.iter().cloned());
+        let coefficients = names
+            .enumerate()
+            .map(|(i, name)| CoefficientRow {
+                name,
+                coef: inference.coefficients[i],
+                std_err: inference.std_errors[i],
+                t_value: inference.t_values[i],
+                p_value: inference.p_values[i],
+                ci_lower: ci[[i, 0]],
+                ci_upper: ci[[i, 1]],
+            })
+            .collect();
+
+        Ok(Summary {
+            target_name: dataset.target_name.clone(),
+            n_observations: n,
+            df_model,
+            df_resid,
+            r_squared,
+            adj_r_squared,
+            f_statistic,
+            f_p_value,
+            alpha,
+            coefficients,
+        })
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rule = "=".repeat(78);
+        let thin = "-".repeat(78);
+        let name_width = self
+            .coefficients
+            .iter()
+            .map(|row| row.name.len())
+            .max()
+            .unwrap_or(0)
+            .max(9);
+
+        writeln!(f, "{:^78}", "OLS Regression Results")?;
+        writeln!(f, "{}", rule)?;
+        writeln!(f, "{:<20}{:>18}   {:<20}{:>17.4}", "Dep. Variable:", self.target_name, "R-squared:", self.r_squared)?;
+        writeln!(f, "{:<20}{:>18}   {:<20}{:>17.4}", "No. Observations:", self.n_observations, "Adj. R-squared:", self.adj_r_squared)?;
+        writeln!(f, "{:<20}{:>18}   {:<20}{:>17.4}", "Df Residuals:", self.df_resid, "F-statistic:", self.f_statistic)?;
+        writeln!(f, "{:<20}{:>18}   {:<20}{:>17.3e}", "Df Model:", self.df_model, "Prob (F-statistic):", self.f_p_value)?;
+        writeln!(f, "{}", rule)?;
+
+        let lower = format!("[{}", self.alpha / 2.0);
+        let upper = format!("{}]", 1.0 - self.alpha / 2.0);
+        writeln!(
+            f,
+            "{:<w$} {:>11} {:>11} {:>9} {:>9} {:>11} {:>11}",
+            "", "coef", "std err", "t", "P>|t|", lower, upper,
+            w = name_width
+        )?;
+        writeln!(f, "{}", thin)?;
+        for row in &self.coefficients {
+            writeln!(
+                f,
+                "{:<w$} {:>11.4} {:>11.4} {:>9.3} {:>9.3} {:>11.4} {:>11.4}",
+                row.name, row.coef, row.std_err, row.t_value, row.p_value, row.ci_lower, row.ci_upper,
+                w = name_width
+            )?;
+        }
+        write!(f, "{}", rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{arr2, Array1};
+
+    #[test]
+    fn test_summary() -> Result<(), LinearRegressionError> {
+        let dataset = Dataset {
+            x: arr2(&[[1.0], [2.0], [3.0], [4.0], [5.0]]),
+            y: Array1::from(vec![2.9, 5.1, 7.2, 8.8, 11.0]),
+            feature_names: vec!["rooms".to_string()],
+            target_name: "price".to_string(),
+        };
+        let model = LinearRegression::from_parameters(Array1::from(vec![1.99]), 1.03);
+
+        let summary = model.summary(&dataset)?;
+        assert_eq!(summary.coefficients.len(), 2);
+        assert_eq!(summary.coefficients[1].name, "rooms");
+        assert_eq!(summary.df_resid, 3);
+        // With one feature, F equals the slope's t statistic squared
+        let t = summary.coefficients[1].t_value;
+        assert!((summary.f_statistic - t * t).abs() < 1e-6 * t * t);
+        assert!((summary.f_p_value - summary.coefficients[1].p_value).abs() < 1e-10);
+        assert!(summary.adj_r_squared < summary.r_squared);
+
+        let report = summary.to_string();
+        assert!(report.contains("OLS Regression Results"));
+        assert!(report.contains("intercept"));
+        Ok(())
+    }
+}
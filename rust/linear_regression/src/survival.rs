@@ -0,0 +1,356 @@
+use crate::linalg::inverse;
+use crate::traits::{param_f64, param_usize, unknown_param, ParamMap, Params};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, Axis};
+use serde_json::Value;
+
+// Cox proportional hazards model, h(t | x) = h₀(t) exp(x·β), with the
+// baseline hazard h₀ left unspecified. β maximizes the partial likelihood
+// (Breslow's handling of tied event times) by Newton's method.
+#[derive(Debug, Clone)]
+pub struct CoxRegression {
+    max_iter: usize,
+    tol: f64,
+    l2: f64,
+    coefficients: Array1<f64>,
+    standard_errors: Array1<f64>,
+    log_likelihood: f64,
+}
+
+// Partial log-likelihood with its gradient and Hessian at one β
+struct Evaluation {
+    log_likelihood: f64,
+    gradient: Array1<f64>,
+    hessian: Array2<f64>,
+}
+
+impl CoxRegression {
+    pub fn new() -> Self {
+        Self {
+            max_iter: 100,
+            tol: 1e-9,
+            l2: 0.0,
+            coefficients: Array1::zeros(0),
+            standard_errors: Array1::zeros(0),
+            log_likelihood: f64::NAN,
+        }
+    }
+
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    // Stop once the largest gradient component falls below this
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    // Ridge penalty on β; keeps the estimates finite when some feature
+    // perfectly orders the event times
+    pub fn with_l2(mut self, l2: f64) -> Self {
+        self.l2 = l2;
+        self
+    }
+
+    // β, the log hazard ratio per unit of each feature
+    pub fn coefficients(&self) -> &Array1<f64> {
+        &self.coefficients
+    }
+
+    // exp(β): the multiplicative change in hazard per unit of each feature
+    pub fn hazard_ratios(&self) -> Array1<f64> {
+        self.coefficients.mapv(f64::exp)
+    }
+
+    // From the inverse of the observed information at the estimates
+    pub fn standard_errors(&self) -> &Array1<f64> {
+        &self.standard_errors
+    }
+
+    // Partial log-likelihood at the estimates, without the penalty
+    pub fn log_likelihood(&self) -> f64 {
+        self.log_likelihood
+    }
+
+    // `events[i]` is true when subject i had the event at `durations[i]` and
+    // false when it was censored then
+    pub fn fit(
+        &mut self,
+        x: &Array2<f64>,
+        durations: &Array1<f64>,
+        events: &Array1<bool>,
+    ) -> Result<(), LinearRegressionError> {
+        check_survival_data(x.nrows(), durations, events)?;
+        if !events.iter().any(|&e| e) {
+            return Err(LinearRegressionError::InvalidParameter("no events observed"));
+        }
+        if self.l2.is_nan() || self.l2 < 0.0 {
+            return Err(LinearRegressionError::InvalidParameter("l2 must be non-negative"));
+        }
+
+        // Centring leaves β unchanged but keeps exp(x·β) in range
+        let centred = x - &x.mean_axis(Axis(0)).unwrap();
+        let mut order: Vec<usize> = (0..x.nrows()).collect();
+        order.sort_by(|&a, &b| durations[b].total_cmp(&durations[a]));
+
+        let mut beta = Array1::zeros(x.ncols());
+        let mut current = self.evaluate(&centred, durations, events, &order, &beta);
+        for _ in 0..self.max_iter {
+            if current.gradient.iter().fold(0.0f64, |m, g| m.max(g.abs())) < self.tol {
+                break;
+            }
+            let mut information = -&current.hessian;
+            information.diag_mut().mapv_inplace(|v| v + 1e-10);
+            let direction = inverse(&information)?.dot(&current.gradient);
+
+            // Step halving keeps each update an improvement
+            let mut step = 1.0;
+            let next = loop {
+                let candidate = &beta + &(&direction * step);
+                let evaluation = self.evaluate(&centred, durations, events, &order, &candidate);
+                if evaluation.log_likelihood >= current.log_likelihood {
+                    break Some((candidate, evaluation));
+                }
+                step /= 2.0;
+                if step < 1e-10 {
+                    break None;
+                }
+            };
+            match next {
+                Some((candidate, evaluation)) => {
+                    beta = candidate;
+                    current = evaluation;
+                }
+                None => break,
+            }
+        }
+        if !current.log_likelihood.is_finite() {
+            return Err(LinearRegressionError::NumericalError("partial likelihood is not finite"));
+        }
+
+        let covariance = inverse(&-&current.hessian)?;
+        self.standard_errors = covariance.diag().mapv(|v| v.max(0.0).sqrt());
+        self.log_likelihood = current.log_likelihood + 0.5 * self.l2 * beta.dot(&beta);
+        self.coefficients = beta;
+        Ok(())
+    }
+
+    // Partial hazard exp(x·β), relative to a subject with all features zero
+    pub fn predict_risk(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        // Log-likelihood is NaN until `fit` has succeeded
+        if self.log_likelihood.is_nan() {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if x.ncols() != self.coefficients.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.coefficients.len(),
+                found: x.ncols(),
+                context: "number of features",
+            });
+        }
+        Ok(x.dot(&self.coefficients).mapv(f64::exp))
+    }
+
+    // Harrell's concordance index of the predicted risks on (x, durations, events)
+    pub fn score(
+        &self,
+        x: &Array2<f64>,
+        durations: &Array1<f64>,
+        events: &Array1<bool>,
+    ) -> Result<f64, LinearRegressionError> {
+        concordance_index(durations, events, &self.predict_risk(x)?)
+    }
+
+    // Walks subjects from the longest duration down, so the risk set at each
+    // event time is everyone added so far. All subjects at a tied time join
+    // the risk set before any of their events are counted (Breslow).
+    fn evaluate(
+        &self,
+        x: &Array2<f64>,
+        durations: &Array1<f64>,
+        events: &Array1<bool>,
+        order: &[usize],
+        beta: &Array1<f64>,
+    ) -> Evaluation {
+        let p = x.ncols();
+        let eta = x.dot(beta);
+        let mut s0 = 0.0;
+        let mut s1 = Array1::<f64>::zeros(p);
+        let mut s2 = Array2::<f64>::zeros((p, p));
+        let mut log_likelihood = 0.0;
+        let mut gradient = Array1::zeros(p);
+        let mut hessian = Array2::zeros((p, p));
+
+        let mut start = 0;
+        while start < order.len() {
+            let time = durations[order[start]];
+            let end = start + order[start..].iter().take_while(|&&i| durations[i] == time).count();
+            for &i in &order[start..end] {
+                let w = eta[i].exp();
+                let row = x.row(i);
+                s0 += w;
+                s1.scaled_add(w, &row);
+                for a in 0..p {
+                    for b in 0..p {
+                        s2[[a, b]] += w * row[a] * row[b];
+                    }
+                }
+            }
+            let n_events = order[start..end].iter().filter(|&&i| events[i]).count() as f64;
+            if n_events > 0.0 {
+                let mean = &s1 / s0;
+                for &i in order[start..end].iter().filter(|&&i| events[i]) {
+                    log_likelihood += eta[i];
+                    gradient += &x.row(i);
+                }
+                log_likelihood -= n_events * s0.ln();
+                gradient.scaled_add(-n_events, &mean);
+                for a in 0..p {
+                    for b in 0..p {
+                        hessian[[a, b]] -= n_events * (s2[[a, b]] / s0 - mean[a] * mean[b]);
+                    }
+                }
+            }
+            start = end;
+        }
+
+        log_likelihood -= 0.5 * self.l2 * beta.dot(beta);
+        gradient.scaled_add(-self.l2, beta);
+        hessian.diag_mut().mapv_inplace(|v| v - self.l2);
+        Evaluation { log_likelihood, gradient, hessian }
+    }
+}
+
+impl Default for CoxRegression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Params for CoxRegression {
+    fn get_params(&self) -> ParamMap {
+        ParamMap::from([
+            ("max_iter".to_string(), Value::from(self.max_iter)),
+            ("tol".to_string(), Value::from(self.tol)),
+            ("l2".to_string(), Value::from(self.l2)),
+        ])
+    }
+
+    fn set_param(&mut self, name: &str, value: &Value) -> Result<(), LinearRegressionError> {
+        match name {
+            "max_iter" => self.max_iter = param_usize(name, value, 0)?,
+            "tol" => self.tol = param_f64(name, value, 0.0)?,
+            "l2" => self.l2 = param_f64(name, value, 0.0)?,
+            _ => return Err(unknown_param(name)),
+        }
+        Ok(())
+    }
+}
+
+fn check_survival_data(
+    n_rows: usize,
+    durations: &Array1<f64>,
+    events: &Array1<bool>,
+) -> Result<(), LinearRegressionError> {
+    if durations.len() != n_rows {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: n_rows,
+            found: durations.len(),
+            context: "number of durations",
+        });
+    }
+    if events.len() != n_rows {
+        return Err(LinearRegressionError::DimensionMismatch {
+            expected: n_rows,
+            found: events.len(),
+            context: "number of event indicators",
+        });
+    }
+    if n_rows == 0 {
+        return Err(LinearRegressionError::EmptyData);
+    }
+    if durations.iter().any(|d| !d.is_finite()) {
+        return Err(LinearRegressionError::InvalidParameter("durations must be finite"));
+    }
+    Ok(())
+}
+
+// Harrell's C: among pairs where the shorter duration ends in an event, the
+// share in which that subject has the higher risk, counting tied risks as
+// half. 0.5 is random ordering and 1 is perfect.
+pub fn concordance_index(
+    durations: &Array1<f64>,
+    events: &Array1<bool>,
+    risk: &Array1<f64>,
+) -> Result<f64, LinearRegressionError> {
+    check_survival_data(risk.len(), durations, events)?;
+    let (mut concordant, mut comparable) = (0.0, 0.0);
+    for i in 0..risk.len() {
+        if !events[i] {
+            continue;
+        }
+        for j in 0..risk.len() {
+            if durations[j] > durations[i] {
+                comparable += 1.0;
+                if risk[i] > risk[j] {
+                    concordant += 1.0;
+                } else if risk[i] == risk[j] {
+                    concordant += 0.5;
+                }
+            }
+        }
+    }
+    if comparable == 0.0 {
+        return Err(LinearRegressionError::InvalidParameter("no comparable pairs"));
+    }
+    Ok(concordant / comparable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_cox_regression() -> Result<(), LinearRegressionError> {
+        // Exponential event times with hazard exp(0.7 x₀ - 0.5 x₁), censored
+        // by independent exponential times
+        let mut rng = StdRng::seed_from_u64(5);
+        let n = 1500;
+        let x = Array2::from_shape_fn((n, 2), |_| rng.random_range(-1.0..1.0f64));
+        let mut durations = Array1::zeros(n);
+        let mut events = Array1::from_elem(n, false);
+        for i in 0..n {
+            let hazard = (0.7 * x[[i, 0]] - 0.5 * x[[i, 1]]).exp();
+            let event_time = -rng.random_range(1e-12..1.0f64).ln() / hazard;
+            let censor_time = -rng.random_range(1e-12..1.0f64).ln() / 0.5;
+            durations[i] = event_time.min(censor_time);
+            events[i] = event_time <= censor_time;
+        }
+
+        let mut model = CoxRegression::new();
+        model.fit(&x, &durations, &events)?;
+        let beta = model.coefficients();
+        assert!((beta[0] - 0.7).abs() < 3.0 * model.standard_errors()[0]);
+        assert!((beta[1] + 0.5).abs() < 3.0 * model.standard_errors()[1]);
+        assert!(model.standard_errors().iter().all(|&se| se > 0.0 && se < 0.1));
+        assert!((model.hazard_ratios()[0] - beta[0].exp()).abs() < 1e-12);
+        let c = model.score(&x, &durations, &events)?;
+        assert!(c > 0.6 && c < 0.8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_concordance_index() -> Result<(), LinearRegressionError> {
+        let durations = Array1::from(vec![1.0, 2.0, 3.0, 4.0]);
+        let events = Array1::from(vec![true, true, false, true]);
+        // Risk falling with duration orders every comparable pair correctly
+        assert_eq!(concordance_index(&durations, &events, &Array1::from(vec![4.0, 3.0, 2.0, 1.0]))?, 1.0);
+        assert_eq!(concordance_index(&durations, &events, &Array1::from(vec![1.0, 2.0, 3.0, 4.0]))?, 0.0);
+        assert_eq!(concordance_index(&durations, &events, &Array1::from_elem(4, 1.0))?, 0.5);
+        Ok(())
+    }
+}
@@ -0,0 +1,351 @@
+use crate::traits::{BinaryScore, Fit, Predictor, Score};
+use crate::LinearRegressionError;
+use ndarray::linalg::general_mat_vec_mul;
+use ndarray::{Array1, Array2};
+
+// Weights, bias and the gradient-descent settings shared by both machines.
+// The objective is the mean loss plus ||w||² / (2 C n), which is the usual
+// `0.5 ||w||² + C * sum(loss)` scaled by 1 / (C n) so that the learning rate
+// does not depend on the number of rows.
+#[derive(Debug, Clone)]
+struct LinearMachine {
+    weights: Array1<f64>,
+    bias: f64,
+    c: f64,
+    learning_rate: f64,
+    epochs: usize,
+    history: Vec<f64>,
+}
+
+impl LinearMachine {
+    fn new() -> Self {
+        Self {
+            weights: Array1::zeros(0),
+            bias: 0.0,
+            c: 1.0,
+            learning_rate: 0.01,
+            epochs: 1000,
+            history: Vec::new(),
+        }
+    }
+
+    fn check(&self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.c.is_nan() || self.c <= 0.0 {
+            return Err(LinearRegressionError::InvalidParameter("C must be positive"));
+        }
+        // Without a single epoch `fit` would leave the model unfitted
+        if self.epochs == 0 {
+            return Err(LinearRegressionError::InvalidParameter("epochs must be at least 1"));
+        }
+        if !(self.learning_rate.is_finite() && self.learning_rate > 0.0) {
+            return Err(LinearRegressionError::InvalidParameter("learning rate must be positive and finite"));
+        }
+        Ok(())
+    }
+
+    // Full-batch subgradient descent, the same loop `LinearRegression::train`
+    // runs. `loss_grad(target, score)` returns the loss and its derivative with
+    // respect to the score.
+    fn train<L>(&mut self, x: &Array2<f64>, y: &Array1<f64>, loss_grad: L) -> Result<(), LinearRegressionError>
+    where
+        L: Fn(f64, f64) -> (f64, f64),
+    {
+        let n = x.nrows() as f64;
+        let penalty = 1.0 / (self.c * n);
+        self.weights = Array1::zeros(x.ncols());
+        self.bias = 0.0;
+        self.history.clear();
+
+        let mut scores = Array1::zeros(x.nrows());
+        let mut weight_gradients = Array1::zeros(x.ncols());
+        for _ in 0..self.epochs {
+            self.scores_into(x, &mut scores);
+
+            let mut loss = 0.0;
+            let mut bias_gradient = 0.0;
+            scores.zip_mut_with(y, |score, &target| {
+                let (l, g) = loss_grad(target, *score);
+                loss += l;
+                bias_gradient += g;
+                *score = g;
+            });
+            loss = loss / n + 0.5 * penalty * self.weights.dot(&self.weights);
+            if !loss.is_finite() {
+                return Err(LinearRegressionError::NumericalError("Loss became non-finite during training"));
+            }
+            self.history.push(loss);
+
+            // scores now holds d loss / d score for each row
+            general_mat_vec_mul(1.0 / n, &x.t(), &scores, 0.0, &mut weight_gradients);
+            weight_gradients.scaled_add(penalty, &self.weights);
+            self.weights.scaled_add(-self.learning_rate, &weight_gradients);
+            self.bias -= self.learning_rate * bias_gradient / n;
+        }
+        Ok(())
+    }
+
+    fn decision_function(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        if self.history.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        if x.ncols() != self.weights.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.weights.len(),
+                found: x.ncols(),
+                context: "number of features in prediction",
+            });
+        }
+        let mut scores = Array1::zeros(x.nrows());
+        self.scores_into(x, &mut scores);
+        Ok(scores)
+    }
+
+    fn scores_into(&self, x: &Array2<f64>, scores: &mut Array1<f64>) {
+        scores.fill(self.bias);
+        general_mat_vec_mul(1.0, x, &self.weights, 1.0, scores);
+    }
+}
+
+// Binary linear SVM on hinge loss. The smaller label is the negative class.
+#[derive(Debug, Clone)]
+pub struct LinearSvc {
+    inner: LinearMachine,
+    classes: Vec<f64>,
+}
+
+impl LinearSvc {
+    pub fn new() -> Self {
+        Self {
+            inner: LinearMachine::new(),
+            classes: Vec::new(),
+        }
+    }
+
+    // Inverse regularization strength: larger C fits the training data harder
+    pub fn with_c(mut self, c: f64) -> Self {
+        self.inner.c = c;
+        self
+    }
+
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.inner.learning_rate = learning_rate;
+        self
+    }
+
+    pub fn with_epochs(mut self, epochs: usize) -> Self {
+        self.inner.epochs = epochs;
+        self
+    }
+
+    pub fn weights(&self) -> &Array1<f64> {
+        &self.inner.weights
+    }
+
+    pub fn bias(&self) -> f64 {
+        self.inner.bias
+    }
+
+    // Objective value per epoch, before that epoch's update
+    pub fn history(&self) -> &[f64] {
+        &self.inner.history
+    }
+
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        self.inner.check(x, y)?;
+        let mut classes = y.to_vec();
+        classes.sort_by(f64::total_cmp);
+        classes.dedup();
+        if classes.len() != 2 {
+            return Err(LinearRegressionError::InvalidParameter("LinearSvc needs exactly two classes"));
+        }
+
+        let signs = y.mapv(|label| if label == classes[1] { 1.0 } else { -1.0 });
+        self.classes = classes;
+        self.inner.train(x, &signs, |sign, score| {
+            let margin = sign * score;
+            if margin < 1.0 {
+                (1.0 - margin, -sign)
+            } else {
+                (0.0, 0.0)
+            }
+        })
+    }
+
+    // Signed distance-like score w·x + b; positive means the larger label
+    pub fn decision_function(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        self.inner.decision_function(x)
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let scores = self.decision_function(x)?;
+        Ok(scores.mapv(|s| if s > 0.0 { self.classes[1] } else { self.classes[0] }))
+    }
+}
+
+impl Default for LinearSvc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Linear support vector regression on the epsilon-insensitive loss
+// max(0, |y - f(x)| - epsilon)
+#[derive(Debug, Clone)]
+pub struct LinearSvr {
+    inner: LinearMachine,
+    epsilon: f64,
+}
+
+impl LinearSvr {
+    pub fn new(epsilon: f64) -> Self {
+        Self {
+            inner: LinearMachine::new(),
+            epsilon,
+        }
+    }
+
+    pub fn with_c(mut self, c: f64) -> Self {
+        self.inner.c = c;
+        self
+    }
+
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.inner.learning_rate = learning_rate;
+        self
+    }
+
+    pub fn with_epochs(mut self, epochs: usize) -> Self {
+        self.inner.epochs = epochs;
+        self
+    }
+
+    pub fn weights(&self) -> &Array1<f64> {
+        &self.inner.weights
+    }
+
+    pub fn bias(&self) -> f64 {
+        self.inner.bias
+    }
+
+    pub fn history(&self) -> &[f64] {
+        &self.inner.history
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        self.inner.check(x, y)?;
+        if self.epsilon.is_nan() || self.epsilon < 0.0 {
+            return Err(LinearRegressionError::InvalidParameter("epsilon must be non-negative"));
+        }
+        let epsilon = self.epsilon;
+        self.inner.train(x, y, |target, score| {
+            let residual = score - target;
+            if residual.abs() > epsilon {
+                (residual.abs() - epsilon, residual.signum())
+            } else {
+                (0.0, 0.0)
+            }
+        })
+    }
+
+    pub fn decision_function(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        self.inner.decision_function(x)
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        self.decision_function(x)
+    }
+}
+
+impl Fit for LinearSvc {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        LinearSvc::fit(self, x, y)
+    }
+}
+
+impl Predictor for LinearSvc {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        LinearSvc::predict(self, x)
+    }
+}
+
+impl Score for LinearSvc {
+    fn is_classifier(&self) -> bool {
+        true
+    }
+}
+
+impl BinaryScore for LinearSvc {
+    fn binary_score(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        self.decision_function(x)
+    }
+}
+
+impl Fit for LinearSvr {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        LinearSvr::fit(self, x, y)
+    }
+}
+
+impl Predictor for LinearSvr {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        LinearSvr::predict(self, x)
+    }
+}
+
+impl Score for LinearSvr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_linear_svc() -> Result<(), LinearRegressionError> {
+        let x = arr2(&[[2.0, 2.5], [3.0, 1.5], [2.5, 3.0], [-2.0, -1.0], [-1.5, -3.0], [-3.0, -2.0]]);
+        let y = Array1::from(vec![1.0, 1.0, 1.0, 0.0, 0.0, 0.0]);
+
+        let mut svc = LinearSvc::new().with_c(10.0).with_learning_rate(0.1);
+        svc.fit(&x, &y)?;
+        assert_eq!(svc.predict(&x)?, y);
+
+        // Every training point ends up outside the margin
+        let scores = svc.decision_function(&x)?;
+        assert!(scores.iter().zip(y.iter()).all(|(&s, &label)| if label == 1.0 { s > 0.9 } else { s < -0.9 }));
+        let history = svc.history();
+        assert!(history[history.len() - 1] < history[0]);
+
+        assert!(LinearSvc::new().fit(&x, &Array1::zeros(6)).is_err());
+        assert!(LinearSvc::new().with_c(0.0).fit(&x, &y).is_err());
+        assert!(LinearSvc::new().with_epochs(0).fit(&x, &y).is_err());
+        assert!(LinearSvc::new().with_learning_rate(f64::NAN).fit(&x, &y).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_linear_svr() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((30, 1), |(i, _)| i as f64 / 10.0);
+        let y = x.column(0).mapv(|v| 3.0 * v - 1.0);
+
+        let mut svr = LinearSvr::new(0.1).with_c(100.0).with_learning_rate(0.1).with_epochs(3000);
+        svr.fit(&x, &y)?;
+        let residuals = &svr.predict(&x)? - &y;
+        assert!(residuals.iter().all(|r| r.abs() < 0.2));
+        assert!((svr.weights()[0] - 3.0).abs() < 0.1);
+        assert!(LinearSvr::new(0.1).with_learning_rate(-0.1).fit(&x, &y).is_err());
+        Ok(())
+    }
+}
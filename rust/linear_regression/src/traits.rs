@@ -0,0 +1,156 @@
+use crate::pipeline::Pipeline;
+use crate::random::{RandomState, Seeded};
+use crate::{Float, LinearRegression, LinearRegressionError};
+use ndarray::{Array1, Array2};
+use serde_json::Value;
+
+// The estimator traits live in ml_core so other crates can implement them;
+// this module adds the implementations for the models defined here.
+pub use ml_core::traits::{BinaryScore, Estimator, Fit, ParamMap, Params, Predictor, Score, WarmStart};
+pub(crate) use ml_core::traits::{param_error, param_f64, param_usize, positive_column, unknown_param};
+
+impl<F: Float> Predictor<Array2<F>, Array1<F>> for LinearRegression<F> {
+    fn predict(&self, x: &Array2<F>) -> Result<Array1<F>, LinearRegressionError> {
+        LinearRegression::predict(self, x)
+    }
+}
+
+impl<F: Float> Fit<Array2<F>, Array1<F>> for LinearRegression<F> {
+    // Starts again from the configured initial weights, sized to `x`, then
+    // runs `epochs()` epochs
+    fn fit(&mut self, x: &Array2<F>, y: &Array1<F>) -> Result<(), LinearRegressionError> {
+        self.initialize(x.ncols())?;
+        self.train(x, y, self.epochs())?;
+        Ok(())
+    }
+}
+
+impl Score for LinearRegression {}
+
+// Reseeds a random weight initialization; zero-initialized models are unchanged
+impl<F> Seeded for LinearRegression<F> {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.weight_init.set_random_state(state);
+    }
+}
+
+impl WarmStart for LinearRegression {
+    // Unfitted models start from the initial weights sized to `x`, as in `fit`
+    fn fit_more(&mut self, x: &Array2<f64>, y: &Array1<f64>, epochs: usize) -> Result<(), LinearRegressionError> {
+        if !self.is_fitted() {
+            self.initialize(x.ncols())?;
+        }
+        self.train(x, y, epochs)?;
+        Ok(())
+    }
+}
+
+impl<F: Float> Params for LinearRegression<F> {
+    fn get_params(&self) -> ParamMap {
+        ParamMap::from([
+            ("learning_rate".to_string(), Value::from(self.learning_rate().to_f64().unwrap())),
+            ("epochs".to_string(), Value::from(self.epochs())),
+        ])
+    }
+
+    fn set_param(&mut self, name: &str, value: &Value) -> Result<(), LinearRegressionError> {
+        match name {
+            "learning_rate" => {
+                let learning_rate = param_f64(name, value, 0.0)?;
+                if learning_rate == 0.0 {
+                    return Err(param_error(name, "must be positive"));
+                }
+                self.learning_rate = F::from_f64(learning_rate).unwrap();
+            }
+            "epochs" => self.epochs = param_usize(name, value, 1)?,
+            _ => return Err(unknown_param(name)),
+        }
+        Ok(())
+    }
+}
+
+impl Predictor for Pipeline {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        Pipeline::predict(self, x)
+    }
+}
+
+impl Score for Pipeline {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Written once against the traits, usable for any data type a model
+    // supports
+    fn fit_predict<X, Y, M>(model: &mut M, x: &X, y: &Y) -> Result<Y, LinearRegressionError>
+    where
+        M: Fit<X, Y> + Predictor<X, Y>,
+    {
+        model.fit(x, y)?;
+        model.predict(x)
+    }
+
+    #[test]
+    fn test_generic_estimator() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((4, 1), |(i, _)| i as f64);
+        let y = x.column(0).mapv(|v| 2.0 * v + 1.0);
+        let mut model = LinearRegression::new(1, 0.1).with_epochs(2000);
+        let predicted = fit_predict(&mut model, &x, &y)?;
+        assert!(predicted.iter().zip(y.iter()).all(|(p, t)| (p - t).abs() < 1e-3));
+
+        let mut single = LinearRegression::<f32>::new(1, 0.1).with_epochs(2000);
+        let predicted = fit_predict(&mut single, &x.mapv(|v| v as f32), &y.mapv(|v| v as f32))?;
+        assert!(predicted.iter().zip(y.iter()).all(|(&p, &t)| (p as f64 - t).abs() < 1e-2));
+
+        // The default parameters keep the f64 interface object safe
+        let boxed: Box<dyn Estimator> = Box::new(model);
+        assert_eq!(boxed.predict(&x)?.len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_score() -> Result<(), LinearRegressionError> {
+        use crate::tree::{Criterion, DecisionTree};
+        let x = Array2::from_shape_fn((8, 1), |(i, _)| i as f64);
+        let labels = Array1::from(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+
+        // A stump gets the classes right but explains only part of the variance
+        let mut classifier = DecisionTree::new(Criterion::Gini).with_max_depth(1);
+        classifier.fit(&x, &labels)?;
+        assert_eq!(Score::score(&classifier, &x, &labels)?, 1.0);
+        let mut regressor = DecisionTree::new(Criterion::Mse).with_max_depth(1);
+        regressor.fit(&x, &x.column(0).to_owned())?;
+        let r2 = Score::score(&regressor, &x, &x.column(0).to_owned())?;
+        assert!(r2 > 0.7 && r2 < 1.0);
+
+        let scored: &dyn Score = &classifier;
+        assert!(scored.is_classifier() && !regressor.is_classifier());
+        assert!(scored.score(&x, &labels.slice(ndarray::s![..4]).to_owned()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_params() -> Result<(), LinearRegressionError> {
+        let mut model = LinearRegression::new(2, 0.1);
+        let params = model.get_params();
+        assert_eq!(serde_json::to_string(&params)?, r#"{"epochs":1000,"learning_rate":0.1}"#);
+
+        model.set_params(&ParamMap::from([
+            ("learning_rate".to_string(), Value::from(0.05)),
+            ("epochs".to_string(), Value::from(20)),
+        ]))?;
+        assert_eq!((model.learning_rate(), model.epochs()), (0.05, 20));
+
+        // A bad entry rejects the whole update
+        let bad = ParamMap::from([("epochs".to_string(), Value::from(5)), ("alpha".to_string(), Value::from(1.0))]);
+        match model.set_params(&bad) {
+            Err(LinearRegressionError::Parameter { name, .. }) => assert_eq!(name, "alpha"),
+            _ => panic!("Expected unknown parameter error"),
+        }
+        assert_eq!(model.epochs(), 20);
+        assert!(model.set_param("learning_rate", &Value::from(-1.0)).is_err());
+        assert!(model.set_param("epochs", &Value::from("many")).is_err());
+        Ok(())
+    }
+}
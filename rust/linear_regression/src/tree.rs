@@ -0,0 +1,733 @@
+use crate::traits::{positive_column, BinaryScore, Fit, Predictor, Score};
+use crate::random::{RandomState, Seeded};
+use crate::LinearRegressionError;
+use ndarray::{Array1, Array2, ArrayView1};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Criterion {
+    // Classification
+    Gini,
+    Entropy,
+    // Regression, the within-node variance
+    Mse,
+}
+
+impl Criterion {
+    pub fn is_classification(&self) -> bool {
+        !matches!(self, Self::Mse)
+    }
+
+    fn class_impurity(&self, counts: &[f64], n: f64) -> f64 {
+        match self {
+            Self::Gini => 1.0 - counts.iter().map(|&c| (c / n) * (c / n)).sum::<f64>(),
+            Self::Entropy => -counts
+                .iter()
+                .filter(|&&c| c > 0.0)
+                .map(|&c| (c / n) * (c / n).log2())
+                .sum::<f64>(),
+            Self::Mse => unreachable!("mse is not a class impurity"),
+        }
+    }
+}
+
+// How split thresholds are chosen for each candidate feature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Splitter {
+    // The best threshold between consecutive distinct values
+    Best,
+    // One threshold drawn uniformly between the feature's minimum and maximum
+    // in the node, as in extremely randomized trees
+    Random,
+}
+
+// Rows with `x[feature] <= threshold` go to `left`, the rest to `right`.
+// Both are indices into `DecisionTree::nodes`.
+#[derive(Debug, Clone)]
+pub struct Split {
+    pub feature: usize,
+    pub threshold: f64,
+    pub left: usize,
+    pub right: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub depth: usize,
+    pub n_samples: usize,
+    pub impurity: f64,
+    // Mean target for regression, majority class label for classification
+    pub value: f64,
+    // Class proportions in `classes()` order; empty for regression
+    pub distribution: Vec<f64>,
+    // None for leaves
+    pub split: Option<Split>,
+}
+
+impl Node {
+    pub fn is_leaf(&self) -> bool {
+        self.split.is_none()
+    }
+}
+
+// CART decision tree. The fitted tree is stored as a flat list of nodes with
+// the root at index 0, so it can be walked without recursion.
+#[derive(Debug, Clone)]
+pub struct DecisionTree {
+    criterion: Criterion,
+    max_depth: Option<usize>,
+    min_samples_leaf: usize,
+    min_impurity_decrease: f64,
+    max_features: Option<usize>,
+    splitter: Splitter,
+    seed: u64,
+    n_features: usize,
+    feature_names: Option<Vec<String>>,
+    classes: Vec<f64>,
+    nodes: Vec<Node>,
+}
+
+// Rows of a node still to be built: `indices[start..end]` of the working buffer
+struct Pending {
+    start: usize,
+    end: usize,
+    depth: usize,
+    parent: Option<(usize, bool)>,
+}
+
+struct BestSplit {
+    feature: usize,
+    threshold: f64,
+    n_left: usize,
+    child_impurity: f64,
+}
+
+impl DecisionTree {
+    pub fn new(criterion: Criterion) -> Self {
+        Self {
+            criterion,
+            max_depth: None,
+            min_samples_leaf: 1,
+            min_impurity_decrease: 0.0,
+            max_features: None,
+            splitter: Splitter::Best,
+            seed: 0,
+            n_features: 0,
+            feature_names: None,
+            classes: Vec::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.min_samples_leaf = min_samples_leaf;
+        self
+    }
+
+    // A node is only split when the weighted impurity decrease
+    // n_t / n * (impurity - n_l / n_t * left_impurity - n_r / n_t * right_impurity)
+    // is at least this large
+    pub fn with_min_impurity_decrease(mut self, min_impurity_decrease: f64) -> Self {
+        self.min_impurity_decrease = min_impurity_decrease;
+        self
+    }
+
+    // Consider only a random subset of this many features at each split, as
+    // random forests do. By default every feature is searched.
+    pub fn with_max_features(mut self, max_features: usize) -> Self {
+        self.max_features = Some(max_features);
+        self
+    }
+
+    pub fn with_splitter(mut self, splitter: Splitter) -> Self {
+        self.splitter = splitter;
+        self
+    }
+
+    // Seed for the feature subsampling and random thresholds; unused by the
+    // best splitter when every feature is searched
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    // Column names for `named_feature_importances` and the exports
+    pub fn with_feature_names(mut self, names: Vec<String>) -> Self {
+        self.feature_names = Some(names);
+        self
+    }
+
+    pub fn feature_names(&self) -> Option<&[String]> {
+        self.feature_names.as_deref()
+    }
+
+    pub fn criterion(&self) -> Criterion {
+        self.criterion
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    // For ensembles that refit leaf values after growing, e.g. Newton steps in boosting
+    pub(crate) fn nodes_mut(&mut self) -> &mut [Node] {
+        &mut self.nodes
+    }
+
+    pub fn root(&self) -> Option<&Node> {
+        self.nodes.first()
+    }
+
+    pub fn n_features(&self) -> usize {
+        self.n_features
+    }
+
+    // Distinct labels seen in training, in ascending order; empty for regression
+    pub fn classes(&self) -> &[f64] {
+        &self.classes
+    }
+
+    pub fn depth(&self) -> usize {
+        self.nodes.iter().map(|node| node.depth).max().unwrap_or(0)
+    }
+
+    pub fn n_leaves(&self) -> usize {
+        self.nodes.iter().filter(|node| node.is_leaf()).count()
+    }
+
+    // Total impurity decrease contributed by each feature over all splits,
+    // weighted by node size and normalized to sum to one
+    pub fn feature_importances(&self) -> Array1<f64> {
+        let mut importances = Array1::zeros(self.n_features);
+        for node in &self.nodes {
+            if let Some(split) = &node.split {
+                let (left, right) = (&self.nodes[split.left], &self.nodes[split.right]);
+                importances[split.feature] += node.n_samples as f64 * node.impurity
+                    - left.n_samples as f64 * left.impurity
+                    - right.n_samples as f64 * right.impurity;
+            }
+        }
+        let total = importances.sum();
+        if total > 0.0 {
+            importances /= total;
+        }
+        importances
+    }
+
+    // (name, importance) pairs in column order, named like the linear
+    // model's `coefficients`
+    pub fn named_feature_importances(&self) -> Vec<(String, f64)> {
+        let names = crate::column_names(self.feature_names.as_deref(), self.n_features);
+        names.into_iter().zip(self.feature_importances()).collect()
+    }
+
+    pub fn importance_for(&self, name: &str) -> Option<f64> {
+        self.named_feature_importances().into_iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    // The fitted tree as indented rules, left (<=) branches first:
+    //
+    //     |--- sqft <= 1550.0000
+    //     |   |--- value: 230.0000 (samples: 4)
+    //     |--- sqft >  1550.0000
+    //     |   |--- value: 315.0000 (samples: 6)
+    //
+    // Uses the tree's own feature names when none are given, and x0, x1, ...
+    // without either
+    pub fn export_text(&self, feature_names: Option<&[String]>) -> Result<String, LinearRegressionError> {
+        let names = self.export_names(feature_names)?;
+        let mut out = String::new();
+        self.write_text(&mut out, &names, 0, 0);
+        Ok(out)
+    }
+
+    fn write_text(&self, out: &mut String, names: &[String], id: usize, level: usize) {
+        let node = &self.nodes[id];
+        let indent = "|   ".repeat(level);
+        match &node.split {
+            None => out.push_str(&format!("{}|--- {} (samples: {})\n", indent, self.leaf_label(node), node.n_samples)),
+            Some(split) => {
+                let name = &names[split.feature];
+                out.push_str(&format!("{}|--- {} <= {:.4}\n", indent, name, split.threshold));
+                self.write_text(out, names, split.left, level + 1);
+                out.push_str(&format!("{}|--- {} >  {:.4}\n", indent, name, split.threshold));
+                self.write_text(out, names, split.right, level + 1);
+            }
+        }
+    }
+
+    // The fitted tree in Graphviz DOT, one box per node with its rule (or
+    // prediction), impurity and sample count, e.g. for `dot -Tpng tree.dot`
+    pub fn export_dot(&self, feature_names: Option<&[String]>) -> Result<String, LinearRegressionError> {
+        let names = self.export_names(feature_names)?;
+        let impurity = match self.criterion {
+            Criterion::Gini => "gini",
+            Criterion::Entropy => "entropy",
+            Criterion::Mse => "mse",
+        };
+        let mut out = String::from("digraph Tree {\nnode [shape=box, fontname=\"helvetica\"];\n");
+        for (id, node) in self.nodes.iter().enumerate() {
+            let rule = match &node.split {
+                Some(split) => format!("{} <= {:.4}", names[split.feature], split.threshold),
+                None => self.leaf_label(node),
+            };
+            // Quotes and backslashes in feature names would end the label early
+            let rule = rule.replace('\\', "\\\\").replace('"', "\\\"");
+            let label = format!("{}\\n{} = {:.4}\\nsamples = {}", rule, impurity, node.impurity, node.n_samples);
+            out.push_str(&format!("{} [label=\"{}\"];\n", id, label));
+            if let Some(split) = &node.split {
+                out.push_str(&format!("{} -> {} [label=\"True\"];\n", id, split.left));
+                out.push_str(&format!("{} -> {} [label=\"False\"];\n", id, split.right));
+            }
+        }
+        out.push_str("}\n");
+        Ok(out)
+    }
+
+    fn export_names(&self, feature_names: Option<&[String]>) -> Result<Vec<String>, LinearRegressionError> {
+        if self.nodes.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        match feature_names {
+            Some(names) if names.len() != self.n_features => Err(LinearRegressionError::DimensionMismatch {
+                expected: self.n_features,
+                found: names.len(),
+                context: "number of feature names",
+            }),
+            Some(names) => Ok(names.to_vec()),
+            None => Ok(crate::column_names(self.feature_names.as_deref(), self.n_features)),
+        }
+    }
+
+    fn leaf_label(&self, node: &Node) -> String {
+        if self.criterion.is_classification() {
+            format!("class: {}", node.value)
+        } else {
+            format!("value: {:.4}", node.value)
+        }
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        if x.nrows() != y.len() {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if x.nrows() == 0 {
+            return Err(LinearRegressionError::EmptyData);
+        }
+        if self.min_samples_leaf == 0 {
+            return Err(LinearRegressionError::InvalidParameter("min_samples_leaf must be at least 1"));
+        }
+        if self.min_impurity_decrease.is_nan() || self.min_impurity_decrease < 0.0 {
+            return Err(LinearRegressionError::InvalidParameter("min_impurity_decrease must be non-negative"));
+        }
+        if self.max_features == Some(0) {
+            return Err(LinearRegressionError::InvalidParameter("max_features must be at least 1"));
+        }
+
+        // Classification works on class indices rather than raw labels
+        let targets = if self.criterion.is_classification() {
+            let mut classes = y.to_vec();
+            classes.sort_by(f64::total_cmp);
+            classes.dedup();
+            let targets = y.mapv(|label| classes.binary_search_by(|c| c.total_cmp(&label)).unwrap() as f64);
+            self.classes = classes;
+            targets
+        } else {
+            self.classes.clear();
+            y.clone()
+        };
+
+        self.n_features = x.ncols();
+        self.nodes.clear();
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut indices: Vec<usize> = (0..x.nrows()).collect();
+        let mut stack = vec![Pending { start: 0, end: x.nrows(), depth: 0, parent: None }];
+        while let Some(pending) = stack.pop() {
+            let rows = &mut indices[pending.start..pending.end];
+            let mut node = self.leaf(rows, &targets, pending.depth);
+            let id = self.nodes.len();
+            if let Some((parent, is_left)) = pending.parent {
+                let split = self.nodes[parent].split.as_mut().unwrap();
+                if is_left {
+                    split.left = id;
+                } else {
+                    split.right = id;
+                }
+            }
+
+            let can_split = node.impurity > 0.0
+                && rows.len() >= 2 * self.min_samples_leaf
+                && self.max_depth.is_none_or(|max| pending.depth < max);
+            if can_split {
+                let features = match self.max_features {
+                    Some(k) if k < x.ncols() => rand::seq::index::sample(&mut rng, x.ncols(), k).into_vec(),
+                    _ => (0..x.ncols()).collect(),
+                };
+                let best = match self.splitter {
+                    Splitter::Best => self.best_split(x, &targets, rows, &features),
+                    Splitter::Random => self.random_split(x, &targets, rows, &features, &mut rng),
+                };
+                // Ignore "improvements" that are only rounding noise
+                let best = best.filter(|b| node.impurity - b.child_impurity > 1e-12 * node.impurity);
+                if let Some(best) = best {
+                    let decrease = rows.len() as f64 / x.nrows() as f64 * (node.impurity - best.child_impurity);
+                    if decrease >= self.min_impurity_decrease {
+                        partition(rows, |i| x[[i, best.feature]] <= best.threshold);
+                        node.split = Some(Split { feature: best.feature, threshold: best.threshold, left: 0, right: 0 });
+                        let mid = pending.start + best.n_left;
+                        let depth = pending.depth + 1;
+                        // Right is pushed first so the left subtree is numbered first
+                        stack.push(Pending { start: mid, end: pending.end, depth, parent: Some((id, false)) });
+                        stack.push(Pending { start: pending.start, end: mid, depth, parent: Some((id, true)) });
+                    }
+                }
+            }
+            self.nodes.push(node);
+        }
+        Ok(())
+    }
+
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        let leaves = self.apply(x)?;
+        Ok(leaves.iter().map(|&leaf| self.nodes[leaf].value).collect())
+    }
+
+    // Class probabilities, one row per sample and one column per class in `classes()` order
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, LinearRegressionError> {
+        if !self.criterion.is_classification() {
+            return Err(LinearRegressionError::InvalidParameter(
+                "predict_proba requires a classification criterion"
+            ));
+        }
+        let leaves = self.apply(x)?;
+        let mut proba = Array2::zeros((x.nrows(), self.classes.len()));
+        for (mut row, &leaf) in proba.rows_mut().into_iter().zip(leaves.iter()) {
+            row.assign(&ArrayView1::from(&self.nodes[leaf].distribution));
+        }
+        Ok(proba)
+    }
+
+    // Index of the leaf each row of `x` ends up in
+    pub fn apply(&self, x: &Array2<f64>) -> Result<Vec<usize>, LinearRegressionError> {
+        if self.nodes.is_empty() {
+            return Err(LinearRegressionError::NotFitted);
+        }
+        if x.ncols() != self.n_features {
+            return Err(LinearRegressionError::DimensionMismatch {
+                expected: self.n_features,
+                found: x.ncols(),
+                context: "number of features in prediction",
+            });
+        }
+
+        Ok(x
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let mut id = 0;
+                while let Some(split) = &self.nodes[id].split {
+                    id = if row[split.feature] <= split.threshold { split.left } else { split.right };
+                }
+                id
+            })
+            .collect())
+    }
+
+    fn leaf(&self, rows: &[usize], targets: &Array1<f64>, depth: usize) -> Node {
+        let n = rows.len() as f64;
+        if self.criterion.is_classification() {
+            let mut counts = vec![0.0; self.classes.len()];
+            for &i in rows {
+                counts[targets[i] as usize] += 1.0;
+            }
+            let majority = (1..counts.len()).fold(0, |best, c| if counts[c] > counts[best] { c } else { best });
+            Node {
+                depth,
+                n_samples: rows.len(),
+                impurity: self.criterion.class_impurity(&counts, n),
+                value: self.classes[majority],
+                distribution: counts.iter().map(|&c| c / n).collect(),
+                split: None,
+            }
+        } else {
+            let mean = rows.iter().map(|&i| targets[i]).sum::<f64>() / n;
+            let impurity = rows.iter().map(|&i| (targets[i] - mean).powi(2)).sum::<f64>() / n;
+            Node {
+                depth,
+                n_samples: rows.len(),
+                impurity,
+                value: mean,
+                distribution: Vec::new(),
+                split: None,
+            }
+        }
+    }
+
+    // Exhaustive search over `features` and every threshold between
+    // consecutive distinct values for the lowest weighted child impurity
+    fn best_split(
+        &self,
+        x: &Array2<f64>,
+        targets: &Array1<f64>,
+        rows: &[usize],
+        features: &[usize],
+    ) -> Option<BestSplit> {
+        let n = rows.len();
+        let mut order = rows.to_vec();
+        let mut best: Option<BestSplit> = None;
+        let mut left_counts = vec![0.0; self.classes.len()];
+        let mut right_counts = vec![0.0; self.classes.len()];
+
+        for &feature in features {
+            order.sort_by(|&a, &b| x[[a, feature]].total_cmp(&x[[b, feature]]));
+
+            let (mut left_sum, mut left_sq) = (0.0, 0.0);
+            let (total_sum, total_sq) = order
+                .iter()
+                .fold((0.0, 0.0), |(s, q), &i| (s + targets[i], q + targets[i] * targets[i]));
+            if self.criterion.is_classification() {
+                left_counts.fill(0.0);
+                right_counts.fill(0.0);
+                for &i in &order {
+                    right_counts[targets[i] as usize] += 1.0;
+                }
+            }
+
+            for pos in 0..n - 1 {
+                let i = order[pos];
+                let t = targets[i];
+                if self.criterion.is_classification() {
+                    left_counts[t as usize] += 1.0;
+                    right_counts[t as usize] -= 1.0;
+                } else {
+                    left_sum += t;
+                    left_sq += t * t;
+                }
+
+                let n_left = pos + 1;
+                let n_right = n - n_left;
+                if n_left < self.min_samples_leaf || n_right < self.min_samples_leaf {
+                    continue;
+                }
+                let (value, next) = (x[[i, feature]], x[[order[pos + 1], feature]]);
+                if value == next {
+                    continue;
+                }
+
+                let (nl, nr) = (n_left as f64, n_right as f64);
+                let (left_impurity, right_impurity) = if self.criterion.is_classification() {
+                    (
+                        self.criterion.class_impurity(&left_counts, nl),
+                        self.criterion.class_impurity(&right_counts, nr),
+                    )
+                } else {
+                    let (right_sum, right_sq) = (total_sum - left_sum, total_sq - left_sq);
+                    (
+                        (left_sq / nl - (left_sum / nl).powi(2)).max(0.0),
+                        (right_sq / nr - (right_sum / nr).powi(2)).max(0.0),
+                    )
+                };
+                let child_impurity = (nl * left_impurity + nr * right_impurity) / n as f64;
+
+                if best.as_ref().is_none_or(|b| child_impurity < b.child_impurity) {
+                    // The midpoint can round up to `next` when the values are adjacent floats
+                    let mid = value + (next - value) / 2.0;
+                    let threshold = if mid < next { mid } else { value };
+                    best = Some(BestSplit { feature, threshold, n_left, child_impurity });
+                }
+            }
+        }
+        best
+    }
+
+    // One random threshold per feature, keeping the feature whose split gives
+    // the lowest weighted child impurity
+    fn random_split(
+        &self,
+        x: &Array2<f64>,
+        targets: &Array1<f64>,
+        rows: &[usize],
+        features: &[usize],
+        rng: &mut StdRng,
+    ) -> Option<BestSplit> {
+        let n = rows.len();
+        let mut best: Option<BestSplit> = None;
+        let mut left_counts = vec![0.0; self.classes.len()];
+        let mut right_counts = vec![0.0; self.classes.len()];
+
+        for &feature in features {
+            let (min, max) = rows.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &i| {
+                (lo.min(x[[i, feature]]), hi.max(x[[i, feature]]))
+            });
+            if min >= max {
+                continue;
+            }
+            let threshold = rng.random_range(min..max);
+
+            left_counts.fill(0.0);
+            right_counts.fill(0.0);
+            let (mut left, mut right) = ((0.0, 0.0, 0usize), (0.0, 0.0, 0usize));
+            for &i in rows {
+                let t = targets[i];
+                let (side, counts) = if x[[i, feature]] <= threshold {
+                    (&mut left, &mut left_counts)
+                } else {
+                    (&mut right, &mut right_counts)
+                };
+                side.0 += t;
+                side.1 += t * t;
+                side.2 += 1;
+                if self.criterion.is_classification() {
+                    counts[t as usize] += 1.0;
+                }
+            }
+            if left.2 < self.min_samples_leaf || right.2 < self.min_samples_leaf {
+                continue;
+            }
+
+            let (nl, nr) = (left.2 as f64, right.2 as f64);
+            let (left_impurity, right_impurity) = if self.criterion.is_classification() {
+                (
+                    self.criterion.class_impurity(&left_counts, nl),
+                    self.criterion.class_impurity(&right_counts, nr),
+                )
+            } else {
+                (
+                    (left.1 / nl - (left.0 / nl).powi(2)).max(0.0),
+                    (right.1 / nr - (right.0 / nr).powi(2)).max(0.0),
+                )
+            };
+            let child_impurity = (nl * left_impurity + nr * right_impurity) / n as f64;
+            if best.as_ref().is_none_or(|b| child_impurity < b.child_impurity) {
+                best = Some(BestSplit { feature, threshold, n_left: left.2, child_impurity });
+            }
+        }
+        best
+    }
+}
+
+impl Seeded for DecisionTree {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+// Reorder `rows` so that the rows matching `goes_left` come first
+fn partition(rows: &mut [usize], goes_left: impl Fn(usize) -> bool) {
+    let mut next = 0;
+    for i in 0..rows.len() {
+        if goes_left(rows[i]) {
+            rows.swap(next, i);
+            next += 1;
+        }
+    }
+}
+
+impl Fit for DecisionTree {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array1<f64>) -> Result<(), LinearRegressionError> {
+        DecisionTree::fit(self, x, y)
+    }
+}
+
+impl Predictor for DecisionTree {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        DecisionTree::predict(self, x)
+    }
+}
+
+impl Score for DecisionTree {
+    fn is_classifier(&self) -> bool {
+        self.criterion.is_classification()
+    }
+}
+
+impl BinaryScore for DecisionTree {
+    fn binary_score(&self, x: &Array2<f64>) -> Result<Array1<f64>, LinearRegressionError> {
+        positive_column(self.predict_proba(x)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_regression_tree() -> Result<(), LinearRegressionError> {
+        let x = Array2::from_shape_fn((20, 1), |(i, _)| i as f64);
+        let y = x.column(0).mapv(|v| if v < 7.0 { 1.0 } else if v < 15.0 { 5.0 } else { -2.0 });
+
+        let mut tree = DecisionTree::new(Criterion::Mse);
+        tree.fit(&x, &y)?;
+        assert_eq!(tree.predict(&x)?, y);
+        assert_eq!(tree.n_leaves(), 3);
+        let root = tree.root().unwrap().split.as_ref().unwrap();
+        assert!(root.threshold == 6.5 || root.threshold == 14.5);
+
+        let mut stump = DecisionTree::new(Criterion::Mse).with_max_depth(1);
+        stump.fit(&x, &y)?;
+        assert_eq!(stump.depth(), 1);
+        assert_eq!(stump.n_leaves(), 2);
+
+        let mut coarse = DecisionTree::new(Criterion::Mse).with_min_samples_leaf(8);
+        coarse.fit(&x, &y)?;
+        assert!(coarse.nodes().iter().filter(|n| n.is_leaf()).all(|n| n.n_samples >= 8));
+
+        // The only available split is worth far less than this
+        let mut pruned = DecisionTree::new(Criterion::Mse).with_min_impurity_decrease(100.0);
+        pruned.fit(&x, &y)?;
+        assert_eq!(pruned.n_leaves(), 1);
+        assert_eq!(pruned.feature_importances(), Array1::<f64>::zeros(1));
+
+        let names = vec!["t".to_string()];
+        let text = stump.export_text(Some(&names))?;
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("|--- t <= ") && lines[2].starts_with("|--- t >  "));
+        let left = stump.nodes()[stump.root().unwrap().split.as_ref().unwrap().left].n_samples;
+        assert!(lines[1].starts_with("|   |--- value: ") && lines[1].ends_with(&format!("(samples: {})", left)));
+        let dot = stump.export_dot(None)?;
+        assert!(dot.starts_with("digraph Tree {") && dot.contains("0 -> 1 [label=\"True\"]"));
+        assert_eq!(dot.matches("label=\"value").count(), 2);
+        assert!(stump.export_text(Some(&[])).is_err());
+        assert!(DecisionTree::new(Criterion::Mse).export_dot(None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_classification_tree() -> Result<(), LinearRegressionError> {
+        // XOR: no single split helps much, two levels separate it exactly
+        let x = arr2(&[
+            [0.0, 0.0], [0.1, 0.2], [1.0, 1.0], [0.9, 0.8],
+            [0.0, 1.0], [0.2, 0.9], [1.0, 0.0], [0.8, 0.1],
+        ]);
+        let y = Array1::from(vec![3.0, 3.0, 3.0, 3.0, 7.0, 7.0, 7.0, 7.0]);
+
+        for criterion in [Criterion::Gini, Criterion::Entropy] {
+            let mut tree = DecisionTree::new(criterion);
+            tree.fit(&x, &y)?;
+            assert_eq!(tree.classes(), &[3.0, 7.0]);
+            assert_eq!(tree.predict(&x)?, y);
+
+            let proba = tree.predict_proba(&arr2(&[[0.05, 0.95]]))?;
+            assert_eq!(proba.row(0).to_vec(), vec![0.0, 1.0]);
+        }
+
+        let mut stump = DecisionTree::new(Criterion::Gini).with_max_depth(0);
+        stump.fit(&x, &y)?;
+        assert_eq!(stump.root().unwrap().impurity, 0.5);
+        assert!(DecisionTree::new(Criterion::Mse).predict_proba(&x).is_err());
+        Ok(())
+    }
+}
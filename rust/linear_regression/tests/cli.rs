@@ -0,0 +1,30 @@
+// The command-line binary, run as a subprocess on the bundled housing data
+
+use std::process::Command;
+
+const HOUSING: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/housing.csv");
+
+fn cli(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_linear_regression"))
+        .args(args)
+        .output()
+        .expect("failed to run the linear_regression binary")
+}
+
+#[test]
+fn test_train_rejects_zero_epochs() {
+    let out = std::env::temp_dir().join("linear_regression_cli_zero_epochs.json");
+    let path = out.to_str().unwrap();
+    let train = |epochs| cli(&["train", "--data", HOUSING, "--target", "price", "--out", path, "--epochs", epochs]);
+    let output = train("0");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("--epochs"), "{}", stderr);
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+    assert!(!out.exists());
+
+    let output = train("1");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Final loss"));
+    std::fs::remove_file(&out).unwrap();
+}
@@ -0,0 +1,35 @@
+// Seeded runs reproduce bit for bit, with or without the `parallel` feature:
+// run this binary under both builds to compare them.
+
+use linear_regression::automl::Leaderboard;
+use linear_regression::prelude::*;
+use ndarray::{Array1, Array2};
+use std::time::Duration;
+
+#[test]
+fn test_seeded_runs_reproduce() -> Result<(), MlError> {
+    let x = Array2::from_shape_fn((60, 3), |(i, j)| ((i * (j + 3)) % 17) as f64 / 4.0);
+    let y = Array1::from_shape_fn(60, |i| x[[i, 0]] * 2.0 - x[[i, 1]] + (x[[i, 2]] * 1.3).sin());
+
+    // Deterministic mode tries every candidate whatever the budget and machine
+    let dataset = Dataset { x: x.clone(), y: y.clone(), feature_names: vec![], target_name: "y".to_string() };
+    let deterministic = RandomState::new(11).with_deterministic(true);
+    let (_, leaderboard) = auto_fit(&dataset, Duration::ZERO, deterministic)?;
+    assert_eq!(leaderboard.entries.len(), 5);
+    let (_, again) = auto_fit(&dataset, Duration::ZERO, deterministic)?;
+    let scores = |board: &Leaderboard| board.entries.iter().map(|e| e.mean_score.to_bits()).collect::<Vec<_>>();
+    assert_eq!(scores(&leaderboard), scores(&again));
+
+    // One state seeds a whole run the same way twice over
+    let state = RandomState::new(11);
+    let run = || -> Result<(Array1<f64>, Vec<f64>), MlError> {
+        let mut forest = RandomForest::new(Criterion::Mse, 20).with_random_state(state.stream(0));
+        forest.fit(&x, &y)?;
+        let scores = cross_val_score(&forest, &x, &y, 4, state.stream(1).seed(), None)?;
+        Ok((forest.predict(&x)?, scores))
+    };
+    let (first, second) = (run()?, run()?);
+    assert!(first.0.iter().zip(second.0.iter()).all(|(a, b)| a.to_bits() == b.to_bits()));
+    assert!(first.1.iter().zip(second.1.iter()).all(|(a, b)| a.to_bits() == b.to_bits()));
+    Ok(())
+}
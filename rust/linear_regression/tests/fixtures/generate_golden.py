@@ -0,0 +1,175 @@
+"""Writes golden.json, the reference values tests/golden.rs checks.
+
+    python tests/fixtures/generate_golden.py [--exact]
+
+By default the values come from scikit-learn when it and NumPy are
+installed. With --exact, or without them, the same quantities are computed
+from their definitions (population standard deviation, centered ridge) in
+50-digit decimal arithmetic. "generated_by" in the file records which was
+used; the committed file comes from the exact path.
+"""
+
+import json
+import sys
+from decimal import Decimal, getcontext
+from pathlib import Path
+
+getcontext().prec = 50
+
+# Small, fixed and well conditioned: y = 2*x0 - 3*x1 + 0.5*x2 + 4 plus noise
+X_TRAIN = [
+    [1, 7, 3], [2, 1, 8], [3, 5, 2], [4, 9, 6], [5, 2, 4], [6, 6, 9],
+    [7, 3, 1], [8, 8, 5], [9, 4, 7], [10, 0, 3], [11, 5, 8], [12, 2, 2],
+]
+NOISE = [0.3, -0.2, 0.1, -0.4, 0.5, 0.0, -0.1, 0.2, -0.3, 0.4, -0.5, 0.1]
+Y_TRAIN = [2 * a - 3 * b + 0.5 * c + 4 + e for (a, b, c), e in zip(X_TRAIN, NOISE)]
+X_TEST = [[0, 0, 0], [5, 5, 5], [13, 1, 9], [2.5, 6.5, 4]]
+ALPHA = 1.0
+# Forest on the first feature only, without bootstrap: every tree grows to
+# one row per leaf, so the seed fixes the run without changing the result
+SEED = 7
+N_TREES = 5
+
+Y_TRUE = [3.0, -0.5, 2.0, 7.0, 4.2]
+Y_PRED = [2.5, 0.0, 2.0, 8.0, 3.9]
+LABELS_TRUE = [0, 1, 1, 0, 1, 1, 0, 2]
+LABELS_PRED = [0, 1, 0, 0, 1, 2, 0, 2]
+
+
+def with_sklearn():
+    import numpy as np
+    from sklearn.ensemble import RandomForestRegressor
+    from sklearn.linear_model import LinearRegression, Ridge
+    from sklearn.metrics import accuracy_score, mean_absolute_error, mean_squared_error, r2_score
+    from sklearn.pipeline import make_pipeline
+    from sklearn.preprocessing import StandardScaler
+
+    x, y, x_test = np.array(X_TRAIN, float), np.array(Y_TRAIN), np.array(X_TEST, float)
+    scaler = StandardScaler().fit(x)
+    ols = LinearRegression().fit(x, y)
+    ridge = make_pipeline(StandardScaler(), Ridge(alpha=ALPHA)).fit(x, y)
+    forest = RandomForestRegressor(n_estimators=N_TREES, bootstrap=False, max_features=None, random_state=SEED)
+    forest.fit(x[:, :1], y)
+    mse = mean_squared_error(Y_TRUE, Y_PRED)
+    return "scikit-learn", {
+        "scaler_mean": scaler.mean_.tolist(),
+        "scaler_scale": scaler.scale_.tolist(),
+        "scaled_test": scaler.transform(x_test).tolist(),
+        "ols_coef": ols.coef_.tolist(),
+        "ols_intercept": float(ols.intercept_),
+        "ols_predictions": ols.predict(x_test).tolist(),
+        "ridge_predictions": ridge.predict(x_test).tolist(),
+        "forest_predictions": forest.predict(x_test[:, :1]).tolist(),
+        "mse": mse,
+        "rmse": mse ** 0.5,
+        "mae": mean_absolute_error(Y_TRUE, Y_PRED),
+        "r2": r2_score(Y_TRUE, Y_PRED),
+        "accuracy": accuracy_score(LABELS_TRUE, LABELS_PRED),
+    }
+
+
+def solve(a, b):
+    """Gaussian elimination with partial pivoting on Decimal matrices."""
+    n = len(a)
+    m = [row[:] + [b[i]] for i, row in enumerate(a)]
+    for col in range(n):
+        pivot = max(range(col, n), key=lambda r: abs(m[r][col]))
+        m[col], m[pivot] = m[pivot], m[col]
+        for r in range(n):
+            if r != col:
+                f = m[r][col] / m[col][col]
+                m[r] = [v - f * p for v, p in zip(m[r], m[col])]
+    return [m[i][n] / m[i][i] for i in range(n)]
+
+
+def least_squares(x, y, alpha):
+    """Centered least squares with an L2 penalty on the weights, as Ridge fits it."""
+    n, p = len(x), len(x[0])
+    x_mean = [sum(row[j] for row in x) / n for j in range(p)]
+    y_mean = sum(y) / n
+    xc = [[row[j] - x_mean[j] for j in range(p)] for row in x]
+    yc = [v - y_mean for v in y]
+    gram = [[sum(r[i] * r[j] for r in xc) + (alpha if i == j else 0) for j in range(p)] for i in range(p)]
+    rhs = [sum(r[i] * t for r, t in zip(xc, yc)) for i in range(p)]
+    w = solve(gram, rhs)
+    return w, y_mean - sum(wj * mj for wj, mj in zip(w, x_mean))
+
+
+def fully_grown_tree(x, y, t):
+    """A tree with one row per leaf on distinct 1-D inputs, split at midpoints."""
+    points = sorted(zip(x, y))
+    for (a, value), (b, _) in zip(points, points[1:]):
+        if t <= (a + b) / 2:
+            return value
+    return points[-1][1]
+
+
+def exact():
+    d = lambda rows: [[Decimal(str(v)) for v in row] for row in rows]
+    x, x_test = d(X_TRAIN), d(X_TEST)
+    y = [Decimal(str(v)) for v in Y_TRAIN]
+    n, p = len(x), len(x[0])
+
+    # Population standard deviation, as StandardScaler uses
+    mean = [sum(row[j] for row in x) / n for j in range(p)]
+    scale = [(sum((row[j] - mean[j]) ** 2 for row in x) / n).sqrt() for j in range(p)]
+    standardize = lambda rows: [[(row[j] - mean[j]) / scale[j] for j in range(p)] for row in rows]
+    predict = lambda w, b, rows: [sum(wj * v for wj, v in zip(w, row)) + b for row in rows]
+
+    coef, intercept = least_squares(x, y, Decimal(0))
+    ridge_w, ridge_b = least_squares(standardize(x), y, Decimal(str(ALPHA)))
+
+    t, q = [Decimal(str(v)) for v in Y_TRUE], [Decimal(str(v)) for v in Y_PRED]
+    mse = sum((a - b) ** 2 for a, b in zip(t, q)) / len(t)
+    t_mean = sum(t) / len(t)
+    r2 = 1 - sum((a - b) ** 2 for a, b in zip(t, q)) / sum((a - t_mean) ** 2 for a in t)
+    accuracy = Decimal(sum(a == b for a, b in zip(LABELS_TRUE, LABELS_PRED))) / len(LABELS_TRUE)
+
+    f = lambda values: [float(v) for v in values]
+    return "50-digit decimal arithmetic from the definitions, not scikit-learn", {
+        "scaler_mean": f(mean),
+        "scaler_scale": f(scale),
+        "scaled_test": [f(row) for row in standardize(x_test)],
+        "ols_coef": f(coef),
+        "ols_intercept": float(intercept),
+        "ols_predictions": f(predict(coef, intercept, x_test)),
+        "ridge_predictions": f(predict(ridge_w, ridge_b, standardize(x_test))),
+        "forest_predictions": f(fully_grown_tree([r[0] for r in x], y, row[0]) for row in x_test),
+        "mse": float(mse),
+        "rmse": float(mse.sqrt()),
+        "mae": float(sum(abs(a - b) for a, b in zip(t, q)) / len(t)),
+        "r2": float(r2),
+        "accuracy": float(accuracy),
+    }
+
+
+def main():
+    source, values = None, None
+    if "--exact" not in sys.argv:
+        try:
+            source, values = with_sklearn()
+        except ImportError:
+            pass
+    if values is None:
+        source, values = exact()
+    fixture = {
+        "generated_by": source,
+        "x_train": X_TRAIN,
+        "y_train": Y_TRAIN,
+        "x_test": X_TEST,
+        "alpha": ALPHA,
+        "seed": SEED,
+        "n_trees": N_TREES,
+        "y_true": Y_TRUE,
+        "y_pred": Y_PRED,
+        "labels_true": LABELS_TRUE,
+        "labels_pred": LABELS_PRED,
+        **values,
+    }
+    path = Path(__file__).with_name("golden.json")
+    path.write_text(json.dumps(fixture, indent=2) + "\n")
+    print(f"Wrote {path} ({source})")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,137 @@
+// Checks this crate against reference values in tests/fixtures/golden.json,
+// computed independently in 50-digit decimal arithmetic from the
+// definitions scikit-learn uses (its "generated_by" field says so; it was
+// not produced by scikit-learn itself). Regenerate the fixture with
+// `python tests/fixtures/generate_golden.py --exact` after changing its
+// inputs, or without --exact where scikit-learn is installed.
+
+use linear_regression::automl::{AutoModel, Candidate};
+use linear_regression::prelude::*;
+use ndarray::{Array1, Array2};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Golden {
+    x_train: Vec<Vec<f64>>,
+    y_train: Vec<f64>,
+    x_test: Vec<Vec<f64>>,
+    alpha: f64,
+    seed: u64,
+    n_trees: usize,
+    scaler_mean: Vec<f64>,
+    scaler_scale: Vec<f64>,
+    scaled_test: Vec<Vec<f64>>,
+    ols_coef: Vec<f64>,
+    ols_intercept: f64,
+    ols_predictions: Vec<f64>,
+    ridge_predictions: Vec<f64>,
+    forest_predictions: Vec<f64>,
+    y_true: Vec<f64>,
+    y_pred: Vec<f64>,
+    mse: f64,
+    rmse: f64,
+    mae: f64,
+    r2: f64,
+    labels_true: Vec<f64>,
+    labels_pred: Vec<f64>,
+    accuracy: f64,
+}
+
+fn golden() -> Golden {
+    let text = include_str!("fixtures/golden.json");
+    serde_json::from_str(text).expect("fixture parses")
+}
+
+fn matrix(rows: &[Vec<f64>]) -> Array2<f64> {
+    Array2::from_shape_fn((rows.len(), rows[0].len()), |(i, j)| rows[i][j])
+}
+
+fn assert_close(actual: &[f64], expected: &[f64], tolerance: f64, what: &str) {
+    assert_eq!(actual.len(), expected.len(), "{}: length", what);
+    for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+        assert!((a - e).abs() <= tolerance, "{}[{}]: {} vs reference {}", what, i, a, e);
+    }
+}
+
+fn dataset(golden: &Golden) -> Dataset {
+    let x = matrix(&golden.x_train);
+    Dataset {
+        feature_names: (0..x.ncols()).map(|j| format!("x{}", j)).collect(),
+        x,
+        y: Array1::from(golden.y_train.clone()),
+        target_name: "y".to_string(),
+    }
+}
+
+#[test]
+fn test_standard_scaler_matches_reference() -> Result<(), MlError> {
+    let golden = golden();
+    let scaler = StandardScaler::fit(&matrix(&golden.x_train))?;
+    assert_close(scaler.means.as_slice().unwrap(), &golden.scaler_mean, 1e-12, "mean_");
+    assert_close(scaler.stds.as_slice().unwrap(), &golden.scaler_scale, 1e-12, "scale_");
+    let scaled = scaler.transform(&matrix(&golden.x_test))?;
+    for (row, expected) in scaled.rows().into_iter().zip(&golden.scaled_test) {
+        assert_close(&row.to_vec(), expected, 1e-12, "transform");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_linear_regression_matches_reference() -> Result<(), MlError> {
+    let golden = golden();
+    // Gradient descent on standardized features converges to the
+    // least-squares solution LinearRegression computes directly
+    let (pipeline, _) = Pipeline::fit(&dataset(&golden), 0.1, 5000)?;
+    let scaling = pipeline.scaler.means.iter().zip(pipeline.scaler.stds.iter());
+    let mut intercept = pipeline.model.bias;
+    let mut coef = Vec::new();
+    for (&w, (&mean, &std)) in pipeline.model.weights.iter().zip(scaling) {
+        intercept -= w * mean / std;
+        coef.push(w / std);
+    }
+    assert_close(&coef, &golden.ols_coef, 1e-6, "coef_");
+    assert_close(&[intercept], &[golden.ols_intercept], 1e-6, "intercept_");
+    let predictions = pipeline.predict(&matrix(&golden.x_test))?;
+    assert_close(predictions.as_slice().unwrap(), &golden.ols_predictions, 1e-6, "predict");
+    Ok(())
+}
+
+#[test]
+fn test_ridge_pipeline_matches_reference() -> Result<(), MlError> {
+    let golden = golden();
+    // The same as make_pipeline(StandardScaler(), Ridge(alpha))
+    let mut ridge = AutoModel::new(Candidate::Ridge { alpha: golden.alpha });
+    ridge.fit(&matrix(&golden.x_train), &Array1::from(golden.y_train.clone()))?;
+    let predictions = ridge.predict(&matrix(&golden.x_test))?;
+    assert_close(predictions.as_slice().unwrap(), &golden.ridge_predictions, 1e-9, "predict");
+    Ok(())
+}
+
+#[test]
+fn test_seeded_forest_matches_reference() -> Result<(), MlError> {
+    let golden = golden();
+    // Without bootstrap, on one feature with distinct values, every tree
+    // grows to one row per leaf whatever its seed, as
+    // RandomForestRegressor(bootstrap=False, random_state=seed) does
+    let first = |rows: &[Vec<f64>]| Array2::from_shape_fn((rows.len(), 1), |(i, _)| rows[i][0]);
+    let mut forest = RandomForest::new(Criterion::Mse, golden.n_trees)
+        .with_bootstrap(false)
+        .with_random_state(RandomState::new(golden.seed));
+    forest.fit(&first(&golden.x_train), &Array1::from(golden.y_train.clone()))?;
+    let predictions = forest.predict(&first(&golden.x_test))?;
+    assert_close(predictions.as_slice().unwrap(), &golden.forest_predictions, 1e-12, "predict");
+    Ok(())
+}
+
+#[test]
+fn test_metrics_match_reference() {
+    let golden = golden();
+    let (y_true, y_pred) = (Array1::from(golden.y_true.clone()), Array1::from(golden.y_pred.clone()));
+    assert_close(&[mean_squared_error(&y_pred, &y_true)], &[golden.mse], 1e-12, "mean_squared_error");
+    assert_close(&[root_mean_squared_error(&y_pred, &y_true)], &[golden.rmse], 1e-12, "root_mean_squared_error");
+    assert_close(&[mean_absolute_error(&y_pred, &y_true)], &[golden.mae], 1e-12, "mean_absolute_error");
+    assert_close(&[r2_score(&y_pred, &y_true)], &[golden.r2], 1e-12, "r2_score");
+    let labels_true = Array1::from(golden.labels_true.clone());
+    let labels_pred = Array1::from(golden.labels_pred.clone());
+    assert_close(&[accuracy_score(&labels_pred, &labels_true)], &[golden.accuracy], 1e-12, "accuracy_score");
+}
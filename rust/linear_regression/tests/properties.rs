@@ -0,0 +1,98 @@
+// Invariants that must hold for any input, checked on random data with
+// proptest. Failing cases are shrunk to a minimal example and saved next
+// to this file in properties.regressions, so they are replayed on later runs.
+
+use linear_regression::automl::{AutoModel, Candidate};
+use linear_regression::prelude::*;
+use ndarray::{Array1, Array2};
+use proptest::prelude::*;
+use proptest::test_runner::FileFailurePersistence;
+
+// A design matrix with 2 to 20 rows and 1 to 4 columns, and a target
+fn regression_data() -> impl Strategy<Value = (Array2<f64>, Array1<f64>)> {
+    (2usize..20, 1usize..5).prop_flat_map(|(n, f)| {
+        (prop::collection::vec(-10.0..10.0f64, n * f), prop::collection::vec(-10.0..10.0f64, n)).prop_map(
+            move |(x, y)| (Array2::from_shape_vec((n, f), x).unwrap(), Array1::from(y)),
+        )
+    })
+}
+
+fn close(a: &Array1<f64>, b: &Array1<f64>, tolerance: f64) -> bool {
+    a.iter().zip(b.iter()).all(|(a, b)| (a - b).abs() <= tolerance * (1.0 + a.abs().max(b.abs())))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        failure_persistence: Some(Box::new(FileFailurePersistence::WithSource("regressions"))),
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn prop_shifting_intercept_shifts_predictions((x, _) in regression_data(), shift in -100.0..100.0f64) {
+        let weights = Array1::from_shape_fn(x.ncols(), |j| j as f64 - 1.5);
+        let base = LinearRegression::from_parameters(weights.clone(), 0.5).predict(&x)?;
+        let shifted = LinearRegression::from_parameters(weights, 0.5 + shift).predict(&x)?;
+        prop_assert!(close(&shifted, &(base + shift), 1e-12));
+    }
+
+    #[test]
+    fn prop_fitted_intercept_follows_target_shift((x, y) in regression_data(), shift in -100.0..100.0f64) {
+        // Ridge centers the target, so moving it moves only the intercept
+        let mut model = AutoModel::new(Candidate::Ridge { alpha: 1.0 });
+        model.fit(&x, &y)?;
+        let base = model.predict(&x)?;
+        model.fit(&x, &(&y + shift))?;
+        prop_assert!(close(&model.predict(&x)?, &(base + shift), 1e-9));
+    }
+
+    #[test]
+    fn prop_feature_scaling_is_undone_by_coefficients(
+        (x, _) in regression_data(),
+        scales in prop::collection::vec(0.1..10.0f64, 4),
+    ) {
+        let scales = Array1::from(scales[..x.ncols()].to_vec());
+        let weights = Array1::from_shape_fn(x.ncols(), |j| 2.0 - j as f64);
+        let original = LinearRegression::from_parameters(weights.clone(), 1.0).predict(&x)?;
+        let rescaled = LinearRegression::from_parameters(&weights / &scales, 1.0).predict(&(&x * &scales))?;
+        prop_assert!(close(&rescaled, &original, 1e-12));
+    }
+
+    #[test]
+    fn prop_r2_is_at_most_one((_, y) in regression_data(), noise in prop::collection::vec(-10.0..10.0f64, 20)) {
+        let mean = y.mean().unwrap();
+        prop_assume!(y.iter().any(|v| (v - mean).abs() > 1e-9));
+        let predictions = Array1::from_shape_fn(y.len(), |i| y[i] + noise[i]);
+        prop_assert!(r2_score(&predictions, &y) <= 1.0);
+        prop_assert!(r2_score(&y, &y) == 1.0);
+    }
+
+    #[test]
+    fn prop_training_never_increases_loss((x, y) in regression_data()) {
+        // The loss is quadratic with curvature at most the trace of the Gram
+        // matrix of [X 1] over n, so a step of its inverse is one a line
+        // search would accept: no epoch may overshoot
+        let n = x.nrows() as f64;
+        let curvature = (x.mapv(|v| v * v).sum() + n) / n;
+        let mut model = LinearRegression::new(x.ncols(), 1.0 / curvature);
+        let history = model.train(&x, &y, 50)?;
+        let final_loss = model.mse_loss(&model.predict(&x)?, &y);
+        let losses: Vec<f64> = history.train_loss.iter().copied().chain([final_loss]).collect();
+        for pair in losses.windows(2) {
+            prop_assert!(pair[1] <= pair[0] * (1.0 + 1e-12), "loss rose from {} to {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn prop_mismatched_shapes_are_rejected((x, y) in regression_data(), extra in 1usize..3) {
+        let mut model = LinearRegression::from_parameters(Array1::zeros(x.ncols()), 0.0);
+        let short_y = y.slice(ndarray::s![..y.len() - 1]).to_owned();
+        let wide_x = Array2::<f64>::zeros((x.nrows(), x.ncols() + extra));
+        let mismatch = |r: Result<_, MlError>| matches!(r, Err(MlError::DimensionMismatch { .. }));
+        prop_assert!(mismatch(model.train(&x, &short_y, 1).map(|_| ())));
+        prop_assert!(mismatch(model.train(&wide_x, &y, 1).map(|_| ())));
+        prop_assert!(mismatch(model.predict(&wide_x).map(|_| ())));
+        prop_assert!(mismatch(model.score(&x, &short_y).map(|_| ())));
+        let scaler = StandardScaler::fit(&x)?;
+        prop_assert!(mismatch(scaler.transform(&wide_x).map(|_| ())));
+    }
+}
@@ -0,0 +1,450 @@
+use crate::metrics::Metric;
+use crate::MlError;
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    pub x: Array2<f64>,
+    pub y: Array1<f64>,
+    pub feature_names: Vec<String>,
+    pub target_name: String,
+}
+
+impl Dataset {
+    // Load a CSV file with a header row, using `target` as the label column
+    // and every other column as a feature
+    pub fn from_csv<P: AsRef<Path>>(path: P, target: &str) -> Result<Self, MlError> {
+        Self::from_csv_reader(File::open(path)?, target)
+    }
+
+    // Same as `from_csv`, for CSV text from memory, a socket or a pipe
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(target = target)))]
+    pub fn from_csv_reader<R: Read>(reader: R, target: &str) -> Result<Self, MlError> {
+        let (headers, records) = read_records(reader)?;
+        let table = parse_table(&headers, &records)?;
+        let target_idx = target_index(&headers, target)?;
+
+        let feature_idx: Vec<usize> = (0..headers.len()).filter(|&j| j != target_idx).collect();
+        let feature_names = feature_idx.iter().map(|&j| headers[j].clone()).collect();
+
+        Ok(Self {
+            x: table.select(ndarray::Axis(1), &feature_idx),
+            y: table.column(target_idx).to_owned(),
+            feature_names,
+            target_name: target.to_string(),
+        })
+    }
+
+    // Like `from_csv`, but a target column of text labels is accepted too:
+    // the labels are encoded as 0, 1, … in sorted order and the mapping is
+    // returned, so predictions can be turned back into class names. The
+    // mapping is None when the target is numeric. Features must be numeric.
+    pub fn from_csv_with_labels<P: AsRef<Path>>(
+        path: P,
+        target: &str,
+    ) -> Result<(Self, Option<LabelMap>), MlError> {
+        let (headers, records) = read_records(File::open(path)?)?;
+        let target_idx = target_index(&headers, target)?;
+        let numeric = records.iter().all(|r| r[target_idx].trim().parse::<f64>().is_ok());
+        let labels = (!numeric).then(|| LabelMap::from_labels(records.iter().map(|r| r[target_idx].trim())));
+
+        let feature_idx: Vec<usize> = (0..headers.len()).filter(|&j| j != target_idx).collect();
+        let mut values = Vec::with_capacity(records.len() * feature_idx.len());
+        let mut targets = Vec::with_capacity(records.len());
+        for (row, record) in records.iter().enumerate() {
+            for &j in &feature_idx {
+                values.push(parse_field(&record[j], row + 1, &headers[j])?);
+            }
+            targets.push(match &labels {
+                Some(labels) => labels.encode(record[target_idx].trim()).unwrap(),
+                None => parse_field(&record[target_idx], row + 1, target)?,
+            });
+        }
+
+        let dataset = Self {
+            x: Array2::from_shape_vec((records.len(), feature_idx.len()), values).unwrap(),
+            y: Array1::from(targets),
+            feature_names: feature_idx.iter().map(|&j| headers[j].clone()).collect(),
+            target_name: target.to_string(),
+        };
+        Ok((dataset, labels))
+    }
+
+    // Build from plain rows of feature values and one target per row.
+    // Features are named x0, x1, … and the target y.
+    pub fn from_rows<R: AsRef<[f64]>>(rows: &[R], targets: &[f64]) -> Result<Self, MlError> {
+        let x = rows_to_array(rows)?;
+        if targets.len() != x.nrows() {
+            return Err(MlError::DimensionMismatch {
+                expected: x.nrows(),
+                found: targets.len(),
+                context: "number of targets for the rows",
+            });
+        }
+        Ok(Self {
+            feature_names: (0..x.ncols()).map(|j| format!("x{}", j)).collect(),
+            x,
+            y: Array1::from(targets.to_vec()),
+            target_name: "y".to_string(),
+        })
+    }
+
+    pub fn n_samples(&self) -> usize {
+        self.x.nrows()
+    }
+
+    pub fn n_features(&self) -> usize {
+        self.x.ncols()
+    }
+}
+
+// Class labels of a text target column and their numeric codes: the i-th
+// class in sorted order is encoded as i
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelMap {
+    classes: Vec<String>,
+}
+
+impl LabelMap {
+    pub fn from_labels<I, S>(labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let classes: BTreeSet<String> = labels.into_iter().map(|l| l.as_ref().to_string()).collect();
+        Self { classes: classes.into_iter().collect() }
+    }
+
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    pub fn n_classes(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn encode(&self, label: &str) -> Option<f64> {
+        self.classes.binary_search_by(|c| c.as_str().cmp(label)).ok().map(|i| i as f64)
+    }
+
+    pub fn label(&self, code: f64) -> Option<&str> {
+        if code.fract() != 0.0 || code < 0.0 {
+            return None;
+        }
+        self.classes.get(code as usize).map(String::as_str)
+    }
+
+    // Class names for predicted codes. A code that is not a class index,
+    // e.g. a regression output, is an error naming its row.
+    pub fn decode(&self, predictions: &Array1<f64>) -> Result<Vec<String>, MlError> {
+        predictions
+            .iter()
+            .enumerate()
+            .map(|(row, &code)| {
+                self.label(code).map(str::to_string).ok_or_else(|| MlError::InvalidValue {
+                    row: row + 1,
+                    column: "prediction".to_string(),
+                    message: format!("{} is not a class index below {}", code, self.n_classes()),
+                })
+            })
+            .collect()
+    }
+}
+
+// Whether a target holds classes or continuous values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Task {
+    Classification,
+    Regression,
+}
+
+impl Task {
+    // Most distinct values a numeric target can have and still be taken as
+    // classes
+    pub const MAX_CLASSES: usize = 20;
+
+    // Text labels are classes. A numeric target is taken as classes when
+    // every value is an integer and there are few distinct values, at most
+    // `MAX_CLASSES` and at most half the number of samples; otherwise it is
+    // continuous. Callers that know better should say so instead.
+    pub fn infer(y: &Array1<f64>, labels: Option<&LabelMap>) -> Self {
+        if labels.is_some() {
+            return Self::Classification;
+        }
+        if y.iter().any(|v| v.fract() != 0.0) {
+            return Self::Regression;
+        }
+        let distinct: BTreeSet<i64> = y.iter().map(|&v| v as i64).collect();
+        if distinct.len() <= Self::MAX_CLASSES && 2 * distinct.len() <= y.len() {
+            Self::Classification
+        } else {
+            Self::Regression
+        }
+    }
+
+    // The metrics worth reporting for the task, the one to rank by first
+    pub fn default_metrics(&self) -> &'static [Metric] {
+        match self {
+            Self::Classification => &[Metric::Accuracy],
+            Self::Regression => &[Metric::R2, Metric::Rmse, Metric::Mae],
+        }
+    }
+}
+
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Classification => write!(f, "classification"),
+            Self::Regression => write!(f, "regression"),
+        }
+    }
+}
+
+impl FromStr for Task {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "classification" => Ok(Self::Classification),
+            "regression" => Ok(Self::Regression),
+            _ => Err(format!("unknown task '{}', expected classification or regression", s)),
+        }
+    }
+}
+
+// Read a numeric CSV file with a header row into its column names and values
+pub fn read_csv<P: AsRef<Path>>(path: P) -> Result<(Vec<String>, Array2<f64>), MlError> {
+    let (headers, records) = read_records(File::open(path)?)?;
+    let table = parse_table(&headers, &records)?;
+    Ok((headers, table))
+}
+
+fn parse_table(headers: &[String], records: &[csv::StringRecord]) -> Result<Array2<f64>, MlError> {
+    let mut values = Vec::with_capacity(records.len() * headers.len());
+    for (row, record) in records.iter().enumerate() {
+        for (field, name) in record.iter().zip(headers.iter()) {
+            values.push(parse_field(field, row + 1, name)?);
+        }
+    }
+    Array2::from_shape_vec((records.len(), headers.len()), values).map_err(|e| MlError::Parse(e.to_string()))
+}
+
+// Header names and the raw records of non-empty CSV text with rows of the
+// header's length
+pub(crate) fn read_records<R: Read>(reader: R) -> Result<(Vec<String>, Vec<csv::StringRecord>), MlError> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(csv_error)?
+        .iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+
+    let mut records = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        if record.len() != headers.len() {
+            return Err(MlError::DimensionMismatch {
+                expected: headers.len(),
+                found: record.len(),
+                context: "number of fields in CSV row",
+            });
+        }
+        records.push(record);
+    }
+
+    if records.is_empty() {
+        return Err(MlError::EmptyData);
+    }
+    Ok((headers, records))
+}
+
+// NaN is refused like any other non-number, so a stray "nan" cannot end up
+// in the arrays unnoticed
+fn parse_field(field: &str, row: usize, column: &str) -> Result<f64, MlError> {
+    match field.trim().parse::<f64>() {
+        Ok(value) if !value.is_nan() => Ok(value),
+        _ => Err(MlError::InvalidValue {
+            row,
+            column: column.to_string(),
+            message: format!("invalid number '{}'", field),
+        }),
+    }
+}
+
+fn target_index(headers: &[String], target: &str) -> Result<usize, MlError> {
+    headers
+        .iter()
+        .position(|h| h == target)
+        .ok_or_else(|| MlError::Parse(format!("target column '{}' not found", target)))
+}
+
+// Stack equal-length rows into a matrix, one row each. Ragged input is an
+// error naming the expected and found lengths.
+pub fn rows_to_array<T: Clone, R: AsRef<[T]>>(rows: &[R]) -> Result<Array2<T>, MlError> {
+    let n_cols = match rows.first() {
+        Some(row) => row.as_ref().len(),
+        None => return Err(MlError::EmptyData),
+    };
+    let mut values = Vec::with_capacity(rows.len() * n_cols);
+    for row in rows {
+        let row = row.as_ref();
+        if row.len() != n_cols {
+            return Err(MlError::DimensionMismatch {
+                expected: n_cols,
+                found: row.len(),
+                context: "length of row (rows must all have the same length)",
+            });
+        }
+        values.extend_from_slice(row);
+    }
+    Ok(Array2::from_shape_vec((rows.len(), n_cols), values).unwrap())
+}
+
+// Pick the named columns out of a table, in the order given
+pub fn select_columns(
+    headers: &[String],
+    table: &Array2<f64>,
+    names: &[String],
+) -> Result<Array2<f64>, MlError> {
+    let indices = names
+        .iter()
+        .map(|name| {
+            headers
+                .iter()
+                .position(|h| h == name)
+                .ok_or_else(|| MlError::Parse(format!("column '{}' not found", name)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(table.select(ndarray::Axis(1), &indices))
+}
+
+pub fn write_predictions<P: AsRef<Path>>(path: P, predictions: &Array1<f64>) -> Result<(), MlError> {
+    let mut writer = csv::Writer::from_path(path).map_err(csv_error)?;
+    writer.write_record(["prediction"]).map_err(csv_error)?;
+    for pred in predictions.iter() {
+        writer.write_record([pred.to_string()]).map_err(csv_error)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+// IO failures surface as `Io`; anything else keeps the CSV error as its source
+fn csv_error(err: csv::Error) -> MlError {
+    if err.is_io_error() {
+        match err.into_kind() {
+            csv::ErrorKind::Io(io) => MlError::Io(io),
+            _ => unreachable!("is_io_error implies an Io kind"),
+        }
+    } else {
+        MlError::Csv(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_from_rows() -> Result<(), MlError> {
+        let rows = vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]];
+        let dataset = Dataset::from_rows(&rows, &[1.0, 0.0, 1.0])?;
+        assert_eq!(dataset.feature_names, vec!["x0", "x1"]);
+        assert_eq!(dataset.x.row(2).to_vec(), vec![5.0, 6.0]);
+
+        let ragged: [&[f64]; 2] = [&[1.0, 2.0], &[3.0]];
+        match rows_to_array(&ragged) {
+            Err(MlError::DimensionMismatch { expected: 2, found: 1, .. }) => (),
+            _ => panic!("Expected dimension mismatch for ragged rows"),
+        }
+        assert!(Dataset::from_rows(&rows, &[1.0]).is_err());
+        assert!(rows_to_array::<f64, Vec<f64>>(&[]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_csv_splits_target() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join("linear_regression_data_test.csv");
+        let mut file = std::fs::File::create(&path)?;
+        writeln!(file, "sqft,price,bedrooms")?;
+        writeln!(file, "1200,200,2")?;
+        writeln!(file, "1500,250,3")?;
+        drop(file);
+
+        let dataset = Dataset::from_csv(&path, "price")?;
+        assert_eq!(dataset.feature_names, vec!["sqft", "bedrooms"]);
+        assert_eq!(dataset.x.row(1).to_vec(), vec![1500.0, 3.0]);
+        assert_eq!(dataset.y.to_vec(), vec![200.0, 250.0]);
+
+        match Dataset::from_csv(&path, "missing") {
+            Err(MlError::Parse(_)) => (),
+            _ => panic!("Expected parse error for missing target"),
+        }
+
+        let mut file = std::fs::File::create(&path)?;
+        writeln!(file, "sqft,price")?;
+        writeln!(file, "1200,abc")?;
+        drop(file);
+        match read_csv(&path) {
+            Err(MlError::InvalidValue { row: 1, column, .. }) => assert_eq!(column, "price"),
+            _ => panic!("Expected invalid value error"),
+        }
+        // Any spelling of NaN is refused rather than read as a number
+        let nan = Dataset::from_csv_reader("sqft,price\n1200,NaN\n".as_bytes(), "price");
+        assert!(matches!(nan, Err(MlError::InvalidValue { row: 1, .. })));
+        assert_eq!(Dataset::from_csv_reader("sqft,price\n1200,250\n".as_bytes(), "price")?.y.to_vec(), vec![250.0]);
+        // IO failures keep the underlying error as their source
+        let missing = read_csv(std::env::temp_dir().join("linear_regression_no_such_file.csv")).unwrap_err();
+        assert!(matches!(missing, MlError::Io(_)));
+        assert!(std::error::Error::source(&missing).is_some());
+
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_csv_with_text_labels() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join("linear_regression_labels_test.csv");
+        std::fs::write(&path, "petal,species\n1.4,setosa\n4.7,versicolor\n1.3,setosa\n6.0,virginica\n")?;
+
+        let (dataset, labels) = Dataset::from_csv_with_labels(&path, "species")?;
+        let labels = labels.unwrap();
+        assert_eq!(labels.classes(), ["setosa", "versicolor", "virginica"]);
+        assert_eq!(dataset.y.to_vec(), vec![0.0, 1.0, 0.0, 2.0]);
+        assert_eq!(dataset.x.column(0).to_vec(), vec![1.4, 4.7, 1.3, 6.0]);
+        assert_eq!(labels.decode(&Array1::from(vec![2.0, 0.0]))?, vec!["virginica", "setosa"]);
+        assert!(labels.decode(&Array1::from(vec![0.5])).is_err() && labels.label(3.0).is_none());
+        // The plain loader still wants numbers
+        assert!(matches!(Dataset::from_csv(&path, "species"), Err(MlError::InvalidValue { .. })));
+
+        // Text features are still errors, and numeric targets need no mapping
+        assert!(Dataset::from_csv_with_labels(&path, "petal").is_err());
+        std::fs::write(&path, "petal,class\n1.4,0\n4.7,1\n")?;
+        let (dataset, labels) = Dataset::from_csv_with_labels(&path, "class")?;
+        assert!(labels.is_none() && dataset.y.to_vec() == vec![0.0, 1.0]);
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_task_infer() {
+        let labels = LabelMap::from_labels(["a", "b"]);
+        let codes = Array1::from(vec![0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(Task::infer(&codes, Some(&labels)), Task::Classification);
+        assert_eq!(Task::infer(&codes, None), Task::Classification);
+        assert_eq!(Task::infer(&Array1::from(vec![0.5, 1.0, 1.0, 0.0]), None), Task::Regression);
+        // Integers that are nearly all distinct are counts or prices, not classes
+        assert_eq!(Task::infer(&Array1::from_shape_fn(40, |i| (i * 7) as f64), None), Task::Regression);
+        assert_eq!("regression".parse::<Task>(), Ok(Task::Regression));
+        assert!("clustering".parse::<Task>().is_err());
+    }
+}
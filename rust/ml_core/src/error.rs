@@ -0,0 +1,97 @@
+// Errors shared by every crate built on ml_core. Variants say exactly what
+// went wrong; `kind` sorts them into the few categories callers usually
+// branch on. Wrapped IO, CSV and JSON errors are kept as the source.
+#[derive(Debug, thiserror::Error)]
+pub enum MlError {
+    #[error("Dimension mismatch in {context}: expected {expected}, found {found}")]
+    DimensionMismatch {
+        expected: usize,
+        found: usize,
+        context: &'static str,
+    },
+    #[error("Empty data provided")]
+    EmptyData,
+    // A model that needs `fit`/`train` before it can be used
+    #[error("Model is not fitted yet")]
+    NotFitted,
+    // Saved or assembled parts that do not fit together, such as a model
+    // with a different number of weights than its pipeline has features
+    #[error("Incompatible model: {0}")]
+    IncompatibleModel(String),
+    #[error("Numerical error: {0}")]
+    NumericalError(&'static str),
+    // Loss or gradients stopped being finite during iterative training
+    #[error("Training diverged at epoch {epoch}: {message}")]
+    Diverged { epoch: usize, message: String },
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(&'static str),
+    // A hyperparameter set by name that is unknown or has a bad value
+    #[error("Parameter '{name}': {message}")]
+    Parameter { name: String, message: String },
+    // A bad value in input data, located by its row (1-based, as in the
+    // file) and column
+    #[error("Invalid value at row {row}, column '{column}': {message}")]
+    InvalidValue { row: usize, column: String, message: String },
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Parse error: {0}")]
+    Parse(String),
+    #[cfg(feature = "parquet")]
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    // Input data that is missing, malformed or has bad values
+    Data,
+    // Arrays, or saved model parts, whose sizes do not fit together
+    Shape,
+    // A computation that broke down: singular systems, divergence, NaN
+    Numerical,
+    NotFitted,
+    // A bad hyperparameter or argument
+    Parameter,
+    Io,
+    // Text that could not be read as JSON, TOML or the expected structure
+    Parse,
+}
+
+impl MlError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::EmptyData | Self::InvalidValue { .. } | Self::Csv(_) => ErrorKind::Data,
+            Self::DimensionMismatch { .. } | Self::IncompatibleModel(_) => ErrorKind::Shape,
+            Self::NumericalError(_) | Self::Diverged { .. } => ErrorKind::Numerical,
+            Self::NotFitted => ErrorKind::NotFitted,
+            Self::InvalidParameter(_) | Self::Parameter { .. } => ErrorKind::Parameter,
+            Self::Io(_) => ErrorKind::Io,
+            Self::Json(_) | Self::Parse(_) => ErrorKind::Parse,
+            #[cfg(feature = "parquet")]
+            Self::Parquet(_) => ErrorKind::Parse,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_error_kind() {
+        let err = MlError::DimensionMismatch { expected: 2, found: 3, context: "test" };
+        assert_eq!(err.kind(), ErrorKind::Shape);
+        assert!(err.source().is_none());
+
+        let io = MlError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "missing.csv"));
+        assert_eq!(io.kind(), ErrorKind::Io);
+        assert_eq!(io.source().unwrap().to_string(), "missing.csv");
+        let json = MlError::from(serde_json::from_str::<f64>("{").unwrap_err());
+        assert!(json.kind() == ErrorKind::Parse && json.source().is_some());
+    }
+}
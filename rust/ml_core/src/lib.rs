@@ -0,0 +1,14 @@
+// The pieces every model crate in the workspace needs and none of them owns:
+// datasets and file loading, the estimator traits, metrics, feature
+// transforms, the shared error type, train/validation splitters and seeding.
+
+pub mod data;
+pub mod error;
+pub mod loader;
+pub mod metrics;
+pub mod preprocessing;
+pub mod random;
+pub mod split;
+pub mod traits;
+
+pub use error::{ErrorKind, MlError};
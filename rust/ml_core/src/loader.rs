@@ -0,0 +1,666 @@
+use crate::data::{read_records, Dataset, LabelMap};
+use crate::preprocessing::{CategoricalEncoder, ImputeStrategy, SimpleImputer};
+use crate::MlError;
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+// Ways a file can be laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Format {
+    Csv,
+    // One JSON object per line
+    JsonLines,
+    // Needs the `parquet` feature
+    Parquet,
+}
+
+impl Format {
+    // From the extension: .csv, .jsonl or .ndjson, .parquet
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "jsonl" | "ndjson" => Some(Self::JsonLines),
+            "parquet" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnType {
+    Numeric,
+    // Text values, encoded as 0, 1, … in sorted order of the classes
+    Categorical,
+    // ISO 8601 dates or date-times (UTC), encoded as seconds since the epoch
+    Datetime,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub column_type: ColumnType,
+    // Missing cells in the file, before the NaN policy is applied
+    pub n_missing: usize,
+}
+
+// Columns of a loaded file in file order, target included
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schema {
+    pub columns: Vec<ColumnSchema>,
+}
+
+impl Schema {
+    pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+}
+
+// What to do with missing cells: empty fields, NA, N/A, NaN, null and JSON
+// nulls. Rows with a missing target are dropped by every policy but `Error`;
+// targets are never filled in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum NanPolicy {
+    // The first missing cell is an error naming its row and column
+    #[default]
+    Error,
+    DropRows,
+    // The column mean for numeric and datetime columns, the most frequent
+    // class for categorical ones
+    FillMean,
+}
+
+// A loaded file: the dataset plus what is needed to read it back, such as
+// the class names behind a categorical target
+#[derive(Debug, Clone)]
+pub struct LoadedData {
+    pub dataset: Dataset,
+    pub schema: Schema,
+    // Classes of the target, when it is categorical
+    pub labels: Option<LabelMap>,
+    // Encoder of each categorical feature, by column name
+    pub categories: BTreeMap<String, CategoricalEncoder>,
+    // The fills `NanPolicy::FillMean` used, fitted on the kept rows, for
+    // applying to new data
+    pub imputer: Option<SimpleImputer>,
+}
+
+// Loads CSV, JSON Lines or Parquet files into a `Dataset`, inferring each
+// column's type. Without `with_target` the last column is the target.
+#[derive(Debug, Clone)]
+pub struct DataLoader {
+    path: PathBuf,
+    format: Option<Format>,
+    target: Option<String>,
+    nan_policy: NanPolicy,
+    column_types: BTreeMap<String, ColumnType>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Cell {
+    Missing,
+    Number(f64),
+    Text(String),
+    // Seconds since the epoch, from typed Parquet columns
+    #[cfg_attr(not(feature = "parquet"), allow(dead_code))]
+    Datetime(f64),
+}
+
+impl DataLoader {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            format: None,
+            target: None,
+            nan_policy: NanPolicy::Error,
+            column_types: BTreeMap::new(),
+        }
+    }
+
+    // Overrides the format taken from the file extension
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    pub fn with_target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    pub fn with_nan_policy(mut self, nan_policy: NanPolicy) -> Self {
+        self.nan_policy = nan_policy;
+        self
+    }
+
+    // Fixes a column's type instead of inferring it, e.g. to treat integer
+    // codes as categories
+    pub fn with_column_type(mut self, name: &str, column_type: ColumnType) -> Self {
+        self.column_types.insert(name.to_string(), column_type);
+        self
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(path = %self.path.display())
+    ))]
+    pub fn load(&self) -> Result<LoadedData, MlError> {
+        let file = std::fs::File::open(&self.path)?;
+        let (headers, rows) = match self.format()? {
+            Format::Csv => read_csv_cells(file)?,
+            Format::JsonLines => read_json_lines(BufReader::new(file))?,
+            Format::Parquet => read_parquet(file)?,
+        };
+        self.build(headers, rows)
+    }
+
+    // Same as `load`, with the data read from `reader` instead of the path,
+    // which then only serves to pick the format
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(path = %self.path.display())
+    ))]
+    pub fn load_reader<R: Read>(&self, mut reader: R) -> Result<LoadedData, MlError> {
+        let (headers, rows) = match self.format()? {
+            Format::Csv => read_csv_cells(reader)?,
+            Format::JsonLines => read_json_lines(BufReader::new(reader))?,
+            Format::Parquet => {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes)?;
+                read_parquet_bytes(bytes)?
+            }
+        };
+        self.build(headers, rows)
+    }
+
+    fn format(&self) -> Result<Format, MlError> {
+        self.format
+            .or_else(|| Format::from_path(&self.path))
+            .ok_or_else(|| MlError::Parse(format!("unknown data format for {}", self.path.display())))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(n_rows = rows.len(), n_columns = headers.len())
+    ))]
+    fn build(&self, headers: Vec<String>, rows: Vec<Vec<Cell>>) -> Result<LoadedData, MlError> {
+        if headers.is_empty() || rows.is_empty() {
+            return Err(MlError::EmptyData);
+        }
+        let target_idx = match &self.target {
+            Some(target) => headers
+                .iter()
+                .position(|h| h == target)
+                .ok_or_else(|| MlError::Parse(format!("target column '{}' not found", target)))?,
+            None => headers.len() - 1,
+        };
+
+        let mut columns = Vec::with_capacity(headers.len());
+        let mut encoded = Vec::with_capacity(headers.len());
+        let mut categories = BTreeMap::new();
+        for (j, name) in headers.iter().enumerate() {
+            let cells: Vec<&Cell> = rows.iter().map(|row| &row[j]).collect();
+            let column_type = match self.column_types.get(name) {
+                Some(&column_type) => column_type,
+                None => infer_type(&cells),
+            };
+            let (values, classes) = encode(&cells, column_type, name)?;
+            let n_missing = values.iter().filter(|v| v.is_none()).count();
+            columns.push(ColumnSchema { name: name.clone(), column_type, n_missing });
+            encoded.push(values);
+            if let Some(classes) = classes {
+                categories.insert(name.clone(), classes);
+            }
+        }
+
+        let keep = self.rows_to_keep(&headers, &encoded, target_idx)?;
+        let feature_idx: Vec<usize> = (0..headers.len()).filter(|&j| j != target_idx).collect();
+        let mut x = Array2::from_shape_fn((keep.len(), feature_idx.len()), |(i, k)| {
+            encoded[feature_idx[k]][keep[i]].unwrap_or(f64::NAN)
+        });
+        let mut imputer = None;
+        if self.nan_policy == NanPolicy::FillMean {
+            let strategies: Vec<ImputeStrategy> = feature_idx
+                .iter()
+                .map(|&j| match columns[j].column_type {
+                    ColumnType::Categorical => ImputeStrategy::MostFrequent,
+                    ColumnType::Numeric | ColumnType::Datetime => ImputeStrategy::Mean,
+                })
+                .collect();
+            let fitted = SimpleImputer::fit(&x, &strategies)?;
+            x = fitted.transform(&x)?;
+            imputer = Some(fitted);
+        }
+        let y: Array1<f64> = keep.iter().map(|&i| encoded[target_idx][i].unwrap()).collect();
+
+        let target_name = headers[target_idx].clone();
+        let labels = categories.remove(&target_name).map(|encoder| encoder.classes);
+        let dataset = Dataset {
+            x,
+            y,
+            feature_names: feature_idx.iter().map(|&j| headers[j].clone()).collect(),
+            target_name,
+        };
+        Ok(LoadedData { dataset, schema: Schema { columns }, labels, categories, imputer })
+    }
+
+    // Indices of the rows that survive the NaN policy
+    fn rows_to_keep(
+        &self,
+        headers: &[String],
+        encoded: &[Vec<Option<f64>>],
+        target_idx: usize,
+    ) -> Result<Vec<usize>, MlError> {
+        let n_rows = encoded[0].len();
+        if self.nan_policy == NanPolicy::Error {
+            let first = (0..n_rows).find_map(|i| (0..headers.len()).find(|&j| encoded[j][i].is_none()).map(|j| (i, j)));
+            if let Some((i, j)) = first {
+                return Err(MlError::InvalidValue {
+                    row: i + 1,
+                    column: headers[j].clone(),
+                    message: "missing value".to_string(),
+                });
+            }
+        }
+        let keep: Vec<usize> = (0..n_rows)
+            .filter(|&i| encoded[target_idx][i].is_some())
+            .filter(|&i| self.nan_policy != NanPolicy::DropRows || encoded.iter().all(|column| column[i].is_some()))
+            .collect();
+        if keep.is_empty() {
+            return Err(MlError::EmptyData);
+        }
+        Ok(keep)
+    }
+}
+
+// Empty fields and the usual spellings of "not available". Any casing of
+// NaN counts, signed or not, since every one of them would parse as a NaN
+// number.
+fn is_missing(field: &str) -> bool {
+    let unsigned = field.strip_prefix(['+', '-']).unwrap_or(field);
+    matches!(field, "" | "NA" | "N/A" | "null" | "NULL") || unsigned.eq_ignore_ascii_case("nan")
+}
+
+fn text_cell(field: &str) -> Cell {
+    let field = field.trim();
+    if is_missing(field) {
+        Cell::Missing
+    } else {
+        Cell::Text(field.to_string())
+    }
+}
+
+fn read_csv_cells<R: Read>(reader: R) -> Result<(Vec<String>, Vec<Vec<Cell>>), MlError> {
+    let (headers, records) = read_records(reader)?;
+    let rows = records.iter().map(|record| record.iter().map(text_cell).collect()).collect();
+    Ok((headers, rows))
+}
+
+// Columns are the keys of all objects in order of first appearance; a key
+// an object lacks is a missing cell. Blank lines are skipped.
+fn read_json_lines<R: BufRead>(reader: R) -> Result<(Vec<String>, Vec<Vec<Cell>>), MlError> {
+    let mut headers: Vec<String> = Vec::new();
+    let mut objects = Vec::new();
+    for (row, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Value::Object(object) = serde_json::from_str(&line)? else {
+            return Err(MlError::InvalidValue {
+                row: row + 1,
+                column: String::new(),
+                message: "expected a JSON object".to_string(),
+            });
+        };
+        for key in object.keys() {
+            if !headers.contains(key) {
+                headers.push(key.clone());
+            }
+        }
+        objects.push((row + 1, object));
+    }
+
+    let mut rows = Vec::with_capacity(objects.len());
+    for (row, object) in objects {
+        let cells = headers
+            .iter()
+            .map(|name| match object.get(name) {
+                None | Some(Value::Null) => Ok(Cell::Missing),
+                Some(Value::Number(n)) => Ok(n.as_f64().map_or(Cell::Missing, Cell::Number)),
+                Some(Value::String(s)) => Ok(text_cell(s)),
+                Some(Value::Bool(b)) => Ok(Cell::Text(b.to_string())),
+                Some(_) => Err(MlError::InvalidValue {
+                    row,
+                    column: name.clone(),
+                    message: "nested values are not supported".to_string(),
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.push(cells);
+    }
+    Ok((headers, rows))
+}
+
+#[cfg(feature = "parquet")]
+fn read_parquet_bytes(bytes: Vec<u8>) -> Result<(Vec<String>, Vec<Vec<Cell>>), MlError> {
+    read_parquet(bytes::Bytes::from(bytes))
+}
+
+#[cfg(feature = "parquet")]
+fn read_parquet<R: parquet::file::reader::ChunkReader + 'static>(
+    source: R,
+) -> Result<(Vec<String>, Vec<Vec<Cell>>), MlError> {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::Field;
+
+    let reader = SerializedFileReader::new(source)?;
+    let headers: Vec<String> = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .root_schema()
+        .get_fields()
+        .iter()
+        .map(|field| field.name().to_string())
+        .collect();
+    let mut rows = Vec::new();
+    for (row, record) in reader.get_row_iter(None)?.enumerate() {
+        let cells = record?
+            .get_column_iter()
+            .map(|(name, field)| match field {
+                Field::Null => Ok(Cell::Missing),
+                Field::Bool(b) => Ok(Cell::Text(b.to_string())),
+                Field::Byte(v) => Ok(Cell::Number(*v as f64)),
+                Field::Short(v) => Ok(Cell::Number(*v as f64)),
+                Field::Int(v) => Ok(Cell::Number(*v as f64)),
+                Field::Long(v) => Ok(Cell::Number(*v as f64)),
+                Field::UByte(v) => Ok(Cell::Number(*v as f64)),
+                Field::UShort(v) => Ok(Cell::Number(*v as f64)),
+                Field::UInt(v) => Ok(Cell::Number(*v as f64)),
+                Field::ULong(v) => Ok(Cell::Number(*v as f64)),
+                Field::Float16(v) => Ok(Cell::Number(v.to_f64())),
+                Field::Float(v) => Ok(Cell::Number(*v as f64)),
+                Field::Double(v) => Ok(Cell::Number(*v)),
+                Field::Str(s) => Ok(text_cell(s)),
+                Field::Date(days) => Ok(Cell::Datetime(*days as f64 * 86_400.0)),
+                Field::TimestampMillis(ms) => Ok(Cell::Datetime(*ms as f64 / 1e3)),
+                Field::TimestampMicros(us) => Ok(Cell::Datetime(*us as f64 / 1e6)),
+                Field::TimeMillis(ms) => Ok(Cell::Number(*ms as f64 / 1e3)),
+                Field::TimeMicros(us) => Ok(Cell::Number(*us as f64 / 1e6)),
+                Field::Decimal(_) | Field::Bytes(_) => Ok(text_cell(&field.to_string())),
+                Field::Group(_) | Field::ListInternal(_) | Field::MapInternal(_) => Err(MlError::InvalidValue {
+                    row: row + 1,
+                    column: name.clone(),
+                    message: "nested values are not supported".to_string(),
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.push(cells);
+    }
+    Ok((headers, rows))
+}
+
+#[cfg(not(feature = "parquet"))]
+fn read_parquet_bytes(_bytes: Vec<u8>) -> Result<(Vec<String>, Vec<Vec<Cell>>), MlError> {
+    Err(MlError::Parse("reading Parquet needs the `parquet` feature".to_string()))
+}
+
+#[cfg(not(feature = "parquet"))]
+fn read_parquet(_file: std::fs::File) -> Result<(Vec<String>, Vec<Vec<Cell>>), MlError> {
+    read_parquet_bytes(Vec::new())
+}
+
+// Numeric when every present cell is a number, datetime when every one is a
+// date, categorical otherwise. A column with no values at all is numeric.
+fn infer_type(cells: &[&Cell]) -> ColumnType {
+    let present = || cells.iter().filter(|c| !matches!(c, Cell::Missing));
+    let numeric = |c: &Cell| match c {
+        Cell::Number(_) => true,
+        Cell::Text(s) => s.parse::<f64>().is_ok(),
+        _ => false,
+    };
+    let datetime = |c: &Cell| match c {
+        Cell::Datetime(_) => true,
+        Cell::Text(s) => parse_datetime(s).is_some(),
+        _ => false,
+    };
+    if present().all(|c| numeric(c)) {
+        ColumnType::Numeric
+    } else if present().all(|c| datetime(c)) {
+        ColumnType::Datetime
+    } else {
+        ColumnType::Categorical
+    }
+}
+
+// One value per cell, None where missing, plus the classes of a categorical
+// column. NaN numbers, e.g. from Parquet, count as missing, so the NaN policy
+// applies to them like any other missing cell.
+fn encode(
+    cells: &[&Cell],
+    column_type: ColumnType,
+    name: &str,
+) -> Result<(Vec<Option<f64>>, Option<CategoricalEncoder>), MlError> {
+    let invalid = |row: usize, message: String| MlError::InvalidValue {
+        row: row + 1,
+        column: name.to_string(),
+        message,
+    };
+    match column_type {
+        ColumnType::Categorical => {
+            let text = |c: &Cell| match c {
+                Cell::Missing => None,
+                Cell::Number(v) | Cell::Datetime(v) if v.is_nan() => None,
+                Cell::Number(v) | Cell::Datetime(v) => Some(v.to_string()),
+                Cell::Text(s) => Some(s.clone()),
+            };
+            let texts: Vec<Option<String>> = cells.iter().map(|c| text(c)).collect();
+            let encoder = CategoricalEncoder::fit(texts.iter().map(Option::as_deref));
+            let codes = encoder.transform(texts.iter().map(Option::as_deref));
+            Ok((codes.iter().map(|&v| (!v.is_nan()).then_some(v)).collect(), Some(encoder)))
+        }
+        ColumnType::Numeric | ColumnType::Datetime => {
+            let values = cells
+                .iter()
+                .enumerate()
+                .map(|(row, cell)| match (cell, column_type) {
+                    (Cell::Missing, _) => Ok(None),
+                    (Cell::Number(v), ColumnType::Numeric) | (Cell::Datetime(v), ColumnType::Datetime) => Ok(Some(*v)),
+                    (Cell::Text(s), ColumnType::Numeric) => {
+                        s.parse::<f64>().map(Some).map_err(|_| invalid(row, format!("invalid number '{}'", s)))
+                    }
+                    (Cell::Text(s), _) => {
+                        parse_datetime(s).map(Some).ok_or_else(|| invalid(row, format!("invalid date '{}'", s)))
+                    }
+                    _ => Err(invalid(row, "value does not match the column type".to_string())),
+                })
+                .map(|value| value.map(|v| v.filter(|v| !v.is_nan())))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((values, None))
+        }
+    }
+}
+
+// Seconds since 1970-01-01T00:00:00Z of `YYYY-MM-DD`, optionally followed
+// by `T` or a space and `HH:MM`, `HH:MM:SS` or `HH:MM:SS.fff`, and a `Z`
+fn parse_datetime(s: &str) -> Option<f64> {
+    let s = s.strip_suffix('Z').unwrap_or(s);
+    let (date, time) = match s.find(['T', ' ']) {
+        Some(i) => (&s[..i], Some(&s[i + 1..])),
+        None => (s, None),
+    };
+    let parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = parts[..] else {
+        return None;
+    };
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    let (year, month, day): (i64, u32, u32) = (year.parse().ok()?, month.parse().ok()?, day.parse().ok()?);
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let seconds = match time {
+        None => 0.0,
+        Some(time) => {
+            let parts: Vec<&str> = time.split(':').collect();
+            let (hour, minute, second) = match parts[..] {
+                [h, m] => (h, m, "0"),
+                [h, m, s] => (h, m, s),
+                _ => return None,
+            };
+            let (hour, minute, second): (u32, u32, f64) =
+                (hour.parse().ok()?, minute.parse().ok()?, second.parse().ok()?);
+            if hour > 23 || minute > 59 || !(0.0..61.0).contains(&second) {
+                return None;
+            }
+            (hour * 3600 + minute * 60) as f64 + second
+        }
+    };
+    Some(days_from_civil(year, month, day) as f64 * 86_400.0 + seconds)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01 of a proleptic Gregorian date (Hinnant's algorithm)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_csv_with_schema() -> Result<(), MlError> {
+        let path = std::env::temp_dir().join("ml_core_loader_test.csv");
+        let text = "sqft,city,listed,price\n\
+                    1200,Lisbon,2024-01-01,200\n\
+                    1500,Porto,2024-01-02T12:00:00Z,250\n\
+                    ,Lisbon,2024-01-03,NA\n\
+                    2000,NA,2024-01-04,320\n";
+        std::fs::write(&path, text)?;
+
+        let loaded = DataLoader::new(&path).with_nan_policy(NanPolicy::FillMean).load()?;
+        let types: Vec<ColumnType> = loaded.schema.columns.iter().map(|c| c.column_type).collect();
+        assert_eq!(types, [ColumnType::Numeric, ColumnType::Categorical, ColumnType::Datetime, ColumnType::Numeric]);
+        assert_eq!(loaded.schema.column("sqft").unwrap().n_missing, 1);
+        // The row without a price is dropped; the missing city gets the most
+        // frequent one
+        assert_eq!(loaded.dataset.y.to_vec(), vec![200.0, 250.0, 320.0]);
+        assert_eq!(loaded.categories["city"].classes(), ["Lisbon", "Porto"]);
+        assert_eq!(loaded.dataset.x.column(1).to_vec(), vec![0.0, 1.0, 0.0]);
+        assert_eq!(loaded.dataset.x[[1, 2]] - loaded.dataset.x[[0, 2]], 1.5 * 86_400.0);
+        assert!(loaded.labels.is_none());
+        assert_eq!(loaded.imputer.unwrap().fill_values[0], (1200.0 + 1500.0 + 2000.0) / 3.0);
+
+        let dropped = DataLoader::new(&path).with_nan_policy(NanPolicy::DropRows).load()?;
+        assert_eq!(dropped.dataset.n_samples(), 2);
+        match DataLoader::new(&path).load() {
+            Err(MlError::InvalidValue { row: 3, column, .. }) => assert_eq!(column, "sqft"),
+            _ => panic!("Expected missing value error"),
+        }
+        // Choosing a categorical target returns its classes
+        let by_city = DataLoader::new(&path).with_target("city").with_nan_policy(NanPolicy::DropRows).load()?;
+        assert_eq!(by_city.labels.unwrap().classes(), ["Lisbon", "Porto"]);
+        assert_eq!(by_city.dataset.feature_names, ["sqft", "listed", "price"]);
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_json_lines() -> Result<(), MlError> {
+        let path = std::env::temp_dir().join("ml_core_loader_test.jsonl");
+        std::fs::write(&path, "{\"x\": 1, \"label\": \"a\"}\n\n{\"x\": 2.5, \"label\": \"b\"}\n{\"label\": \"a\"}\n")?;
+        let loaded = DataLoader::new(&path)
+            .with_target("label")
+            .with_nan_policy(NanPolicy::FillMean)
+            .load()?;
+        assert_eq!(loaded.dataset.x.column(0).to_vec(), vec![1.0, 2.5, 1.75]);
+        assert_eq!(loaded.dataset.y.to_vec(), vec![0.0, 1.0, 0.0]);
+        // Integer codes can be declared categorical
+        let coded = DataLoader::new(&path).with_column_type("x", ColumnType::Categorical).with_target("x");
+        assert_eq!(coded.with_nan_policy(NanPolicy::DropRows).load()?.labels.unwrap().classes(), ["1", "2.5"]);
+        std::fs::remove_file(path)?;
+
+        // The same from memory; objects without keys are no data at all
+        let memory = DataLoader::new("data.jsonl").with_nan_policy(NanPolicy::DropRows);
+        let text = "{\"x\": \"NAN\", \"y\": 1}\n{\"x\": 3, \"y\": 2}\n";
+        assert_eq!(memory.load_reader(text.as_bytes())?.dataset.n_samples(), 1);
+        assert!(matches!(memory.load_reader("{}\n".as_bytes()), Err(MlError::EmptyData)));
+
+        assert_eq!(parse_datetime("1970-01-02"), Some(86_400.0));
+        assert_eq!(parse_datetime("2000-03-01 00:00:30"), Some(951_868_830.0));
+        assert!(parse_datetime("2023-02-29").is_none() && parse_datetime("12/01/2023").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_signed_nan_is_missing() -> Result<(), MlError> {
+        let memory = DataLoader::new("data.csv");
+        let text = "a,y\n1,2\n-nan,3\n4,+NaN\n";
+        match memory.load_reader(text.as_bytes()) {
+            Err(MlError::InvalidValue { row: 2, column, .. }) => assert_eq!(column, "a"),
+            _ => panic!("Expected missing value error"),
+        }
+        let dropped = memory.with_nan_policy(NanPolicy::DropRows).load_reader(text.as_bytes())?;
+        assert_eq!(dropped.dataset.x.column(0).to_vec(), vec![1.0]);
+        assert_eq!(dropped.dataset.y.to_vec(), vec![2.0]);
+        // A NaN that is not spelled out, as a Parquet double would be
+        let cells = [Cell::Number(1.0), Cell::Number(f64::NAN)];
+        let refs: Vec<&Cell> = cells.iter().collect();
+        assert_eq!(encode(&refs, ColumnType::Numeric, "a")?.0, vec![Some(1.0), None]);
+        assert_eq!(encode(&refs, ColumnType::Categorical, "a")?.0, vec![Some(0.0), None]);
+        Ok(())
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_nan_is_missing() -> Result<(), MlError> {
+        use parquet::data_type::DoubleType;
+        use parquet::file::properties::WriterProperties;
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::parser::parse_message_type;
+        use std::sync::Arc;
+
+        let schema = Arc::new(parse_message_type("message data { REQUIRED DOUBLE x; REQUIRED DOUBLE y; }")?);
+        let mut bytes = Vec::new();
+        let mut writer = SerializedFileWriter::new(&mut bytes, schema, Arc::new(WriterProperties::builder().build()))?;
+        let mut row_group = writer.next_row_group()?;
+        for values in [[1.0, f64::NAN, 3.0], [10.0, 20.0, f64::NAN]] {
+            let mut column = row_group.next_column()?.unwrap();
+            column.typed::<DoubleType>().write_batch(&values, None, None)?;
+            column.close()?;
+        }
+        row_group.close()?;
+        writer.close()?;
+
+        let loader = DataLoader::new("data.parquet");
+        assert!(matches!(loader.load_reader(bytes.as_slice()), Err(MlError::InvalidValue { row: 2, .. })));
+        let dropped = loader.with_nan_policy(NanPolicy::DropRows).load_reader(bytes.as_slice())?;
+        assert_eq!(dropped.dataset.x.column(0).to_vec(), vec![1.0]);
+        assert_eq!(dropped.dataset.y.to_vec(), vec![10.0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use ndarray::Array1;
+
+pub fn mean_squared_error(predictions: &Array1<f64>, y: &Array1<f64>) -> f64 {
+    let errors = predictions - y;
+    errors.mapv(|e| e * e).mean().unwrap_or(f64::INFINITY)
+}
+
+pub fn root_mean_squared_error(predictions: &Array1<f64>, y: &Array1<f64>) -> f64 {
+    mean_squared_error(predictions, y).sqrt()
+}
+
+pub fn mean_absolute_error(predictions: &Array1<f64>, y: &Array1<f64>) -> f64 {
+    let errors = predictions - y;
+    errors.mapv(f64::abs).mean().unwrap_or(f64::INFINITY)
+}
+
+pub fn r2_score(predictions: &Array1<f64>, y: &Array1<f64>) -> f64 {
+    let y_mean = y.mean().unwrap_or(0.0);
+    let ss_tot = y.iter()
+        .map(|&y_i| (y_i - y_mean).powi(2))
+        .sum::<f64>();
+    let ss_res = predictions.iter()
+        .zip(y.iter())
+        .map(|(&pred, &actual)| (actual - pred).powi(2))
+        .sum::<f64>();
+
+    1.0 - (ss_res / ss_tot)
+}
+
+// Share of predicted labels equal to the true ones
+pub fn accuracy_score(predictions: &Array1<f64>, y: &Array1<f64>) -> f64 {
+    let correct = predictions.iter().zip(y.iter()).filter(|(p, t)| p == t).count();
+    correct as f64 / y.len() as f64
+}
+
+// Metrics that model selection and reports can rank by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Mse,
+    Rmse,
+    Mae,
+    R2,
+    Accuracy,
+}
+
+impl Metric {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Mse => "MSE",
+            Self::Rmse => "RMSE",
+            Self::Mae => "MAE",
+            Self::R2 => "R²",
+            Self::Accuracy => "Accuracy",
+        }
+    }
+
+    pub fn evaluate(&self, predictions: &Array1<f64>, y: &Array1<f64>) -> f64 {
+        match self {
+            Self::Mse => mean_squared_error(predictions, y),
+            Self::Rmse => root_mean_squared_error(predictions, y),
+            Self::Mae => mean_absolute_error(predictions, y),
+            Self::R2 => r2_score(predictions, y),
+            Self::Accuracy => accuracy_score(predictions, y),
+        }
+    }
+
+    pub fn higher_is_better(&self) -> bool {
+        matches!(self, Self::R2 | Self::Accuracy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regression_metrics() {
+        let y = Array1::from(vec![1.0, 2.0, 3.0]);
+        let predictions = Array1::from(vec![1.0, 2.0, 5.0]);
+
+        assert!((mean_squared_error(&predictions, &y) - 4.0 / 3.0).abs() < 1e-12);
+        assert!((mean_absolute_error(&predictions, &y) - 2.0 / 3.0).abs() < 1e-12);
+        assert!((r2_score(&predictions, &y) - (1.0 - 4.0 / 2.0)).abs() < 1e-12);
+        assert!((r2_score(&y, &y) - 1.0).abs() < 1e-12);
+        assert!((accuracy_score(&predictions, &y) - 2.0 / 3.0).abs() < 1e-12);
+    }
+}
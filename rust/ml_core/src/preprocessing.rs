@@ -0,0 +1,198 @@
+use crate::data::LabelMap;
+use crate::MlError;
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+
+// Standardizes each feature to zero mean and unit (population) variance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandardScaler {
+    pub means: Array1<f64>,
+    pub stds: Array1<f64>,
+}
+
+impl StandardScaler {
+    pub fn fit(x: &Array2<f64>) -> Result<Self, MlError> {
+        if x.nrows() == 0 {
+            return Err(MlError::EmptyData);
+        }
+
+        let mut means = Array1::zeros(x.ncols());
+        let mut stds = Array1::zeros(x.ncols());
+
+        // Calculate mean and std for each feature
+        for (j, column) in x.axis_iter(Axis(1)).enumerate() {
+            means[j] = column.mean().unwrap_or(0.0);
+            let std = column.iter()
+                .map(|&v| (v - means[j]).powi(2))
+                .sum::<f64>()
+                .sqrt() / (column.len() as f64).sqrt();
+            // Constant columns are left centered but unscaled
+            stds[j] = if std > 0.0 { std } else { 1.0 };
+        }
+
+        Ok(Self { means, stds })
+    }
+
+    pub fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, MlError> {
+        if x.ncols() != self.means.len() {
+            return Err(MlError::DimensionMismatch {
+                expected: self.means.len(),
+                found: x.ncols(),
+                context: "number of features in scaler transform",
+            });
+        }
+
+        Ok((x - &self.means) / &self.stds)
+    }
+
+    pub fn fit_transform(x: &Array2<f64>) -> Result<(Self, Array2<f64>), MlError> {
+        let scaler = Self::fit(x)?;
+        let transformed = scaler.transform(x)?;
+        Ok((scaler, transformed))
+    }
+}
+
+// Encodes a text column as class codes 0, 1, … in sorted order of the
+// classes seen in `fit`. Missing values and classes `fit` never saw become
+// NaN, for an imputer to fill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategoricalEncoder {
+    pub classes: LabelMap,
+}
+
+impl CategoricalEncoder {
+    pub fn fit<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = Option<S>>,
+        S: AsRef<str>,
+    {
+        Self { classes: LabelMap::from_labels(values.into_iter().flatten()) }
+    }
+
+    pub fn transform<I, S>(&self, values: I) -> Array1<f64>
+    where
+        I: IntoIterator<Item = Option<S>>,
+        S: AsRef<str>,
+    {
+        values
+            .into_iter()
+            .map(|v| v.and_then(|v| self.classes.encode(v.as_ref())).unwrap_or(f64::NAN))
+            .collect()
+    }
+
+    pub fn classes(&self) -> &[String] {
+        self.classes.classes()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImputeStrategy {
+    Mean,
+    // The smallest of the most frequent values, e.g. for encoded categories
+    MostFrequent,
+}
+
+// Fills NaN entries with a per-column statistic of the values present in
+// `fit`. A column with no values at all is filled with 0.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimpleImputer {
+    pub fill_values: Array1<f64>,
+}
+
+impl SimpleImputer {
+    // One strategy per column of `x`
+    pub fn fit(x: &Array2<f64>, strategies: &[ImputeStrategy]) -> Result<Self, MlError> {
+        if x.nrows() == 0 {
+            return Err(MlError::EmptyData);
+        }
+        if strategies.len() != x.ncols() {
+            return Err(MlError::DimensionMismatch {
+                expected: x.ncols(),
+                found: strategies.len(),
+                context: "number of imputation strategies",
+            });
+        }
+
+        let fill_values = x
+            .axis_iter(Axis(1))
+            .zip(strategies)
+            .map(|(column, strategy)| {
+                let mut present: Vec<f64> = column.iter().copied().filter(|v| !v.is_nan()).collect();
+                if present.is_empty() {
+                    return 0.0;
+                }
+                match strategy {
+                    ImputeStrategy::Mean => present.iter().sum::<f64>() / present.len() as f64,
+                    ImputeStrategy::MostFrequent => {
+                        present.sort_by(f64::total_cmp);
+                        let mut best = (present[0], 0);
+                        for run in present.chunk_by(|a, b| a == b) {
+                            if run.len() > best.1 {
+                                best = (run[0], run.len());
+                            }
+                        }
+                        best.0
+                    }
+                }
+            })
+            .collect();
+        Ok(Self { fill_values })
+    }
+
+    pub fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, MlError> {
+        if x.ncols() != self.fill_values.len() {
+            return Err(MlError::DimensionMismatch {
+                expected: self.fill_values.len(),
+                found: x.ncols(),
+                context: "number of features in imputer transform",
+            });
+        }
+
+        let mut filled = x.clone();
+        for (mut column, &fill) in filled.axis_iter_mut(Axis(1)).zip(self.fill_values.iter()) {
+            column.mapv_inplace(|v| if v.is_nan() { fill } else { v });
+        }
+        Ok(filled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn test_standard_scaler() -> Result<(), MlError> {
+        let x = arr2(&[[1.0, 5.0], [2.0, 5.0], [3.0, 5.0]]);
+        let (scaler, scaled) = StandardScaler::fit_transform(&x)?;
+
+        assert!((scaler.means[0] - 2.0).abs() < 1e-12);
+        assert!(scaled.column(0).mean().unwrap().abs() < 1e-12);
+        // Population std of [1, 2, 3] is sqrt(2/3)
+        assert!((scaler.stds[0] - (2.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        // Constant column is centered without dividing by zero
+        assert!(scaled.column(1).iter().all(|&v| v == 0.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_encoder_and_imputer() -> Result<(), MlError> {
+        let train = [Some("Porto"), Some("Lisbon"), None, Some("Porto")];
+        let encoder = CategoricalEncoder::fit(train);
+        assert_eq!(encoder.classes(), ["Lisbon", "Porto"]);
+        let codes = encoder.transform(train);
+        assert_eq!(codes[0], 1.0);
+        assert!(codes[2].is_nan());
+
+        let x = Array2::from_shape_fn((4, 2), |(i, j)| if j == 0 { codes[i] } else { [1.0, f64::NAN, 4.0, 7.0][i] });
+        let imputer = SimpleImputer::fit(&x, &[ImputeStrategy::MostFrequent, ImputeStrategy::Mean])?;
+        assert_eq!(imputer.fill_values.to_vec(), vec![1.0, 4.0]);
+
+        // Fitted on training data, applied to new rows and unseen classes
+        let new_codes = encoder.transform([Some("Faro"), Some("Lisbon")]);
+        let new = Array2::from_shape_fn((2, 2), |(i, j)| if j == 0 { new_codes[i] } else { f64::NAN });
+        assert_eq!(imputer.transform(&new)?, arr2(&[[1.0, 4.0], [0.0, 4.0]]));
+        assert!(imputer.transform(&Array2::zeros((1, 3))).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+// The seed behind every random draw of a run: row shuffles, weight
+// initialization, bootstrap samples, tree and forest randomness and search
+// sampling. Components that need several independent generators (one per
+// tree, resample or bagged estimator) take `stream(i)` of their own state,
+// so results do not depend on the order in which the parts run.
+//
+// In deterministic mode nothing may depend on the machine either: time
+// budgets are ignored, so a run does the same work however fast it goes.
+// Parallel loops already collect their results in order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RandomState {
+    seed: u64,
+    #[serde(default)]
+    deterministic: bool,
+}
+
+impl RandomState {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, deterministic: false }
+    }
+
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    pub fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed)
+    }
+
+    // The state of the `index`-th part. The seed and index are hashed
+    // together rather than added, so the parts of neighbouring seeds do not
+    // overlap: seed 8 is not seed 7 shifted by one tree. Deterministic mode
+    // carries over to every part.
+    pub fn stream(&self, index: u64) -> Self {
+        Self { seed: splitmix64(splitmix64(self.seed) ^ index), deterministic: self.deterministic }
+    }
+}
+
+// One step of the SplitMix64 generator, a cheap bijective mix of all 64 bits
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+impl From<u64> for RandomState {
+    fn from(seed: u64) -> Self {
+        Self::new(seed)
+    }
+}
+
+// Models, splitters and searches with a seed, so one `RandomState` can be
+// handed to every part of a run. The models' `with_seed` builders do the
+// same for a single component.
+pub trait Seeded {
+    fn set_random_state(&mut self, state: RandomState);
+
+    fn with_random_state(mut self, state: RandomState) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_random_state(state);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_random_state_streams() {
+        let state = RandomState::new(42);
+        let draws = |state: RandomState| -> Vec<u64> { (0..5).map(|_| state.rng().random()).collect() };
+        assert_eq!(draws(state), draws(RandomState::from(42)));
+        assert_ne!(state.stream(3), RandomState::new(45));
+        assert_ne!(state.stream(0).rng().random::<u64>(), state.stream(1).rng().random::<u64>());
+
+        // No part of one seed reappears under a neighbouring seed
+        assert_ne!(state.stream(1), RandomState::new(43).stream(0));
+        let streams: std::collections::HashSet<u64> =
+            (0..64).flat_map(|seed| (0..64).map(move |i| RandomState::new(seed).stream(i).seed())).collect();
+        assert_eq!(streams.len(), 64 * 64);
+
+        let deterministic = state.with_deterministic(true);
+        assert!(deterministic.stream(2).is_deterministic() && !state.stream(2).is_deterministic());
+        assert_eq!(deterministic.stream(2).seed(), state.stream(2).seed());
+    }
+}
@@ -0,0 +1,67 @@
+use crate::random::{RandomState, Seeded};
+use crate::MlError;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+// (train, held-out) row indices
+pub type Fold = (Vec<usize>, Vec<usize>);
+
+// One fold per split of `n_samples` rows. Rows are shuffled with `seed` and
+// dealt out in turn, so fold sizes differ by at most one and every row is
+// held out exactly once.
+pub fn k_fold_indices(n_samples: usize, n_folds: usize, seed: u64) -> Result<Vec<Fold>, MlError> {
+    if n_folds < 2 || n_folds > n_samples {
+        return Err(MlError::InvalidParameter("n_folds must be between 2 and the number of samples"));
+    }
+    let mut order: Vec<usize> = (0..n_samples).collect();
+    order.shuffle(&mut StdRng::seed_from_u64(seed));
+    let folds = (0..n_folds)
+        .map(|fold| {
+            let (held_out, train): (Vec<usize>, Vec<usize>) = (0..n_samples).partition(|&i| i % n_folds == fold);
+            (train.into_iter().map(|i| order[i]).collect(), held_out.into_iter().map(|i| order[i]).collect())
+        })
+        .collect();
+    Ok(folds)
+}
+
+// Fold settings, for utilities that take the split as one argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KFold {
+    pub n_folds: usize,
+    // Seed for shuffling rows into folds
+    pub seed: u64,
+}
+
+impl KFold {
+    pub fn new(n_folds: usize, seed: u64) -> Self {
+        Self { n_folds, seed }
+    }
+
+    pub fn split(&self, n_samples: usize) -> Result<Vec<Fold>, MlError> {
+        k_fold_indices(n_samples, self.n_folds, self.seed)
+    }
+}
+
+impl Seeded for KFold {
+    fn set_random_state(&mut self, state: RandomState) {
+        self.seed = state.seed();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_k_fold_indices() -> Result<(), MlError> {
+        let folds = k_fold_indices(10, 3, 7)?;
+        let mut held_out: Vec<usize> = folds.iter().flat_map(|(_, held_out)| held_out.clone()).collect();
+        held_out.sort();
+        assert_eq!(held_out, (0..10).collect::<Vec<_>>());
+        assert!(folds.iter().all(|(train, held_out)| train.len() + held_out.len() == 10 && held_out.len() >= 3));
+        assert_eq!(KFold::new(3, 7).split(10)?, folds);
+        assert!(k_fold_indices(10, 1, 0).is_err() && k_fold_indices(3, 4, 0).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use crate::metrics::{accuracy_score, r2_score};
+use crate::MlError;
+use ndarray::{Array1, Array2};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+// These two traits are the estimator interface the generic tools (pipelines,
+// resampling, ensembles, calibration) are written against. Input and output
+// types default to f64 matrices and vectors, which is what almost every model
+// works in; a model can implement them for other data types as well, such as
+// `LinearRegression<f32>` on f32 arrays.
+
+// Anything that maps inputs to one prediction per row. Object safe, so
+// heterogeneous models can be handled as `&dyn Predictor`.
+pub trait Predictor<X = Array2<f64>, Y = Array1<f64>> {
+    fn predict(&self, x: &X) -> Result<Y, MlError>;
+}
+
+// Models that can be (re)fitted from scratch on inputs and targets, with all
+// hyperparameters taken from the model itself. Fitting twice on the same data
+// gives the same model, which is what resampling code relies on.
+pub trait Fit<X = Array2<f64>, Y = Array1<f64>> {
+    fn fit(&mut self, x: &X, y: &Y) -> Result<(), MlError>;
+}
+
+// Iterative models whose training can be continued where it stopped, which
+// budget-based tuners use to give promising candidates more epochs without
+// starting over. An unfitted model starts from its initial parameters.
+pub trait WarmStart: Fit {
+    fn fit_more(&mut self, x: &Array2<f64>, y: &Array1<f64>, epochs: usize) -> Result<(), MlError>;
+}
+
+// Two-class classifiers with a continuous score that grows with the chance of
+// the larger label: a margin, log-odds or an uncalibrated probability. This
+// is what probability calibration maps onto [0, 1].
+pub trait BinaryScore {
+    fn binary_score(&self, x: &Array2<f64>) -> Result<Array1<f64>, MlError>;
+}
+
+// Larger-label column of a two-column `predict_proba` output
+pub fn positive_column(proba: Array2<f64>) -> Result<Array1<f64>, MlError> {
+    if proba.ncols() != 2 {
+        return Err(MlError::InvalidParameter("binary score needs exactly two classes"));
+    }
+    Ok(proba.column(1).to_owned())
+}
+
+// One-call quality measure on labelled data: R² for regressors and accuracy
+// for classifiers, chosen by `is_classifier`. Models that handle both tasks
+// answer from their own settings.
+pub trait Score: Predictor {
+    fn is_classifier(&self) -> bool {
+        false
+    }
+
+    fn score(&self, x: &Array2<f64>, y: &Array1<f64>) -> Result<f64, MlError> {
+        if x.nrows() != y.len() {
+            return Err(MlError::DimensionMismatch {
+                expected: x.nrows(),
+                found: y.len(),
+                context: "number of samples in X and y",
+            });
+        }
+        if y.is_empty() {
+            return Err(MlError::EmptyData);
+        }
+        let predictions = self.predict(x)?;
+        Ok(if self.is_classifier() { accuracy_score(&predictions, y) } else { r2_score(&predictions, y) })
+    }
+}
+
+// Hyperparameters by name, as JSON values so they can be logged or stored
+// next to experiment results
+pub type ParamMap = BTreeMap<String, Value>;
+
+// Uniform read and write access to a model's hyperparameters, so search and
+// tracking code can handle any model. Names match the model's `with_*`
+// builders; fitted state is not included.
+pub trait Params {
+    fn get_params(&self) -> ParamMap;
+
+    // Checks the value before storing it; unknown names are an error
+    fn set_param(&mut self, name: &str, value: &Value) -> Result<(), MlError>;
+
+    // All or nothing: on error the model is left unchanged
+    fn set_params(&mut self, params: &ParamMap) -> Result<(), MlError>
+    where
+        Self: Clone,
+    {
+        let mut updated = self.clone();
+        for (name, value) in params {
+            updated.set_param(name, value)?;
+        }
+        *self = updated;
+        Ok(())
+    }
+}
+
+pub fn param_error(name: &str, message: &str) -> MlError {
+    MlError::Parameter { name: name.to_string(), message: message.to_string() }
+}
+
+pub fn unknown_param(name: &str) -> MlError {
+    param_error(name, "unknown parameter")
+}
+
+// Finite and at least `min`
+pub fn param_f64(name: &str, value: &Value, min: f64) -> Result<f64, MlError> {
+    match value.as_f64() {
+        Some(v) if v.is_finite() && v >= min => Ok(v),
+        Some(_) => Err(param_error(name, &format!("must be finite and at least {}", min))),
+        None => Err(param_error(name, "expected a number")),
+    }
+}
+
+pub fn param_usize(name: &str, value: &Value, min: usize) -> Result<usize, MlError> {
+    match value.as_u64() {
+        Some(v) if v as usize >= min => Ok(v as usize),
+        Some(_) => Err(param_error(name, &format!("must be at least {}", min))),
+        None => Err(param_error(name, "expected a non-negative integer")),
+    }
+}
+
+// A fittable predictor that can be copied behind a box, so ensembles can hold
+// heterogeneous models as `Box<dyn Estimator>` and refit fresh copies of them.
+// Implemented for every `Fit + Predictor + Clone` type.
+pub trait Estimator: Fit + Predictor {
+    fn boxed_clone(&self) -> Box<dyn Estimator>;
+}
+
+impl<T: Fit + Predictor + Clone + 'static> Estimator for T {
+    fn boxed_clone(&self) -> Box<dyn Estimator> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Estimator> {
+    fn clone(&self) -> Self {
+        self.as_ref().boxed_clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Default)]
+    struct Shift {
+        offset: f64,
+    }
+
+    impl Predictor for Shift {
+        fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, MlError> {
+            Ok(x.column(0).mapv(|v| v + self.offset))
+        }
+    }
+
+    impl Score for Shift {}
+
+    impl Params for Shift {
+        fn get_params(&self) -> ParamMap {
+            ParamMap::from([("offset".to_string(), Value::from(self.offset))])
+        }
+
+        fn set_param(&mut self, name: &str, value: &Value) -> Result<(), MlError> {
+            match name {
+                "offset" => self.offset = param_f64(name, value, 0.0)?,
+                _ => return Err(unknown_param(name)),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_traits_without_a_model_crate() -> Result<(), MlError> {
+        let x = Array2::from_shape_fn((4, 1), |(i, _)| i as f64);
+        let mut shift = Shift::default();
+        assert_eq!(shift.score(&x, &x.column(0).to_owned())?, 1.0);
+
+        let params = ParamMap::from([("offset".to_string(), Value::from(2.0)), ("scale".to_string(), Value::from(1))]);
+        assert!(shift.set_params(&params).is_err());
+        assert_eq!(shift.get_params()["offset"], 0.0);
+        shift.set_param("offset", &Value::from(2.0))?;
+        assert_eq!(shift.predict(&x)?[3], 5.0);
+        Ok(())
+    }
+}